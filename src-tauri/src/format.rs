@@ -0,0 +1,154 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+// Human-readable sizes shared by the backend and the UI (format_bytes command).
+// binary: powers of 1024 with IEC units (KiB, MiB...), otherwise powers of 1000 (KB, MB...).
+// Below one unit the exact count is shown ("999 B"), above it one decimal ("1.0 KiB").
+const BINARY_UNITS: [&str; 6] = ["B", "KiB", "MiB", "GiB", "TiB", "PiB"];
+const DECIMAL_UNITS: [&str; 6] = ["B", "KB", "MB", "GB", "TB", "PB"];
+
+pub fn format_bytes(bytes: u64, binary: bool) -> String {
+    format_bytes_with_separator(bytes, binary, '.')
+}
+
+pub fn format_bytes_with_separator(bytes: u64, binary: bool, decimal_separator: char) -> String {
+    let (base, units) = if binary { (1024.0, BINARY_UNITS) } else { (1000.0, DECIMAL_UNITS) };
+    if (bytes as f64) < base {
+        return format!("{} B", bytes);
+    }
+
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= base && unit < units.len() - 1 {
+        value /= base;
+        unit += 1;
+    }
+    // 1023.96 KiB would print as "1024.0 KiB": carry into the next unit instead
+    if (value * 10.0).round() / 10.0 >= base && unit < units.len() - 1 {
+        value /= base;
+        unit += 1;
+    }
+
+    let text = format!("{:.1} {}", value, units[unit]);
+    if decimal_separator == '.' {
+        text
+    } else {
+        text.replacen('.', &decimal_separator.to_string(), 1)
+    }
+}
+
+// Comma for the languages that write 1,5 Go rather than 1.5 GB
+pub fn decimal_separator(locale: &str) -> char {
+    match locale {
+        "fr" | "de" | "es" | "it" | "pt" | "nl" | "ru" | "pl" | "sv" | "da" | "nb" | "fi" | "cs" | "tr" => ',',
+        _ => '.',
+    }
+}
+
+// Base of the *_gb fields the commands report: GiB (2^30, the historical default) or
+// GB (10^9, like Finder). Process-wide so every producer of sizes agrees without threading it.
+static BINARY_BASE: AtomicBool = AtomicBool::new(true);
+
+pub fn set_binary_base(binary: bool) {
+    BINARY_BASE.store(binary, Ordering::Relaxed);
+}
+
+pub fn binary_base() -> bool {
+    BINARY_BASE.load(Ordering::Relaxed)
+}
+
+// "binary" / "decimal", as stored in the units setting and echoed in responses
+pub fn unit_base() -> &'static str {
+    if binary_base() { "binary" } else { "decimal" }
+}
+
+pub fn parse_unit_base(value: &str) -> Option<bool> {
+    match value {
+        "binary" => Some(true),
+        "decimal" => Some(false),
+        _ => None,
+    }
+}
+
+pub fn gb_divisor() -> f64 {
+    divisor(binary_base())
+}
+
+fn divisor(binary: bool) -> f64 {
+    if binary { 1_073_741_824.0 } else { 1_000_000_000.0 }
+}
+
+// A *_gb value recorded under `unit_base` (e.g. in a snapshot taken before the units
+// setting changed), expressed in the current base so it can be compared with fresh values
+pub fn gb_in_current_base(value: f64, unit_base: &str) -> f64 {
+    let recorded = parse_unit_base(unit_base).unwrap_or(true);
+    value * divisor(recorded) / gb_divisor()
+}
+
+pub fn to_gb(bytes: u64) -> f64 {
+    bytes as f64 / gb_divisor()
+}
+
+// "45s", "3m 12s", "2h 5m", "4d 3h": same steps as the process panel's uptime
+pub fn format_duration(secs: u64) -> String {
+    match secs {
+        0..=59 => format!("{}s", secs),
+        60..=3599 => format!("{}m {}s", secs / 60, secs % 60),
+        3600..=86_399 => format!("{}h {}m", secs / 3600, secs % 3600 / 60),
+        _ => format!("{}d {}h", secs / 86_400, secs % 86_400 / 3600),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exact_bytes_below_one_unit() {
+        assert_eq!(format_bytes(999, true), "999 B");
+        assert_eq!(format_bytes(999, false), "999 B");
+        assert_eq!(format_bytes(1000, true), "1000 B");
+        assert_eq!(format_bytes(1023, true), "1023 B");
+    }
+
+    #[test]
+    fn one_unit_boundaries() {
+        assert_eq!(format_bytes(1000, false), "1.0 KB");
+        assert_eq!(format_bytes(1023, false), "1.0 KB");
+        assert_eq!(format_bytes(1024, true), "1.0 KiB");
+        assert_eq!(format_bytes(1024, false), "1.0 KB");
+    }
+
+    #[test]
+    fn rounding_carries_into_next_unit() {
+        // 1023.96 KiB would otherwise print as "1024.0 KiB"
+        assert_eq!(format_bytes(1_048_535, true), "1.0 MiB");
+        assert_eq!(format_bytes(999_960, false), "1.0 MB");
+        assert_eq!(format_bytes(1_048_371, true), "1023.8 KiB");
+    }
+
+    #[test]
+    fn largest_unit_is_pib() {
+        assert_eq!(format_bytes(1u64 << 50, true), "1.0 PiB");
+        assert_eq!(format_bytes(u64::MAX, true), "16384.0 PiB");
+        assert_eq!(format_bytes(u64::MAX, false), "18446.7 PB");
+    }
+
+    #[test]
+    fn locale_decimal_separator() {
+        assert_eq!(decimal_separator("fr"), ',');
+        assert_eq!(decimal_separator("en"), '.');
+        assert_eq!(format_bytes_with_separator(1536, true, ','), "1,5 KiB");
+        assert_eq!(format_bytes_with_separator(999, true, ','), "999 B");
+    }
+
+    #[test]
+    fn gb_values_convert_across_bases() {
+        // Only test touching the process-wide base
+        set_binary_base(false);
+        assert!((gb_in_current_base(1.0, "binary") - 1.073_741_824).abs() < 1e-9);
+        assert!((gb_in_current_base(2.5, "decimal") - 2.5).abs() < 1e-9);
+        set_binary_base(true);
+        assert!((gb_in_current_base(1.073_741_824, "decimal") - 1.0).abs() < 1e-9);
+        assert!((gb_in_current_base(3.0, "binary") - 3.0).abs() < 1e-9);
+    }
+}
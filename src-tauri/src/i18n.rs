@@ -0,0 +1,53 @@
+// Strings emitted by the Rust side (tray menu, notifications), from JSON bundled at build time.
+// Keys missing in a locale fall back to English, then to the key itself.
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+pub const DEFAULT_LOCALE: &str = "en";
+
+const BUNDLED: &[(&str, &str)] = &[
+    ("en", include_str!("../locales/en.json")),
+    ("fr", include_str!("../locales/fr.json")),
+];
+
+fn catalogs() -> &'static HashMap<&'static str, HashMap<String, String>> {
+    static CATALOGS: OnceLock<HashMap<&'static str, HashMap<String, String>>> = OnceLock::new();
+    CATALOGS.get_or_init(|| {
+        BUNDLED
+            .iter()
+            .map(|(locale, json)| (*locale, serde_json::from_str(json).unwrap_or_default()))
+            .collect()
+    })
+}
+
+pub fn supported_locales() -> Vec<&'static str> {
+    BUNDLED.iter().map(|(locale, _)| *locale).collect()
+}
+
+// "fr-FR", "fr_FR.UTF-8" or "FR" -> "fr"; None when the language has no bundled translation
+pub fn normalize_locale(locale: &str) -> Option<&'static str> {
+    let language = locale
+        .split(['-', '_', '.', '@'])
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_lowercase();
+    BUNDLED
+        .iter()
+        .map(|(locale, _)| *locale)
+        .find(|locale| *locale == language)
+}
+
+// Replaces {placeholder}s with args
+pub fn translate(locale: &str, key: &str, args: &[(&str, &str)]) -> String {
+    let catalogs = catalogs();
+    let template = catalogs
+        .get(locale)
+        .and_then(|c| c.get(key))
+        .or_else(|| catalogs.get(DEFAULT_LOCALE).and_then(|c| c.get(key)))
+        .map(String::as_str)
+        .unwrap_or(key);
+    args.iter().fold(template.to_string(), |text, (name, value)| {
+        text.replace(&format!("{{{}}}", name), value)
+    })
+}
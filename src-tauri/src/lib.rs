@@ -1,5 +1,12 @@
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+mod format;
+mod i18n;
+mod render;
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tauri::{
     image::Image,
     menu::{CheckMenuItem, Menu, MenuItem},
@@ -14,6 +21,61 @@ use sysinfo::Disks;
 // Settings keys for persistent storage
 const SETTINGS_AUTOSTART: &str = "settings_autostart";
 const SETTINGS_MINIMIZE_TO_TRAY: &str = "settings_minimize_to_tray";
+const SETTINGS_ALWAYS_ON_TOP: &str = "settings_always_on_top";
+const SETTINGS_MEMORY_GUARD: &str = "settings_memory_guard";
+const SETTINGS_SWAP_ALERT: &str = "settings_swap_alert";
+const SETTINGS_METRIC_TRAYS: &str = "settings_metric_trays";
+const SETTINGS_TERMINAL_APP: &str = "settings_terminal_app";
+const SETTINGS_SERVER_STATUS_STALL_SECS: &str = "settings_server_status_stall_secs";
+const SETTINGS_SSH_COMMAND_TEMPLATE: &str = "settings_ssh_command_template";
+const SETTINGS_SCAN_THREADS: &str = "settings_scan_threads";
+const SETTINGS_UPDATE_CHECK: &str = "settings_update_check";
+const SETTINGS_PROFILING_ENABLED: &str = "settings_profiling_enabled";
+const SETTINGS_DISK_ALERT: &str = "settings_disk_alert";
+const SETTINGS_HIDDEN_PROCESS_NAMES: &str = "settings_hidden_process_names";
+const SETTINGS_COMMAND_TIMEOUT_SECS: &str = "settings_command_timeout_secs";
+const SETTINGS_LOCALE: &str = "settings_locale";
+const SETTINGS_ORGANIZE_RULES: &str = "settings_organize_rules";
+const SETTINGS_CLIPBOARD_HISTORY: &str = "settings_clipboard_history";
+const SETTINGS_SSH_BINARY: &str = "settings_ssh_binary";
+const SETTINGS_UNITS: &str = "settings_units";
+const SETTINGS_HIGH_LOAD_HOOK: &str = "settings_high_load_hook";
+const SETTINGS_TRAY_MENU: &str = "settings_tray_menu";
+const SETTINGS_BATTERY_ALERTS: &str = "settings_battery_alerts";
+const SETTINGS_MONITORING_PAUSED: &str = "settings_monitoring_paused";
+const SETTINGS_RUN_COMMAND_ALLOWLIST: &str = "settings_run_command_allowlist";
+
+// Every backend key above: compact_settings drops other settings_* keys as leftovers.
+// Keys without the prefix belong to the frontend and are never touched.
+const KNOWN_SETTINGS_KEYS: &[&str] = &[
+    SETTINGS_AUTOSTART,
+    SETTINGS_MINIMIZE_TO_TRAY,
+    SETTINGS_ALWAYS_ON_TOP,
+    SETTINGS_MEMORY_GUARD,
+    SETTINGS_SWAP_ALERT,
+    SETTINGS_METRIC_TRAYS,
+    SETTINGS_TERMINAL_APP,
+    SETTINGS_SERVER_STATUS_STALL_SECS,
+    SETTINGS_SSH_COMMAND_TEMPLATE,
+    SETTINGS_SCAN_THREADS,
+    SETTINGS_UPDATE_CHECK,
+    SETTINGS_PROFILING_ENABLED,
+    SETTINGS_DISK_ALERT,
+    SETTINGS_HIDDEN_PROCESS_NAMES,
+    SETTINGS_COMMAND_TIMEOUT_SECS,
+    SETTINGS_LOCALE,
+    SETTINGS_ORGANIZE_RULES,
+    SETTINGS_CLIPBOARD_HISTORY,
+    SETTINGS_SSH_BINARY,
+    SETTINGS_UNITS,
+    SETTINGS_HIGH_LOAD_HOOK,
+    SETTINGS_TRAY_MENU,
+    SETTINGS_BATTERY_ALERTS,
+    SETTINGS_MONITORING_PAUSED,
+    SETTINGS_RUN_COMMAND_ALLOWLIST,
+];
+const BACKEND_SETTINGS_PREFIX: &str = "settings_";
+const SETTINGS_BACKUP_FILE: &str = "settings.backup.json";
 
 // Badge radius and color
 const BADGE_RADIUS: u32 = 6;
@@ -21,10 +83,16 @@ const BADGE_COLOR: [u8; 4] = [255, 59, 48, 255]; // Red color (RGBA)
 
 // State to hold references to tray menu items and settings state
 struct TrayMenuState {
+    show: MenuItem<Wry>,
     autostart: CheckMenuItem<Wry>,
     minimize_to_tray: CheckMenuItem<Wry>,
+    always_on_top: CheckMenuItem<Wry>,
+    pause_monitoring: CheckMenuItem<Wry>,
+    quit: MenuItem<Wry>,
+    metrics: Mutex<Vec<(MenuEntry, MenuItem<Wry>)>>, // Live-metric items of the current menu
     autostart_enabled: AtomicBool,
     minimize_enabled: AtomicBool,
+    always_on_top_enabled: AtomicBool,
 }
 
 // Store original icon for badge overlay
@@ -34,6 +102,193 @@ struct TrayIconState {
     height: u32,
 }
 
+// Central shutdown signal: background threads sleep on it so they stop promptly on quit,
+// and child processes that must not outlive the app (ssh streams) are registered here.
+// Also carries the "pause monitoring" switch the background samplers wait on.
+#[derive(Default)]
+struct Shutdown {
+    stopping: AtomicBool,
+    paused: AtomicBool,
+    lock: Mutex<()>,
+    wake: std::sync::Condvar,
+    children: Mutex<Vec<Arc<Mutex<std::process::Child>>>>,
+}
+
+impl Shutdown {
+    fn is_stopping(&self) -> bool {
+        self.stopping.load(Ordering::SeqCst)
+    }
+
+    // Sleep between monitor iterations; returns false (early) once shutdown has started
+    fn sleep(&self, duration: Duration) -> bool {
+        let deadline = Instant::now() + duration;
+        let Ok(mut guard) = self.lock.lock() else { return !self.is_stopping() };
+        while !self.is_stopping() {
+            let now = Instant::now();
+            if now >= deadline {
+                return true;
+            }
+            guard = match self.wake.wait_timeout(guard, deadline - now) {
+                Ok((guard, _)) => guard,
+                Err(_) => return !self.is_stopping(),
+            };
+        }
+        false
+    }
+
+    fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::SeqCst)
+    }
+
+    // Resuming wakes the samplers blocked in sampler_sleep
+    fn set_paused(&self, paused: bool) {
+        self.paused.store(paused, Ordering::SeqCst);
+        if let Ok(_guard) = self.lock.lock() {
+            self.wake.notify_all();
+        }
+    }
+
+    // sleep for background samplers: while monitoring is paused, keeps waiting until it
+    // resumes. Commands and user-started watchers (exit watch, throttle) use plain sleep.
+    fn sampler_sleep(&self, duration: Duration) -> bool {
+        if !self.sleep(duration) {
+            return false;
+        }
+        let Ok(mut guard) = self.lock.lock() else { return !self.is_stopping() };
+        while self.is_paused() && !self.is_stopping() {
+            guard = match self.wake.wait(guard) {
+                Ok(guard) => guard,
+                Err(_) => return !self.is_stopping(),
+            };
+        }
+        !self.is_stopping()
+    }
+
+    // Wake all sleepers and kill registered children. Returns false if already triggered.
+    fn trigger(&self) -> bool {
+        if self.stopping.swap(true, Ordering::SeqCst) {
+            return false;
+        }
+        if let Ok(_guard) = self.lock.lock() {
+            self.wake.notify_all();
+        }
+        if let Ok(children) = self.children.lock() {
+            for child in children.iter() {
+                if let Ok(mut child) = child.lock() {
+                    kill_process_tree(&mut child);
+                }
+            }
+        }
+        true
+    }
+
+    fn track_child(&self, child: &Arc<Mutex<std::process::Child>>) {
+        if let Ok(mut children) = self.children.lock() {
+            children.push(child.clone());
+        }
+    }
+
+    fn untrack_child(&self, child: &Arc<Mutex<std::process::Child>>) {
+        if let Ok(mut children) = self.children.lock() {
+            children.retain(|c| !Arc::ptr_eq(c, child));
+        }
+    }
+}
+
+// Managed on the builder, so always available
+fn shutdown_signal(app: &AppHandle) -> Arc<Shutdown> {
+    app.state::<Arc<Shutdown>>().inner().clone()
+}
+
+// Stop background threads, kill child processes and flush settings before the app exits
+fn begin_shutdown(app: &AppHandle) {
+    if !shutdown_signal(app).trigger() {
+        return;
+    }
+    resume_throttled_processes(app);
+    if let Ok(store) = app.store("settings.json") {
+        let _ = store.save();
+    }
+}
+
+// Alerts raised by background monitors since the window was last shown
+#[derive(Default)]
+struct AlertState {
+    count: AtomicU32,
+}
+
+// Structured command error: serialized as { kind, message } so the frontend can branch on kind.
+// InvalidInput is a bad argument (a file where a directory is expected, an unknown option);
+// Parse is unreadable data coming back from the system.
+#[derive(Debug, serde::Serialize)]
+#[serde(tag = "kind", content = "message")]
+enum OrganizerError {
+    NotFound(String),
+    PermissionDenied(String),
+    Unsupported(String),
+    InvalidInput(String),
+    Io(String),
+    Parse(String),
+}
+
+impl std::fmt::Display for OrganizerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OrganizerError::NotFound(msg) => write!(f, "Not found: {}", msg),
+            OrganizerError::PermissionDenied(msg) => write!(f, "Permission denied: {}", msg),
+            OrganizerError::Unsupported(msg) => write!(f, "Unsupported: {}", msg),
+            OrganizerError::InvalidInput(msg) => write!(f, "Invalid input: {}", msg),
+            OrganizerError::Io(msg) => write!(f, "I/O error: {}", msg),
+            OrganizerError::Parse(msg) => write!(f, "Parse error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for OrganizerError {}
+
+impl From<std::io::Error> for OrganizerError {
+    fn from(e: std::io::Error) -> Self {
+        match e.kind() {
+            std::io::ErrorKind::NotFound => OrganizerError::NotFound(e.to_string()),
+            std::io::ErrorKind::PermissionDenied => OrganizerError::PermissionDenied(e.to_string()),
+            std::io::ErrorKind::InvalidInput | std::io::ErrorKind::NotADirectory => {
+                OrganizerError::InvalidInput(e.to_string())
+            }
+            _ => OrganizerError::Io(e.to_string()),
+        }
+    }
+}
+
+impl From<std::num::ParseIntError> for OrganizerError {
+    fn from(e: std::num::ParseIntError) -> Self {
+        OrganizerError::Parse(e.to_string())
+    }
+}
+
+impl From<std::num::ParseFloatError> for OrganizerError {
+    fn from(e: std::num::ParseFloatError) -> Self {
+        OrganizerError::Parse(e.to_string())
+    }
+}
+
+impl From<serde_json::Error> for OrganizerError {
+    fn from(e: serde_json::Error) -> Self {
+        OrganizerError::Parse(e.to_string())
+    }
+}
+
+impl From<tauri::Error> for OrganizerError {
+    fn from(e: tauri::Error) -> Self {
+        OrganizerError::Io(e.to_string())
+    }
+}
+
+impl<T> From<std::sync::PoisonError<T>> for OrganizerError {
+    fn from(e: std::sync::PoisonError<T>) -> Self {
+        OrganizerError::Io(e.to_string())
+    }
+}
+
 #[tauri::command]
 fn greet(name: &str) -> String {
     format!("Hello, {}! You've been greeted from Rust!", name)
@@ -43,15 +298,120 @@ fn greet(name: &str) -> String {
 struct DiskSpace {
     free_gb: f64,
     total_gb: f64,
+    unit_base: &'static str, // "binary" (GiB) or "decimal" (GB), from the units setting
 }
 
-#[derive(serde::Serialize)]
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
 struct ProcessMemory {
     pid: u32,
     name: String,
     cwd: Option<String>, // Current working directory (last segment only)
     memory_mb: f64,      // Resident memory (in RAM)
     virtual_mb: f64,     // Virtual memory (includes swap)
+    start_time: u64,     // Unix timestamp the process started at
+    cpu_usage: Option<f32>,       // Per core, Full detail level only
+    disk_read_bytes: Option<u64>, // Since process start, Full detail level only
+    disk_write_bytes: Option<u64>,
+}
+
+// How much a process refresh collects, so callers only pay for what they render.
+// Light: name + memory. Full: also CPU (two samples), disk I/O and cwd.
+#[derive(Clone, Copy, PartialEq, Default, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum DetailLevel {
+    #[default]
+    Light,
+    Full,
+}
+
+impl DetailLevel {
+    fn refresh_kind(self) -> sysinfo::ProcessRefreshKind {
+        use sysinfo::{ProcessRefreshKind, UpdateKind};
+        match self {
+            DetailLevel::Light => ProcessRefreshKind::new().with_memory(),
+            DetailLevel::Full => ProcessRefreshKind::new()
+                .with_memory()
+                .with_cpu()
+                .with_disk_usage()
+                .with_cwd(UpdateKind::OnlyIfNotSet),
+        }
+    }
+
+    // One pass: Full CPU usage is only meaningful on a pass that follows an earlier one by
+    // MINIMUM_CPU_UPDATE_INTERVAL (see with_shared_processes)
+    fn refresh(self, sys: &mut sysinfo::System) {
+        sys.refresh_processes_specifics(sysinfo::ProcessesToUpdate::All, true, self.refresh_kind());
+    }
+}
+
+// One System shared by the process/memory/CPU commands instead of System::new() per call.
+// The first full process enumeration is the slow part (every process is read from scratch),
+// so setup runs it in a background thread and later calls only refresh what they read.
+// Thread safety: a single Mutex, locked by with_shared_system for one targeted refresh + read.
+// The lock is never held across an await, a sleep or while taking another state lock, so
+// calls are serialized behind refreshes only and cannot deadlock. CPU samples use
+// with_shared_sample, which waits between its two refreshes with the lock released.
+// A poisoned lock is recovered: the System is only a cache and the next refresh overwrites it.
+struct SharedSystem {
+    sys: Mutex<sysinfo::System>,
+}
+
+fn spawn_system_warmup(app: AppHandle) {
+    std::thread::spawn(move || {
+        with_shared_system(&app, |sys| {
+            sys.refresh_memory();
+            sys.refresh_cpu_usage();
+            DetailLevel::Light.refresh(sys);
+        });
+    });
+}
+
+// Falls back to a fresh System when the shared one is not managed (yet)
+fn with_shared_system<T>(app: &AppHandle, f: impl FnOnce(&mut sysinfo::System) -> T) -> T {
+    match app.try_state::<Arc<SharedSystem>>() {
+        Some(state) => {
+            let mut sys = state.sys.lock().unwrap_or_else(|e| e.into_inner());
+            f(&mut sys)
+        }
+        None => f(&mut sysinfo::System::new()),
+    }
+}
+
+// CPU usage is a delta between two refreshes: prime, wait with the lock released, then
+// refresh again and read under one lock. A refresh by another caller in between only
+// shortens the measured window.
+fn with_shared_sample<T>(
+    app: &AppHandle,
+    refresh: impl Fn(&mut sysinfo::System),
+    read: impl FnOnce(&mut sysinfo::System) -> T,
+) -> T {
+    with_shared_system(app, |sys| refresh(sys));
+    std::thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+    with_shared_system(app, |sys| {
+        refresh(sys);
+        read(sys)
+    })
+}
+
+// Process refresh at the given detail level, then read (Full samples CPU first)
+fn with_shared_processes<T>(app: &AppHandle, detail: DetailLevel, read: impl FnOnce(&mut sysinfo::System) -> T) -> T {
+    match detail {
+        DetailLevel::Light => with_shared_system(app, |sys| {
+            detail.refresh(sys);
+            read(sys)
+        }),
+        DetailLevel::Full => with_shared_sample(app, |sys| detail.refresh(sys), read),
+    }
+}
+
+// Order for get_top_processes: "memory" (default, biggest first) or "age" (oldest first)
+fn sort_processes(processes: &mut [ProcessMemory], sort_by: Option<&str>) -> Result<(), OrganizerError> {
+    match sort_by.unwrap_or("memory") {
+        "memory" => processes.sort_by(|a, b| b.memory_mb.partial_cmp(&a.memory_mb).unwrap_or(std::cmp::Ordering::Equal)),
+        "age" => processes.sort_by_key(|p| (p.start_time, p.pid)),
+        other => return Err(OrganizerError::InvalidInput(format!("Unknown sort: {}", other))),
+    }
+    Ok(())
 }
 
 #[derive(serde::Serialize)]
@@ -67,9 +427,13 @@ struct ProcessDetails {
     start_time: Option<u64>,     // Unix timestamp
     cpu_usage: f32,              // Percentage
     memory_mb: f64,              // Physical memory
+    footprint_mb: f64,           // macOS phys_footprint (same as memory_mb), RSS elsewhere
+    resident_mb: f64,            // Pages currently in RAM
+    wired_mb: f64,               // macOS only, 0 elsewhere
     virtual_mb: f64,             // Virtual memory
     disk_read_bytes: u64,
     disk_write_bytes: u64,
+    handles: Option<HandleStats>, // Windows only
 }
 
 #[derive(serde::Serialize)]
@@ -84,42 +448,44 @@ struct MemoryInfo {
     cached_gb: f64,
     swap_total_gb: f64,
     swap_used_gb: f64,
+    unit_base: &'static str, // "binary" (GiB) or "decimal" (GB), from the units setting
 }
 
-// macOS: use host_statistics64 for accurate memory info like Activity Monitor
+// macOS: host_statistics64 (HOST_VM_INFO64) layout
+// Match exact macOS struct layout: natural_t = u32, some fields are u64
 #[cfg(target_os = "macos")]
-#[tauri::command]
-fn get_memory_info() -> MemoryInfo {
-    use std::mem;
+#[repr(C)]
+struct VmStatistics64 {
+    free_count: u32,              // natural_t
+    active_count: u32,            // natural_t
+    inactive_count: u32,          // natural_t
+    wire_count: u32,              // natural_t
+    zero_fill_count: u64,
+    reactivations: u64,
+    pageins: u64,
+    pageouts: u64,
+    faults: u64,
+    cow_faults: u64,
+    lookups: u64,
+    hits: u64,
+    purges: u64,
+    purgeable_count: u32,         // natural_t
+    speculative_count: u32,       // natural_t
+    decompressions: u64,
+    compressions: u64,
+    swapins: u64,
+    swapouts: u64,
+    compressor_page_count: u32,   // natural_t
+    throttled_count: u32,         // natural_t
+    external_page_count: u32,     // natural_t
+    internal_page_count: u32,     // natural_t
+    total_uncompressed_pages_in_compressor: u64,
+}
 
-    // Match exact macOS struct layout: natural_t = u32, some fields are u64
-    #[repr(C)]
-    struct VmStatistics64 {
-        free_count: u32,              // natural_t
-        active_count: u32,            // natural_t
-        inactive_count: u32,          // natural_t
-        wire_count: u32,              // natural_t
-        zero_fill_count: u64,
-        reactivations: u64,
-        pageins: u64,
-        pageouts: u64,
-        faults: u64,
-        cow_faults: u64,
-        lookups: u64,
-        hits: u64,
-        purges: u64,
-        purgeable_count: u32,         // natural_t
-        speculative_count: u32,       // natural_t
-        decompressions: u64,
-        compressions: u64,
-        swapins: u64,
-        swapouts: u64,
-        compressor_page_count: u32,   // natural_t
-        throttled_count: u32,         // natural_t
-        external_page_count: u32,     // natural_t
-        internal_page_count: u32,     // natural_t
-        total_uncompressed_pages_in_compressor: u64,
-    }
+// macOS: VM statistics and page size, None if the mach call fails
+#[cfg(target_os = "macos")]
+fn host_vm_statistics() -> Option<(VmStatistics64, u32)> {
+    use std::mem;
 
     extern "C" {
         fn mach_host_self() -> u32;
@@ -143,11 +509,27 @@ fn get_memory_info() -> MemoryInfo {
     let result = unsafe { host_statistics64(host, HOST_VM_INFO64, &mut vm_stat, &mut count) };
 
     if result != 0 {
-        // Fallback to sysinfo if mach call fails
-        return get_memory_info_fallback();
+        return None;
     }
 
-    let page_to_gb = |pages: u64| (pages as f64 * page_size as f64) / 1_073_741_824.0;
+    Some((vm_stat, page_size))
+}
+
+#[tauri::command]
+async fn get_memory_info(app: AppHandle) -> Result<MemoryInfo, OrganizerError> {
+    Ok(tauri::async_runtime::spawn_blocking(move || with_shared_system(&app, memory_info)).await?)
+}
+
+// macOS: use host_statistics64 for accurate memory info like Activity Monitor
+#[cfg(target_os = "macos")]
+fn memory_info(sys: &mut sysinfo::System) -> MemoryInfo {
+    let (vm_stat, page_size) = match host_vm_statistics() {
+        Some(stats) => stats,
+        // Fallback to sysinfo if mach call fails
+        None => return memory_info_fallback(sys),
+    };
+
+    let page_to_gb = |pages: u64| format::to_gb(pages * page_size as u64);
 
     // Get total memory via sysctl
     let total_bytes = {
@@ -159,7 +541,7 @@ fn get_memory_info() -> MemoryInfo {
             .and_then(|o| String::from_utf8_lossy(&o.stdout).trim().parse::<u64>().ok())
             .unwrap_or(0)
     };
-    let total_gb = total_bytes as f64 / 1_073_741_824.0;
+    let total_gb = format::to_gb(total_bytes);
 
     // Calculate memory categories like Activity Monitor (cast u32 to u64)
     let free_pages = (vm_stat.free_count as u64).saturating_sub(vm_stat.speculative_count as u64);
@@ -179,11 +561,8 @@ fn get_memory_info() -> MemoryInfo {
 
     // Swap via sysinfo
     let (swap_total_gb, swap_used_gb) = {
-        use sysinfo::System;
-        let mut sys = System::new();
         sys.refresh_memory();
-        let to_gb = |b: u64| b as f64 / 1_073_741_824.0;
-        (to_gb(sys.total_swap()), to_gb(sys.used_swap()))
+        (format::to_gb(sys.total_swap()), format::to_gb(sys.used_swap()))
     };
 
     MemoryInfo {
@@ -197,15 +576,14 @@ fn get_memory_info() -> MemoryInfo {
         cached_gb,
         swap_total_gb,
         swap_used_gb,
+        unit_base: format::unit_base(),
     }
 }
 
 #[cfg(target_os = "macos")]
-fn get_memory_info_fallback() -> MemoryInfo {
-    use sysinfo::System;
-    let mut sys = System::new();
+fn memory_info_fallback(sys: &mut sysinfo::System) -> MemoryInfo {
     sys.refresh_memory();
-    let to_gb = |b: u64| b as f64 / 1_073_741_824.0;
+    let to_gb = format::to_gb;
     let total = sys.total_memory();
     let used = sys.used_memory();
     MemoryInfo {
@@ -219,18 +597,16 @@ fn get_memory_info_fallback() -> MemoryInfo {
         cached_gb: 0.0,
         swap_total_gb: to_gb(sys.total_swap()),
         swap_used_gb: to_gb(sys.used_swap()),
+        unit_base: format::unit_base(),
     }
 }
 
 // Windows/Linux: use sysinfo
 #[cfg(not(target_os = "macos"))]
-#[tauri::command]
-fn get_memory_info() -> MemoryInfo {
-    use sysinfo::System;
-    let mut sys = System::new();
+fn memory_info(sys: &mut sysinfo::System) -> MemoryInfo {
     sys.refresh_memory();
 
-    let to_gb = |b: u64| b as f64 / 1_073_741_824.0;
+    let to_gb = format::to_gb;
 
     let total = sys.total_memory();
     let used = sys.used_memory();
@@ -247,64 +623,307 @@ fn get_memory_info() -> MemoryInfo {
         cached_gb: 0.0,
         swap_total_gb: to_gb(sys.total_swap()),
         swap_used_gb: to_gb(sys.used_swap()),
+        unit_base: format::unit_base(),
     }
 }
 
-// macOS: use proc_pid_rusage for accurate memory footprint like Activity Monitor
+// macOS: proc_pid_rusage (RUSAGE_INFO_V4) layout, shared by the process commands
+#[cfg(target_os = "macos")]
+#[repr(C)]
+struct RUsageInfoV4 {
+    ri_uuid: [u8; 16],
+    ri_user_time: u64,
+    ri_system_time: u64,
+    ri_pkg_idle_wkups: u64,
+    ri_interrupt_wkups: u64,
+    ri_pageins: u64,
+    ri_wired_size: u64,
+    ri_resident_size: u64,
+    ri_phys_footprint: u64,
+    ri_proc_start_abstime: u64,
+    ri_proc_exit_abstime: u64,
+    ri_child_user_time: u64,
+    ri_child_system_time: u64,
+    ri_child_pkg_idle_wkups: u64,
+    ri_child_interrupt_wkups: u64,
+    ri_child_pageins: u64,
+    ri_child_elapsed_abstime: u64,
+    ri_diskio_bytesread: u64,
+    ri_diskio_byteswritten: u64,
+    ri_cpu_time_qos_default: u64,
+    ri_cpu_time_qos_maintenance: u64,
+    ri_cpu_time_qos_background: u64,
+    ri_cpu_time_qos_utility: u64,
+    ri_cpu_time_qos_legacy: u64,
+    ri_cpu_time_qos_user_initiated: u64,
+    ri_cpu_time_qos_user_interactive: u64,
+    ri_billed_system_time: u64,
+    ri_serviced_system_time: u64,
+    ri_logical_writes: u64,
+    ri_lifetime_max_phys_footprint: u64,
+    ri_instructions: u64,
+    ri_cycles: u64,
+    ri_billed_energy: u64,
+    ri_serviced_energy: u64,
+    ri_interval_max_phys_footprint: u64,
+    ri_runnable_time: u64,
+}
+
+#[cfg(target_os = "macos")]
+extern "C" {
+    fn proc_pid_rusage(pid: i32, flavor: i32, buffer: *mut RUsageInfoV4) -> i32;
+}
+
+#[cfg(target_os = "macos")]
+const RUSAGE_INFO_V4: i32 = 4;
+
+#[cfg(target_os = "macos")]
+fn proc_rusage(pid: u32) -> Option<RUsageInfoV4> {
+    let mut rusage: RUsageInfoV4 = unsafe { std::mem::zeroed() };
+    if unsafe { proc_pid_rusage(pid as i32, RUSAGE_INFO_V4, &mut rusage) } == 0 {
+        Some(rusage)
+    } else {
+        None
+    }
+}
+
+// Memory attributed to a process: phys_footprint on macOS (like Activity Monitor), RSS elsewhere
 #[cfg(target_os = "macos")]
+fn process_footprint_bytes(pid: u32, process: &sysinfo::Process) -> u64 {
+    proc_rusage(pid)
+        .map(|r| r.ri_phys_footprint)
+        .unwrap_or_else(|| process.memory())
+}
+
+#[cfg(not(target_os = "macos"))]
+fn process_footprint_bytes(_pid: u32, process: &sysinfo::Process) -> u64 {
+    process.memory()
+}
+
+#[derive(serde::Serialize)]
+struct TopProcesses {
+    processes: Vec<ProcessMemory>,
+    hidden_count: usize, // Processes left out because their name is in the hidden list
+}
+
+// Names hidden from the top list (matched exactly, case-insensitive)
+struct HiddenProcessesState {
+    names: Mutex<Vec<String>>,
+}
+
+fn is_hidden_process(name: &str, hidden: &[String]) -> bool {
+    hidden.iter().any(|h| h.eq_ignore_ascii_case(name))
+}
+
 #[tauri::command]
-fn get_top_processes(limit: usize) -> Vec<ProcessMemory> {
-    use sysinfo::System;
-    use std::mem;
+async fn get_top_processes(
+    app: AppHandle,
+    limit: usize,
+    sort_by: Option<String>,
+    detail_level: Option<DetailLevel>,
+) -> Result<TopProcesses, OrganizerError> {
+    let hidden = match app.try_state::<Arc<HiddenProcessesState>>() {
+        Some(state) => state.names.lock()?.clone(),
+        None => Vec::new(),
+    };
+    let detail = detail_level.unwrap_or_default();
+    tauri::async_runtime::spawn_blocking(move || {
+        with_shared_processes(&app, detail, |sys| top_processes(sys, limit, sort_by.as_deref(), &hidden, detail))
+    })
+    .await?
+}
+
+// Incremental top list for the live views: only what changed since the view's previous call
+#[derive(Default)]
+struct ProcessDeltaState {
+    views: Mutex<HashMap<String, HashMap<u32, ProcessMemory>>>, // Rows as last sent, per view id
+}
+
+#[derive(serde::Serialize)]
+struct ProcessDelta {
+    added: Vec<ProcessMemory>,
+    removed: Vec<u32>,
+    updated: Vec<ProcessMemory>,
+}
+
+// Smaller moves are noise at the list's display precision
+const PROCESS_DELTA_MEMORY_EPSILON_MB: f64 = 0.5;
+const PROCESS_DELTA_CPU_EPSILON: f32 = 0.5;
+
+fn process_row_changed(previous: &ProcessMemory, current: &ProcessMemory) -> bool {
+    (previous.memory_mb - current.memory_mb).abs() >= PROCESS_DELTA_MEMORY_EPSILON_MB
+        || (previous.cpu_usage.unwrap_or(0.0) - current.cpu_usage.unwrap_or(0.0)).abs() >= PROCESS_DELTA_CPU_EPSILON
+}
+
+// Diff of the top `limit` processes (by memory) against the previous call with the same
+// `view_id`, so two views don't consume each other's changes. Leaving the top N counts as
+// removed and entering it as added, so the UI can patch its table in place. A new view id
+// gets every row as added. A reused PID (different start time) is reported as removed + added.
+#[tauri::command]
+async fn get_process_delta(app: AppHandle, view_id: String, limit: usize) -> Result<ProcessDelta, OrganizerError> {
+    let hidden = match app.try_state::<Arc<HiddenProcessesState>>() {
+        Some(state) => state.names.lock()?.clone(),
+        None => Vec::new(),
+    };
+    tauri::async_runtime::spawn_blocking(move || {
+        let state = app
+            .try_state::<Arc<ProcessDeltaState>>()
+            .ok_or_else(|| OrganizerError::NotFound("Process delta state".into()))?;
+        let top = with_shared_processes(&app, DetailLevel::Light, |sys| {
+            top_processes(sys, limit, None, &hidden, DetailLevel::Light)
+        })?;
+
+        let mut views = state.views.lock()?;
+        let previous = views.entry(view_id).or_default();
+        let mut delta = ProcessDelta { added: Vec::new(), removed: Vec::new(), updated: Vec::new() };
+        let mut current: HashMap<u32, ProcessMemory> = HashMap::with_capacity(top.processes.len());
+        for process in top.processes {
+            match previous.remove(&process.pid) {
+                Some(old) if old.start_time != process.start_time => {
+                    delta.removed.push(old.pid);
+                    delta.added.push(process.clone());
+                    current.insert(process.pid, process);
+                }
+                Some(old) if !process_row_changed(&old, &process) => {
+                    // Keep the last sent values so slow drift still crosses the epsilon
+                    current.insert(old.pid, old);
+                }
+                Some(_) => {
+                    delta.updated.push(process.clone());
+                    current.insert(process.pid, process);
+                }
+                None => {
+                    delta.added.push(process.clone());
+                    current.insert(process.pid, process);
+                }
+            }
+        }
+        delta.removed.extend(previous.keys().copied());
+        delta.removed.sort_unstable();
+        *previous = current;
+        Ok(delta)
+    })
+    .await?
+}
 
-    #[repr(C)]
-    struct RUsageInfoV4 {
-        ri_uuid: [u8; 16],
-        ri_user_time: u64,
-        ri_system_time: u64,
-        ri_pkg_idle_wkups: u64,
-        ri_interrupt_wkups: u64,
-        ri_pageins: u64,
-        ri_wired_size: u64,
-        ri_resident_size: u64,
-        ri_phys_footprint: u64,
-        ri_proc_start_abstime: u64,
-        ri_proc_exit_abstime: u64,
-        ri_child_user_time: u64,
-        ri_child_system_time: u64,
-        ri_child_pkg_idle_wkups: u64,
-        ri_child_interrupt_wkups: u64,
-        ri_child_pageins: u64,
-        ri_child_elapsed_abstime: u64,
-        ri_diskio_bytesread: u64,
-        ri_diskio_byteswritten: u64,
-        ri_cpu_time_qos_default: u64,
-        ri_cpu_time_qos_maintenance: u64,
-        ri_cpu_time_qos_background: u64,
-        ri_cpu_time_qos_utility: u64,
-        ri_cpu_time_qos_legacy: u64,
-        ri_cpu_time_qos_user_initiated: u64,
-        ri_cpu_time_qos_user_interactive: u64,
-        ri_billed_system_time: u64,
-        ri_serviced_system_time: u64,
-        ri_logical_writes: u64,
-        ri_lifetime_max_phys_footprint: u64,
-        ri_instructions: u64,
-        ri_cycles: u64,
-        ri_billed_energy: u64,
-        ri_serviced_energy: u64,
-        ri_interval_max_phys_footprint: u64,
-        ri_runnable_time: u64,
+// Drop a view's last sent rows when it closes
+#[tauri::command]
+fn forget_process_delta(app: AppHandle, view_id: String) -> Result<(), OrganizerError> {
+    if let Some(state) = app.try_state::<Arc<ProcessDeltaState>>() {
+        state.views.lock()?.remove(&view_id);
     }
+    Ok(())
+}
 
-    extern "C" {
-        fn proc_pid_rusage(pid: i32, flavor: i32, buffer: *mut RUsageInfoV4) -> i32;
+#[derive(serde::Serialize)]
+struct Culprit {
+    pid: u32,
+    name: String,
+    cpu_pct: f32,   // 100 = one full core
+    memory_mb: f64, // Footprint on macOS, RSS elsewhere
+    score: f64,     // 0-1
+    reason: String, // "98% CPU" or "6.2 GiB RAM", whichever weighs more
+}
+
+// CPU counts a bit more than memory: a pegged core is what makes the machine feel slow
+const CULPRIT_CPU_WEIGHT: f64 = 0.6;
+const CULPRIT_MEMORY_WEIGHT: f64 = 0.4;
+
+// The single heaviest process: CPU (capped at one core) and share of RAM, weighted
+#[tauri::command]
+async fn get_culprit(app: AppHandle) -> Result<Culprit, OrganizerError> {
+    let hidden = match app.try_state::<Arc<HiddenProcessesState>>() {
+        Some(state) => state.names.lock()?.clone(),
+        None => Vec::new(),
+    };
+    tauri::async_runtime::spawn_blocking(move || {
+        with_shared_processes(&app, DetailLevel::Full, |sys| {
+            sys.refresh_memory();
+            let total_memory = sys.total_memory().max(1) as f64;
+
+            let (pid, process, cpu_share, memory_share, footprint) = sys
+                .processes()
+                .iter()
+                .filter(|(_, p)| !is_hidden_process(&p.name().to_string_lossy(), &hidden))
+                .map(|(pid, p)| {
+                    let footprint = process_footprint_bytes(pid.as_u32(), p);
+                    let cpu_share = (p.cpu_usage() as f64 / 100.0).min(1.0);
+                    (pid.as_u32(), p, cpu_share, footprint as f64 / total_memory, footprint)
+                })
+                .max_by(|a, b| {
+                    let score = |c: f64, m: f64| CULPRIT_CPU_WEIGHT * c + CULPRIT_MEMORY_WEIGHT * m;
+                    score(a.2, a.3).total_cmp(&score(b.2, b.3))
+                })
+                .ok_or_else(|| OrganizerError::NotFound("No process".into()))?;
+
+            let reason = if CULPRIT_CPU_WEIGHT * cpu_share >= CULPRIT_MEMORY_WEIGHT * memory_share {
+                format!("{:.0}% CPU", process.cpu_usage())
+            } else {
+                format!("{} RAM", format::format_bytes(footprint, format::binary_base()))
+            };
+            Ok(Culprit {
+                pid,
+                name: process.name().to_string_lossy().to_string(),
+                cpu_pct: process.cpu_usage(),
+                memory_mb: footprint as f64 / 1_048_576.0,
+                score: CULPRIT_CPU_WEIGHT * cpu_share + CULPRIT_MEMORY_WEIGHT * memory_share,
+                reason,
+            })
+        })
+    })
+    .await?
+}
+
+#[tauri::command]
+fn get_hidden_processes(app: AppHandle) -> Result<Vec<String>, String> {
+    let state = app
+        .try_state::<Arc<HiddenProcessesState>>()
+        .ok_or("Hidden processes state not found")?;
+    let names = state.names.lock().map_err(|e| e.to_string())?.clone();
+    Ok(names)
+}
+
+fn update_hidden_processes(app: &AppHandle, update: impl FnOnce(&mut Vec<String>)) -> Result<(), String> {
+    let state = app
+        .try_state::<Arc<HiddenProcessesState>>()
+        .ok_or("Hidden processes state not found")?;
+    let value = {
+        let mut names = state.names.lock().map_err(|e| e.to_string())?;
+        update(&mut names);
+        serde_json::json!(*names)
+    };
+    save_setting(app, SETTINGS_HIDDEN_PROCESS_NAMES, value);
+    Ok(())
+}
+
+#[tauri::command]
+fn add_hidden_process(app: AppHandle, name: String) -> Result<(), String> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Process name is empty".to_string());
     }
+    update_hidden_processes(&app, |names| {
+        if !is_hidden_process(&name, names) {
+            names.push(name);
+        }
+    })
+}
 
-    const RUSAGE_INFO_V4: i32 = 4;
+#[tauri::command]
+fn remove_hidden_process(app: AppHandle, name: String) -> Result<(), String> {
+    update_hidden_processes(&app, |names| names.retain(|n| !n.eq_ignore_ascii_case(name.trim())))
+}
 
-    let mut sys = System::new();
-    sys.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
+// macOS: use proc_pid_rusage for accurate memory footprint like Activity Monitor
+#[cfg(target_os = "macos")]
+fn top_processes(
+    sys: &mut sysinfo::System,
+    limit: usize,
+    sort_by: Option<&str>,
+    hidden: &[String],
+    detail: DetailLevel,
+) -> Result<TopProcesses, OrganizerError> {
+    let full = detail == DetailLevel::Full;
 
     let to_mb = |b: u64| b as f64 / 1_048_576.0;
 
@@ -314,14 +933,9 @@ fn get_top_processes(limit: usize) -> Vec<ProcessMemory> {
         .map(|(pid, process)| {
             let pid_u32 = pid.as_u32();
 
-            // Try to get phys_footprint via proc_pid_rusage
-            let mut rusage: RUsageInfoV4 = unsafe { mem::zeroed() };
-            let footprint = if unsafe { proc_pid_rusage(pid_u32 as i32, RUSAGE_INFO_V4, &mut rusage) } == 0 {
-                rusage.ri_phys_footprint
-            } else {
-                // Fallback to sysinfo memory
-                process.memory()
-            };
+            // phys_footprint via proc_pid_rusage, falling back to sysinfo memory
+            let footprint = process_footprint_bytes(pid_u32, process);
+            let disk = process.disk_usage();
 
             ProcessMemory {
                 pid: pid_u32,
@@ -329,18 +943,25 @@ fn get_top_processes(limit: usize) -> Vec<ProcessMemory> {
                 cwd: None, // Will be filled later via lsof
                 memory_mb: to_mb(footprint),
                 virtual_mb: to_mb(process.virtual_memory()),
+                start_time: process.start_time(),
+                cpu_usage: full.then(|| process.cpu_usage()),
+                disk_read_bytes: full.then_some(disk.total_read_bytes),
+                disk_write_bytes: full.then_some(disk.total_written_bytes),
             }
         })
         .collect();
 
-    // Sort by memory descending
-    processes.sort_by(|a, b| b.memory_mb.partial_cmp(&a.memory_mb).unwrap_or(std::cmp::Ordering::Equal));
+    let before = processes.len();
+    processes.retain(|p| !is_hidden_process(&p.name, hidden));
+    let hidden_count = before - processes.len();
+
+    sort_processes(&mut processes, sort_by)?;
 
     // Return top N
     processes.truncate(limit);
 
     // Get cwd for top processes via lsof (more reliable on macOS)
-    if !processes.is_empty() {
+    if full && !processes.is_empty() {
         let pids: Vec<String> = processes.iter().map(|p| p.pid.to_string()).collect();
         if let Ok(output) = std::process::Command::new("lsof")
             .args(["-d", "cwd", "-a", "-p", &pids.join(","), "-Fn"])
@@ -367,17 +988,19 @@ fn get_top_processes(limit: usize) -> Vec<ProcessMemory> {
         }
     }
 
-    processes
+    Ok(TopProcesses { processes, hidden_count })
 }
 
 // Windows/Linux: use sysinfo RSS
 #[cfg(not(target_os = "macos"))]
-#[tauri::command]
-fn get_top_processes(limit: usize) -> Vec<ProcessMemory> {
-    use sysinfo::System;
-
-    let mut sys = System::new();
-    sys.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
+fn top_processes(
+    sys: &mut sysinfo::System,
+    limit: usize,
+    sort_by: Option<&str>,
+    hidden: &[String],
+    detail: DetailLevel,
+) -> Result<TopProcesses, OrganizerError> {
+    let full = detail == DetailLevel::Full;
 
     let to_mb = |b: u64| b as f64 / 1_048_576.0;
 
@@ -388,104 +1011,627 @@ fn get_top_processes(limit: usize) -> Vec<ProcessMemory> {
             let cwd = process.cwd().and_then(|p| {
                 p.file_name().map(|n| n.to_string_lossy().to_string())
             });
+            let disk = process.disk_usage();
             ProcessMemory {
                 pid: pid.as_u32(),
                 name: process.name().to_string_lossy().to_string(),
                 cwd,
                 memory_mb: to_mb(process.memory()),
                 virtual_mb: to_mb(process.virtual_memory()),
+                start_time: process.start_time(),
+                cpu_usage: full.then(|| process.cpu_usage()),
+                disk_read_bytes: full.then_some(disk.total_read_bytes),
+                disk_write_bytes: full.then_some(disk.total_written_bytes),
             }
         })
         .collect();
 
-    // Sort by resident memory descending
-    processes.sort_by(|a, b| b.memory_mb.partial_cmp(&a.memory_mb).unwrap_or(std::cmp::Ordering::Equal));
+    let before = processes.len();
+    processes.retain(|p| !is_hidden_process(&p.name, hidden));
+    let hidden_count = before - processes.len();
+
+    sort_processes(&mut processes, sort_by)?;
 
     // Return top N
     processes.truncate(limit);
-    processes
+    Ok(TopProcesses { processes, hidden_count })
 }
 
-// macOS: get detailed process info via sysinfo + ps + lsof
-#[cfg(target_os = "macos")]
-#[tauri::command]
-fn get_process_details(pid: u32) -> Result<ProcessDetails, String> {
-    use sysinfo::{System, Pid, ProcessesToUpdate, ProcessRefreshKind, UpdateKind};
-    use std::mem;
+// Two CPU conventions exist for a process:
+// - per core (top, ps, Activity Monitor): 100% = one full core, so 4 busy cores read 400%
+// - normalized: divided by the logical CPU count, 100% = every core (like the system gauge)
+// Commands report per core unless called with normalize = true.
+fn logical_cpu_count() -> usize {
+    static COUNT: std::sync::OnceLock<usize> = std::sync::OnceLock::new();
+    *COUNT.get_or_init(|| {
+        use sysinfo::{CpuRefreshKind, RefreshKind, System};
+        let sys = System::new_with_specifics(RefreshKind::new().with_cpu(CpuRefreshKind::new()));
+        sys.cpus().len().max(1)
+    })
+}
 
-    #[repr(C)]
-    struct RUsageInfoV4 {
-        ri_uuid: [u8; 16],
-        ri_user_time: u64,
-        ri_system_time: u64,
-        ri_pkg_idle_wkups: u64,
-        ri_interrupt_wkups: u64,
-        ri_pageins: u64,
-        ri_wired_size: u64,
-        ri_resident_size: u64,
-        ri_phys_footprint: u64,
-        ri_proc_start_abstime: u64,
-        ri_proc_exit_abstime: u64,
-        ri_child_user_time: u64,
-        ri_child_system_time: u64,
-        ri_child_pkg_idle_wkups: u64,
-        ri_child_interrupt_wkups: u64,
-        ri_child_pageins: u64,
-        ri_child_elapsed_abstime: u64,
-        ri_diskio_bytesread: u64,
-        ri_diskio_byteswritten: u64,
-        ri_cpu_time_qos_default: u64,
-        ri_cpu_time_qos_maintenance: u64,
-        ri_cpu_time_qos_background: u64,
-        ri_cpu_time_qos_utility: u64,
-        ri_cpu_time_qos_legacy: u64,
-        ri_cpu_time_qos_user_initiated: u64,
-        ri_cpu_time_qos_user_interactive: u64,
-        ri_billed_system_time: u64,
-        ri_serviced_system_time: u64,
-        ri_logical_writes: u64,
-        ri_lifetime_max_phys_footprint: u64,
-        ri_instructions: u64,
-        ri_cycles: u64,
-        ri_billed_energy: u64,
-        ri_serviced_energy: u64,
-        ri_interval_max_phys_footprint: u64,
-        ri_runnable_time: u64,
+fn normalize_cpu(cpu_usage: f32, normalize: bool) -> f32 {
+    if normalize {
+        cpu_usage / logical_cpu_count() as f32
+    } else {
+        cpu_usage
     }
+}
 
-    extern "C" {
-        fn proc_pid_rusage(pid: i32, flavor: i32, buffer: *mut RUsageInfoV4) -> i32;
-    }
+#[derive(serde::Serialize)]
+struct CpuUsage {
+    total: f32,             // 0-100, all cores
+    per_core: Vec<f32>,     // 0-100 per logical CPU
+    core_types: Vec<String>, // Parallel to per_core: "performance" | "efficiency" | "unknown"
+}
 
-    const RUSAGE_INFO_V4: i32 = 4;
+// Apple Silicon: hw.perflevel0 is the performance cluster, hw.perflevel1 the efficiency one.
+// Logical CPUs are numbered efficiency cores first.
+#[cfg(target_os = "macos")]
+fn core_types(count: usize) -> Vec<String> {
+    static TYPES: std::sync::OnceLock<Option<(usize, usize)>> = std::sync::OnceLock::new();
+    let levels = TYPES.get_or_init(|| {
+        let out = command_stdout("sysctl", &["-n", "hw.perflevel0.logicalcpu", "hw.perflevel1.logicalcpu"])?;
+        let mut counts = out.lines().filter_map(|l| l.trim().parse::<usize>().ok());
+        Some((counts.next()?, counts.next()?))
+    });
 
-    let sysinfo_pid = Pid::from_u32(pid);
+    match levels {
+        Some((performance, efficiency)) if performance + efficiency == count => (0..count)
+            .map(|i| if i < *efficiency { "efficiency" } else { "performance" }.to_string())
+            .collect(),
+        // Intel Macs have a single perf level
+        _ => vec!["unknown".to_string(); count],
+    }
+}
 
-    // Refresh process info (CPU is fetched separately via ps)
-    let mut sys = System::new();
-    let refresh_kind = ProcessRefreshKind::new()
-        .with_memory()
-        .with_exe(UpdateKind::OnlyIfNotSet)
-        .with_cwd(UpdateKind::OnlyIfNotSet)
-        .with_cmd(UpdateKind::OnlyIfNotSet);
+#[cfg(not(target_os = "macos"))]
+fn core_types(count: usize) -> Vec<String> {
+    vec!["unknown".to_string(); count]
+}
 
-    sys.refresh_processes_specifics(
-        ProcessesToUpdate::Some(&[sysinfo_pid]),
-        true,
-        refresh_kind,
-    );
+#[tauri::command]
+async fn get_cpu_usage(app: AppHandle) -> Result<CpuUsage, OrganizerError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let (total, per_core) = with_shared_sample(&app, |sys| sys.refresh_cpu_usage(), |sys| {
+            (sys.global_cpu_usage(), sys.cpus().iter().map(|c| c.cpu_usage()).collect::<Vec<f32>>())
+        });
+        CpuUsage {
+            total,
+            core_types: core_types(per_core.len()),
+            per_core,
+        }
+    })
+    .await
+    .map_err(OrganizerError::from)
+}
 
-    let process = sys.process(sysinfo_pid)
-        .ok_or_else(|| format!("Process {} not found", pid))?;
+#[derive(serde::Serialize)]
+struct NetworkThroughput {
+    interface: String,
+    rx_bps: f64,
+    tx_bps: f64,
+}
+
+const SNAPSHOT_TOP_PROCESSES: usize = 10;
+
+#[derive(serde::Serialize)]
+struct FullSnapshot {
+    taken_at: u64, // Unix timestamp shared by every metric below
+    taken_at_iso: String,
+    memory: MemoryInfo,
+    cpu: CpuUsage,
+    disks: Vec<DiskInfo>,
+    network: Vec<NetworkThroughput>,
+    top_processes: TopProcesses,
+}
+
+// Every metric read under one lock on the shared System, right after the second CPU sample,
+// so nothing refreshes in between. CPU and network rates share the same sampling window.
+#[tauri::command]
+async fn capture_snapshot(app: AppHandle) -> Result<FullSnapshot, OrganizerError> {
+    let hidden = match app.try_state::<Arc<HiddenProcessesState>>() {
+        Some(state) => state.names.lock()?.clone(),
+        None => Vec::new(),
+    };
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut networks = sysinfo::Networks::new_with_refreshed_list();
+        let sampled_at = Instant::now();
+        with_shared_sample(&app, |sys| sys.refresh_cpu_usage(), |sys| {
+            networks.refresh();
+            let secs = sampled_at.elapsed().as_secs_f64();
+
+            let taken_at = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0);
+            let per_core: Vec<f32> = sys.cpus().iter().map(|c| c.cpu_usage()).collect();
+            let cpu = CpuUsage {
+                total: sys.global_cpu_usage(),
+                core_types: core_types(per_core.len()),
+                per_core,
+            };
+            let mut network: Vec<NetworkThroughput> = networks
+                .iter()
+                .map(|(interface, data)| NetworkThroughput {
+                    interface: interface.clone(),
+                    rx_bps: data.received() as f64 / secs,
+                    tx_bps: data.transmitted() as f64 / secs,
+                })
+                .collect();
+            network.sort_by(|a, b| a.interface.cmp(&b.interface));
+
+            Ok(FullSnapshot {
+                taken_at,
+                taken_at_iso: iso8601_utc(taken_at),
+                memory: memory_info(sys),
+                cpu,
+                disks: all_disks(),
+                network,
+                top_processes: {
+                    DetailLevel::Light.refresh(sys);
+                    top_processes(sys, SNAPSHOT_TOP_PROCESSES, None, &hidden, DetailLevel::Light)?
+                },
+            })
+        })
+    })
+    .await?
+}
+
+#[derive(serde::Serialize)]
+struct GpuProcess {
+    pid: u32,
+    name: String,
+    gpu_pct: f64,            // Share of GPU time over the sample window
+    gpu_mem_mb: Option<f64>, // Not exposed per process by ioreg
+}
+
+// Sample window for per-process GPU time
+#[cfg(target_os = "macos")]
+const GPU_SAMPLE_MS: u64 = 500;
+
+// Cumulative GPU time (ns) per pid from the accelerator user clients, e.g.
+//   +-o AGXDeviceUserClient  <class AGXDeviceUserClient, ...>
+//       "IOUserClientCreator" = "pid 412, WindowServer"
+//       "AppUsage" = ({"API"="Metal","accumulatedGPUTime"=123456789})
+// One process can own several clients, their times add up.
+#[cfg(target_os = "macos")]
+fn gpu_client_times() -> HashMap<u32, (String, u64)> {
+    let mut times: HashMap<u32, (String, u64)> = HashMap::new();
+    let Some(out) = command_stdout("ioreg", &["-r", "-c", "IOUserClient", "-l", "-w", "0", "-d", "1"]) else {
+        return times;
+    };
+    let mut current: Option<(u32, String)> = None;
+    for line in out.lines() {
+        if line.contains("+-o ") {
+            current = None;
+        } else if let Some(creator) = line.split("\"IOUserClientCreator\" = \"pid ").nth(1) {
+            let creator = creator.trim_end().trim_end_matches('"');
+            current = creator
+                .split_once(", ")
+                .and_then(|(pid, name)| Some((pid.parse().ok()?, name.to_string())));
+        } else if let Some((pid, name)) = &current {
+            let gpu_ns: u64 = line
+                .split("\"accumulatedGPUTime\"=")
+                .skip(1)
+                .filter_map(|rest| {
+                    let digits: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+                    digits.parse::<u64>().ok()
+                })
+                .sum();
+            if gpu_ns > 0 {
+                times.entry(*pid).or_insert_with(|| (name.clone(), 0)).1 += gpu_ns;
+            }
+        }
+    }
+    times
+}
+
+// Best effort, macOS only: empty when ioreg exposes no per-client GPU time
+#[tauri::command]
+async fn get_gpu_processes() -> Vec<GpuProcess> {
+    #[cfg(target_os = "macos")]
+    {
+        tauri::async_runtime::spawn_blocking(|| {
+            let before = gpu_client_times();
+            let started = Instant::now();
+            std::thread::sleep(Duration::from_millis(GPU_SAMPLE_MS));
+            let after = gpu_client_times();
+            let window_ns = started.elapsed().as_nanos() as f64;
+
+            let mut processes: Vec<GpuProcess> = after
+                .into_iter()
+                .filter_map(|(pid, (name, total))| {
+                    let delta = total.saturating_sub(before.get(&pid).map(|(_, t)| *t).unwrap_or(total));
+                    (delta > 0).then(|| GpuProcess {
+                        pid,
+                        name,
+                        gpu_pct: (delta as f64 / window_ns * 100.0).min(100.0),
+                        gpu_mem_mb: None,
+                    })
+                })
+                .collect();
+            processes.sort_by(|a, b| b.gpu_pct.partial_cmp(&a.gpu_pct).unwrap_or(std::cmp::Ordering::Equal));
+            processes
+        })
+        .await
+        .unwrap_or_default()
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        Vec::new()
+    }
+}
+
+#[derive(serde::Serialize)]
+struct ProcessSummary {
+    count: usize,
+    total_memory_mb: f64, // Sum of RSS, shared pages are counted once per process
+    total_cpu_pct: f32,
+    running: usize,
+    sleeping: usize,
+    zombie: usize,
+}
+
+// Aggregates over every process for the header widget, without shipping the full list
+#[tauri::command]
+async fn get_process_summary(app: AppHandle, normalize: Option<bool>) -> Result<ProcessSummary, OrganizerError> {
+    use sysinfo::{ProcessRefreshKind, ProcessStatus, ProcessesToUpdate};
+
+    // CPU usage needs two samples of the same System
+    let kind = ProcessRefreshKind::new().with_memory().with_cpu();
+    let refresh = move |sys: &mut sysinfo::System| {
+        sys.refresh_processes_specifics(ProcessesToUpdate::All, true, kind);
+    };
+    tauri::async_runtime::spawn_blocking(move || with_shared_sample(&app, refresh, |sys| {
+
+        let mut summary = ProcessSummary {
+            count: 0,
+            total_memory_mb: 0.0,
+            total_cpu_pct: 0.0,
+            running: 0,
+            sleeping: 0,
+            zombie: 0,
+        };
+        for process in sys.processes().values() {
+            summary.count += 1;
+            summary.total_memory_mb += process.memory() as f64 / 1_048_576.0;
+            summary.total_cpu_pct += process.cpu_usage();
+            match process.status() {
+                ProcessStatus::Run => summary.running += 1,
+                ProcessStatus::Sleep | ProcessStatus::Idle => summary.sleeping += 1,
+                ProcessStatus::Zombie => summary.zombie += 1,
+                _ => {}
+            }
+        }
+        summary.total_cpu_pct = normalize_cpu(summary.total_cpu_pct, normalize.unwrap_or(false));
+        summary
+    }))
+    .await
+    .map_err(OrganizerError::from)
+}
+
+// Light ProcessMemory row for a single process (no CPU, disk or cwd)
+fn process_memory_entry(pid: u32, process: &sysinfo::Process) -> ProcessMemory {
+    let to_mb = |b: u64| b as f64 / 1_048_576.0;
+    ProcessMemory {
+        pid,
+        name: process.name().to_string_lossy().to_string(),
+        cwd: None,
+        memory_mb: to_mb(process_footprint_bytes(pid, process)),
+        virtual_mb: to_mb(process.virtual_memory()),
+        start_time: process.start_time(),
+        cpu_usage: None,
+        disk_read_bytes: None,
+        disk_write_bytes: None,
+    }
+}
+
+// PIDs with the path open: `lsof -t` prints one PID per line and exits 1 when there are none
+#[cfg(not(target_os = "windows"))]
+fn path_holder_pids(path: &str) -> Result<Vec<u32>, String> {
+    let output = std::process::Command::new("lsof")
+        .args(["-t", "--", path])
+        .output()
+        .map_err(|e| format!("Failed to run lsof: {}", e))?;
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.trim().parse().ok())
+        .collect())
+}
+
+// Windows: the Restart Manager knows which processes have a file open
+#[cfg(target_os = "windows")]
+fn path_holder_pids(path: &str) -> Result<Vec<u32>, String> {
+    use windows_sys::Win32::Foundation::ERROR_MORE_DATA;
+    use windows_sys::Win32::System::RestartManager::{
+        RmEndSession, RmGetList, RmRegisterResources, RmStartSession, CCH_RM_SESSION_KEY, RM_PROCESS_INFO,
+    };
+
+    let mut session = 0u32;
+    let mut key = [0u16; CCH_RM_SESSION_KEY as usize + 1];
+    let err = unsafe { RmStartSession(&mut session, 0, key.as_mut_ptr()) };
+    if err != 0 {
+        return Err(format!("RmStartSession failed: {}", err));
+    }
+
+    let result = (|| {
+        let wide: Vec<u16> = path.encode_utf16().chain(std::iter::once(0)).collect();
+        let files = [wide.as_ptr()];
+        let err = unsafe {
+            RmRegisterResources(session, 1, files.as_ptr(), 0, std::ptr::null(), 0, std::ptr::null())
+        };
+        if err != 0 {
+            return Err(format!("RmRegisterResources failed: {}", err));
+        }
+
+        // First call sizes the list; retry while processes keep appearing in between
+        let mut infos: Vec<RM_PROCESS_INFO> = Vec::new();
+        loop {
+            let mut needed = 0u32;
+            let mut count = infos.len() as u32;
+            let mut reasons = 0u32;
+            let err = unsafe { RmGetList(session, &mut needed, &mut count, infos.as_mut_ptr(), &mut reasons) };
+            match err {
+                0 => {
+                    infos.truncate(count as usize);
+                    return Ok(infos.iter().map(|info| info.Process.dwProcessId).collect());
+                }
+                ERROR_MORE_DATA => infos = vec![unsafe { std::mem::zeroed() }; needed as usize],
+                _ => return Err(format!("RmGetList failed: {}", err)),
+            }
+        }
+    })();
+
+    unsafe { RmEndSession(session) };
+    result
+}
+
+// Which processes hold a file or directory open ("file in use"), empty when none
+#[tauri::command]
+async fn who_holds(path: String) -> Result<Vec<ProcessMemory>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        if !Path::new(&path).exists() {
+            return Err(format!("{} does not exist", path));
+        }
+        let mut pids = path_holder_pids(&path)?;
+        pids.sort_unstable();
+        pids.dedup();
+        if pids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let sysinfo_pids: Vec<sysinfo::Pid> = pids.iter().map(|p| sysinfo::Pid::from_u32(*p)).collect();
+        let mut sys = sysinfo::System::new();
+        sys.refresh_processes_specifics(
+            sysinfo::ProcessesToUpdate::Some(&sysinfo_pids),
+            true,
+            DetailLevel::Light.refresh_kind(),
+        );
+        Ok(pids
+            .iter()
+            .filter_map(|pid| {
+                let process = sys.process(sysinfo::Pid::from_u32(*pid))?;
+                Some(process_memory_entry(*pid, process))
+            })
+            .collect())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+// Case-insensitive substring match; an empty needle matches nothing
+fn process_name_matches(name: &str, needle: &str) -> bool {
+    let needle = needle.trim().to_lowercase();
+    !needle.is_empty() && name.to_lowercase().contains(&needle)
+}
+
+fn pids_by_name(sys: &sysinfo::System, name: &str) -> Vec<u32> {
+    let mut pids: Vec<u32> = sys
+        .processes()
+        .iter()
+        .filter(|(_, process)| process_name_matches(&process.name().to_string_lossy(), name))
+        .map(|(pid, _)| pid.as_u32())
+        .collect();
+    pids.sort_unstable();
+    pids
+}
+
+fn name_by_pid(sys: &sysinfo::System, pid: u32) -> Option<String> {
+    sys.process(sysinfo::Pid::from_u32(pid))
+        .map(|process| process.name().to_string_lossy().to_string())
+}
+
+// Name-only refresh: no CPU, memory or disk sampling needed for lookups
+fn process_names_system(pids: sysinfo::ProcessesToUpdate) -> sysinfo::System {
+    let mut sys = sysinfo::System::new();
+    sys.refresh_processes_specifics(pids, true, sysinfo::ProcessRefreshKind::new());
+    sys
+}
+
+#[tauri::command]
+fn resolve_pids_by_name(name: String) -> Vec<u32> {
+    pids_by_name(&process_names_system(sysinfo::ProcessesToUpdate::All), &name)
+}
+
+#[tauri::command]
+fn resolve_name_by_pid(pid: u32) -> Option<String> {
+    let sysinfo_pid = sysinfo::Pid::from_u32(pid);
+    name_by_pid(&process_names_system(sysinfo::ProcessesToUpdate::Some(&[sysinfo_pid])), pid)
+}
+
+#[derive(serde::Serialize)]
+struct ProcessStart {
+    start_time: u64,     // Unix timestamp, as in the process details
+    started_at: String,  // ISO 8601 in the local timezone, e.g. "2024-05-01T14:34:56+02:00"
+    running_for: String, // "2h 5m"
+}
+
+// Local time is resolved here (with the DST offset of that date) so the UI never converts
+#[tauri::command]
+fn get_process_start_iso(pid: u32) -> Result<ProcessStart, String> {
+    let sysinfo_pid = sysinfo::Pid::from_u32(pid);
+    let sys = process_names_system(sysinfo::ProcessesToUpdate::Some(&[sysinfo_pid]));
+    let process = sys.process(sysinfo_pid).ok_or_else(|| format!("Process {} not found", pid))?;
+    let start_time = process.start_time();
+    let started = chrono::DateTime::from_timestamp(start_time as i64, 0)
+        .ok_or_else(|| format!("Invalid start time {}", start_time))?
+        .with_timezone(&chrono::Local);
+    Ok(ProcessStart {
+        start_time,
+        started_at: started.to_rfc3339_opts(chrono::SecondsFormat::Secs, false),
+        running_for: format::format_duration(unix_now().saturating_sub(start_time)),
+    })
+}
+
+// Stack dumps can be megabytes for processes with many threads
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+const STACK_SAMPLE_MAX_BYTES: usize = 1_048_576;
+#[cfg(target_os = "macos")]
+const STACK_SAMPLE_MAX_MS: u64 = 30_000;
+// Symbolication after the sampling window can take a while
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+const STACK_SAMPLE_GRACE_SECS: u64 = 20;
+
+// Stdout of a command, killed after `timeout` and cut at `max_bytes` (the rest is drained so
+// the child never blocks on a full pipe)
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn capped_output(command: &mut std::process::Command, timeout: Duration, max_bytes: usize) -> Result<String, String> {
+    use std::io::Read;
+    use std::process::Stdio;
+
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| e.to_string())?;
+    let stdout = child.stdout.take().ok_or("No stdout")?;
+    let stderr = child.stderr.take().ok_or("No stderr")?;
+    let reader = std::thread::spawn(move || {
+        let mut stdout = stdout;
+        let mut kept = Vec::new();
+        let _ = (&mut stdout).take(max_bytes as u64).read_to_end(&mut kept);
+        let truncated = std::io::copy(&mut stdout, &mut std::io::sink()).unwrap_or(0) > 0;
+        (kept, truncated)
+    });
+    let stderr_reader = std::thread::spawn(move || {
+        let mut text = String::new();
+        let _ = stderr.take(64 * 1024).read_to_string(&mut text);
+        text
+    });
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        match child.try_wait().map_err(|e| e.to_string())? {
+            Some(status) => break status,
+            None if Instant::now() >= deadline => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!("Timed out after {}s", timeout.as_secs()));
+            }
+            None => std::thread::sleep(Duration::from_millis(100)),
+        }
+    };
+    let (kept, truncated) = reader.join().map_err(|_| "Output reader panicked")?;
+    let stderr = stderr_reader.join().unwrap_or_default();
+    if !status.success() && kept.is_empty() {
+        return Err(stderr.trim().to_string());
+    }
+    let mut text = String::from_utf8_lossy(&kept).into_owned();
+    if truncated {
+        text.push_str("\n[output truncated]\n");
+    }
+    Ok(text)
+}
+
+// macOS: `sample <pid> <seconds>` (whole seconds, at least 1), the report Activity Monitor shows
+#[cfg(target_os = "macos")]
+#[tauri::command]
+async fn sample_process_stack(pid: u32, duration_ms: u64) -> Result<String, String> {
+    let secs = duration_ms.clamp(1, STACK_SAMPLE_MAX_MS).div_ceil(1000);
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut command = std::process::Command::new("/usr/bin/sample");
+        command.args([pid.to_string(), secs.to_string()]);
+        capped_output(&mut command, Duration::from_secs(secs + STACK_SAMPLE_GRACE_SECS), STACK_SAMPLE_MAX_BYTES)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+// Linux: one-shot dumps, so duration_ms is ignored. py-spy for Python processes, eu-stack
+// (elfutils) otherwise, whichever is installed. Both usually need ptrace rights on the pid.
+#[cfg(target_os = "linux")]
+#[tauri::command]
+async fn sample_process_stack(pid: u32, duration_ms: u64) -> Result<String, String> {
+    let _ = duration_ms;
+    tauri::async_runtime::spawn_blocking(move || {
+        let sys = process_names_system(sysinfo::ProcessesToUpdate::Some(&[sysinfo::Pid::from_u32(pid)]));
+        let python = name_by_pid(&sys, pid)
+            .ok_or_else(|| format!("Process {} not found", pid))?
+            .starts_with("python");
+        let pid = pid.to_string();
+        let py_spy = || find_in_path("py-spy").map(|bin| (bin, vec!["dump", "--pid", pid.as_str()]));
+        let eu_stack = || find_in_path("eu-stack").map(|bin| (bin, vec!["-p", pid.as_str()]));
+        let tool = if python { py_spy().or_else(eu_stack) } else { eu_stack().or_else(py_spy) };
+        let (program, args) = tool.ok_or("Unsupported: install eu-stack (elfutils) or py-spy")?;
+
+        let mut command = std::process::Command::new(program);
+        command.args(args);
+        capped_output(&mut command, Duration::from_secs(STACK_SAMPLE_GRACE_SECS), STACK_SAMPLE_MAX_BYTES)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+#[tauri::command]
+fn sample_process_stack(_pid: u32, _duration_ms: u64) -> Result<String, String> {
+    Err("Unsupported: stack sampling is not available on this platform".to_string())
+}
+
+// macOS: get detailed process info via sysinfo + ps + lsof
+#[cfg(target_os = "macos")]
+#[tauri::command]
+fn get_process_details(
+    pid: u32,
+    normalize: Option<bool>,
+    detail_level: Option<DetailLevel>,
+) -> Result<ProcessDetails, OrganizerError> {
+    use sysinfo::{System, Pid, ProcessesToUpdate, ProcessRefreshKind, UpdateKind};
+
+    let sysinfo_pid = Pid::from_u32(pid);
+
+    // Refresh process info (CPU is fetched separately via ps)
+    let mut sys = System::new();
+    let refresh_kind = ProcessRefreshKind::new()
+        .with_memory()
+        .with_exe(UpdateKind::OnlyIfNotSet)
+        .with_cwd(UpdateKind::OnlyIfNotSet)
+        .with_cmd(UpdateKind::OnlyIfNotSet);
+
+    sys.refresh_processes_specifics(
+        ProcessesToUpdate::Some(&[sysinfo_pid]),
+        true,
+        refresh_kind,
+    );
+
+    let process = sys.process(sysinfo_pid)
+        .ok_or_else(|| OrganizerError::NotFound(format!("Process {}", pid)))?;
 
     let to_mb = |b: u64| b as f64 / 1_048_576.0;
 
     // Get phys_footprint and disk I/O via proc_pid_rusage
-    let mut rusage: RUsageInfoV4 = unsafe { mem::zeroed() };
-    let (memory_mb, disk_read, disk_write) = if unsafe { proc_pid_rusage(pid as i32, RUSAGE_INFO_V4, &mut rusage) } == 0 {
-        (to_mb(rusage.ri_phys_footprint), rusage.ri_diskio_bytesread, rusage.ri_diskio_byteswritten)
-    } else {
-        (to_mb(process.memory()), 0, 0)
+    // (footprint, resident, wired) memory split plus disk I/O
+    let (memory_mb, resident_mb, wired_mb, disk_read, disk_write) = match proc_rusage(pid) {
+        Some(rusage) => (
+            to_mb(rusage.ri_phys_footprint),
+            to_mb(rusage.ri_resident_size),
+            to_mb(rusage.ri_wired_size),
+            rusage.ri_diskio_bytesread,
+            rusage.ri_diskio_byteswritten,
+        ),
+        None => (to_mb(process.memory()), to_mb(process.memory()), 0.0, 0, 0),
     };
 
     // Get user via ps (more reliable on macOS)
@@ -502,8 +1648,11 @@ fn get_process_details(pid: u32) -> Result<ProcessDetails, String> {
             }
         });
 
+    // The details view defaults to Full; Light skips the ps CPU and lsof cwd forks
+    let full = detail_level.unwrap_or(DetailLevel::Full) == DetailLevel::Full;
+
     // Get full cwd via lsof (more reliable than sysinfo on macOS)
-    let cwd = std::process::Command::new("lsof")
+    let cwd = full.then(|| std::process::Command::new("lsof")
         .args(["-d", "cwd", "-a", "-p", &pid.to_string(), "-Fn"])
         .output()
         .ok()
@@ -518,7 +1667,8 @@ fn get_process_details(pid: u32) -> Result<ProcessDetails, String> {
             }
             None
         })
-        .or_else(|| process.cwd().map(|p| p.to_string_lossy().to_string()));
+        .or_else(|| process.cwd().map(|p| p.to_string_lossy().to_string())))
+        .flatten();
 
     // Status mapping
     let status = match process.status() {
@@ -543,11 +1693,15 @@ fn get_process_details(pid: u32) -> Result<ProcessDetails, String> {
         cwd,
         cmd_args,
         start_time: Some(process.start_time()),
-        cpu_usage: get_cpu_via_ps(pid),
+        cpu_usage: if full { normalize_cpu(get_cpu_via_ps(pid), normalize.unwrap_or(false)) } else { 0.0 },
         memory_mb,
+        footprint_mb: memory_mb,
+        resident_mb,
+        wired_mb,
         virtual_mb: to_mb(process.virtual_memory()),
         disk_read_bytes: disk_read,
         disk_write_bytes: disk_write,
+        handles: None,
     })
 }
 
@@ -572,19 +1726,21 @@ fn get_cpu_via_ps(pid: u32) -> f32 {
 // Windows/Linux: get detailed process info via sysinfo
 #[cfg(not(target_os = "macos"))]
 #[tauri::command]
-fn get_process_details(pid: u32) -> Result<ProcessDetails, String> {
-    use sysinfo::{System, Pid, ProcessesToUpdate, ProcessRefreshKind, UpdateKind};
+fn get_process_details(
+    pid: u32,
+    normalize: Option<bool>,
+    detail_level: Option<DetailLevel>,
+) -> Result<ProcessDetails, OrganizerError> {
+    use sysinfo::{System, Pid, ProcessesToUpdate, UpdateKind};
 
     let sysinfo_pid = Pid::from_u32(pid);
 
-    // Create system and do two refreshes with delay for accurate CPU usage
+    // The details view defaults to Full: two refreshes with a delay for accurate CPU usage
+    let detail = detail_level.unwrap_or(DetailLevel::Full);
     let mut sys = System::new();
-    let refresh_kind = ProcessRefreshKind::new()
-        .with_cpu()
-        .with_memory()
-        .with_disk_usage()
+    let refresh_kind = detail
+        .refresh_kind()
         .with_exe(UpdateKind::OnlyIfNotSet)
-        .with_cwd(UpdateKind::OnlyIfNotSet)
         .with_cmd(UpdateKind::OnlyIfNotSet);
 
     sys.refresh_processes_specifics(
@@ -593,17 +1749,19 @@ fn get_process_details(pid: u32) -> Result<ProcessDetails, String> {
         refresh_kind,
     );
 
-    // Small delay for CPU measurement
-    std::thread::sleep(std::time::Duration::from_millis(100));
+    if detail == DetailLevel::Full {
+        // Small delay for CPU measurement
+        std::thread::sleep(std::time::Duration::from_millis(100));
 
-    sys.refresh_processes_specifics(
-        ProcessesToUpdate::Some(&[sysinfo_pid]),
-        true,
-        refresh_kind,
-    );
+        sys.refresh_processes_specifics(
+            ProcessesToUpdate::Some(&[sysinfo_pid]),
+            true,
+            refresh_kind,
+        );
+    }
 
     let process = sys.process(sysinfo_pid)
-        .ok_or_else(|| format!("Process {} not found", pid))?;
+        .ok_or_else(|| OrganizerError::NotFound(format!("Process {}", pid)))?;
 
     let to_mb = |b: u64| b as f64 / 1_048_576.0;
 
@@ -632,286 +1790,8228 @@ fn get_process_details(pid: u32) -> Result<ProcessDetails, String> {
         cwd: process.cwd().map(|p| p.to_string_lossy().to_string()),
         cmd_args,
         start_time: Some(process.start_time()),
-        cpu_usage: process.cpu_usage(),
+        cpu_usage: normalize_cpu(process.cpu_usage(), normalize.unwrap_or(false)),
         memory_mb: to_mb(process.memory()),
+        footprint_mb: to_mb(process.memory()),
+        resident_mb: to_mb(process.memory()),
+        wired_mb: 0.0, // No wired accounting per process on Windows/Linux
         virtual_mb: to_mb(process.virtual_memory()),
         disk_read_bytes: disk_usage.read_bytes,
         disk_write_bytes: disk_usage.written_bytes,
+        handles: process_handle_stats(pid).ok(),
     })
 }
 
+// Preferred over get_process_details: the blocking work (ps/lsof forks on macOS, the
+// CPU sampling sleep elsewhere) runs on the blocking pool instead of the IPC thread
+#[tauri::command]
+async fn get_process_details_async(
+    pid: u32,
+    normalize: Option<bool>,
+    detail_level: Option<DetailLevel>,
+) -> Result<ProcessDetails, OrganizerError> {
+    tauri::async_runtime::spawn_blocking(move || get_process_details(pid, normalize, detail_level)).await?
+}
+
+// Windows kernel and GUI handle usage, the usual place leaks show up
 #[derive(serde::Serialize)]
-struct DiskSpaceDetailed {
-    total_gb: f64,
-    available_gb: f64,              // Real available space (without purgeable on macOS)
-    available_with_purgeable_gb: f64, // Available space including purgeable
-    purgeable_gb: f64,              // macOS only, 0 on Windows
-    used_gb: f64,
+struct HandleStats {
+    handle_count: u32,
+    gdi_objects: u32,
+    user_objects: u32,
 }
 
-// Progressive server status step payload
-#[derive(Clone, serde::Serialize)]
-struct ServerStatusStep {
-    step: String,
-    data: Option<serde_json::Value>,
+#[cfg(target_os = "windows")]
+fn process_handle_stats(pid: u32) -> Result<HandleStats, OrganizerError> {
+    use windows_sys::Win32::Foundation::{CloseHandle, ERROR_INVALID_PARAMETER};
+    use windows_sys::Win32::System::Threading::{
+        GetGuiResources, GetProcessHandleCount, OpenProcess, GR_GDIOBJECTS, GR_USEROBJECTS,
+        PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+
+    let handle = unsafe { OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid) };
+    if handle.is_null() {
+        let err = std::io::Error::last_os_error();
+        // OpenProcess reports an unknown PID as an invalid parameter
+        if err.raw_os_error() == Some(ERROR_INVALID_PARAMETER as i32) {
+            return Err(OrganizerError::NotFound(format!("Process {}", pid)));
+        }
+        return Err(err.into());
+    }
+
+    let mut handle_count = 0;
+    let counted = unsafe { GetProcessHandleCount(handle, &mut handle_count) } != 0;
+    let err = std::io::Error::last_os_error();
+    let stats = HandleStats {
+        handle_count,
+        gdi_objects: unsafe { GetGuiResources(handle, GR_GDIOBJECTS) },
+        user_objects: unsafe { GetGuiResources(handle, GR_USEROBJECTS) },
+    };
+    unsafe { CloseHandle(handle) };
+
+    if !counted {
+        return Err(err.into());
+    }
+    Ok(stats)
+}
+
+// macOS/Linux: use the FD counts instead
+#[cfg(not(target_os = "windows"))]
+fn process_handle_stats(_pid: u32) -> Result<HandleStats, OrganizerError> {
+    Err(OrganizerError::Unsupported("Handle counts are Windows only".into()))
 }
 
 #[tauri::command]
-async fn stream_server_status(app: tauri::AppHandle) -> Result<(), String> {
-    use std::io::{BufRead, BufReader};
-    use std::process::{Command, Stdio};
+fn get_process_handles(pid: u32) -> Result<HandleStats, OrganizerError> {
+    process_handle_stats(pid)
+}
 
-    // Emit connecting step
-    let _ = app.emit("server-status:step", ServerStatusStep {
-        step: "connecting".to_string(),
-        data: None,
-    });
+#[derive(serde::Serialize)]
+struct FdLeader {
+    pid: u32,
+    name: String,
+    open_fds: u32, // Handle count on Windows
+}
 
-    // Spawn SSH process
-    let child = Command::new("ssh")
-        .args(["-o", "ConnectTimeout=10", "ubuntu@51.210.150.25", "/home/ubuntu/server-status.sh"])
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn()
-        .map_err(|e| format!("Failed to spawn SSH: {}", e))?;
+// None when the process is gone or not ours to inspect
+#[cfg(target_os = "linux")]
+fn process_fd_count(pid: u32) -> Option<u32> {
+    let entries = std::fs::read_dir(format!("/proc/{}/fd", pid)).ok()?;
+    Some(entries.count() as u32)
+}
 
-    let stdout = child.stdout.ok_or("Failed to capture stdout")?;
-    let reader = BufReader::new(stdout);
+// proc_pidinfo(PROC_PIDLISTFDS) fills one proc_fdinfo { i32 fd, u32 type } per open FD.
+// A null buffer returns a size estimate, the second call the bytes actually written.
+#[cfg(target_os = "macos")]
+fn process_fd_count(pid: u32) -> Option<u32> {
+    const FDINFO_SIZE: usize = 8;
+    let estimate = unsafe { proc_pidinfo(pid as i32, PROC_PIDLISTFDS, 0, std::ptr::null_mut(), 0) };
+    if estimate <= 0 {
+        return None;
+    }
+    // Room for FDs opened between the two calls
+    let mut buffer = vec![0u8; estimate as usize + 32 * FDINFO_SIZE];
+    let written = unsafe {
+        proc_pidinfo(pid as i32, PROC_PIDLISTFDS, 0, buffer.as_mut_ptr().cast(), buffer.len() as i32)
+    };
+    (written > 0).then_some((written as usize / FDINFO_SIZE) as u32)
+}
 
-    for line in reader.lines() {
-        let line = line.map_err(|e| format!("Read error: {}", e))?;
-        if line.is_empty() {
-            continue;
-        }
+#[cfg(target_os = "windows")]
+fn process_fd_count(pid: u32) -> Option<u32> {
+    process_handle_stats(pid).ok().map(|stats| stats.handle_count)
+}
 
-        // Parse the JSON line
-        let parsed: serde_json::Value = serde_json::from_str(&line)
-            .map_err(|e| format!("JSON parse error: {} for line: {}", e, line))?;
+// System-wide FD ranking, most open first, to spot a leaker without opening each process
+#[tauri::command]
+async fn get_fd_leaders(top_n: usize) -> Result<Vec<FdLeader>, OrganizerError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let sys = process_names_system(sysinfo::ProcessesToUpdate::All);
+        let mut leaders: Vec<FdLeader> = sys
+            .processes()
+            .iter()
+            .filter_map(|(pid, process)| {
+                let pid = pid.as_u32();
+                Some(FdLeader {
+                    pid,
+                    name: process.name().to_string_lossy().to_string(),
+                    open_fds: process_fd_count(pid)?,
+                })
+            })
+            .collect();
+        leaders.sort_by(|a, b| b.open_fds.cmp(&a.open_fds).then_with(|| a.pid.cmp(&b.pid)));
+        leaders.truncate(top_n);
+        leaders
+    })
+    .await
+    .map_err(OrganizerError::from)
+}
 
-        let step = parsed.get("step")
-            .and_then(|v| v.as_str())
-            .unwrap_or("unknown")
-            .to_string();
+#[derive(serde::Serialize)]
+struct PageinLeader {
+    pid: u32,
+    name: String,
+    pageins_per_sec: f64,
+    total_pageins: u64,
+    interval_ms: u64,
+}
 
-        let data = parsed.get("data").cloned();
+// Previous page-in counter per pid, keyed with the start time so a reused pid starts over
+#[cfg(target_os = "macos")]
+#[derive(Default)]
+struct PageinState {
+    previous: Mutex<HashMap<u32, (u64, u64, Instant)>>,
+}
 
-        // Emit the step
-        let _ = app.emit("server-status:step", ServerStatusStep {
-            step,
-            data,
+// macOS: page-in rate from the ri_pageins deltas since the previous call. A process seen
+// for the first time reports 0 until the next call, like get_per_disk_io.
+#[cfg(target_os = "macos")]
+#[tauri::command]
+async fn get_pagein_leaders(app: AppHandle, top_n: usize) -> Result<Vec<PageinLeader>, OrganizerError> {
+    let state = app
+        .try_state::<Arc<PageinState>>()
+        .ok_or_else(|| OrganizerError::NotFound("Page-in state".into()))?
+        .inner()
+        .clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let sys = process_names_system(sysinfo::ProcessesToUpdate::All);
+        let now = Instant::now();
+        let mut previous = state.previous.lock()?;
+        let mut current = HashMap::new();
+        let mut leaders = Vec::new();
+        for (pid, process) in sys.processes() {
+            let pid = pid.as_u32();
+            let Some(rusage) = proc_rusage(pid) else { continue };
+            let (start, pageins) = (rusage.ri_proc_start_abstime, rusage.ri_pageins);
+            let (pageins_per_sec, interval_ms) = match previous.get(&pid) {
+                Some((before_start, before, at)) if *before_start == start => {
+                    let secs = now.duration_since(*at).as_secs_f64();
+                    let rate = if secs > 0.0 { pageins.saturating_sub(*before) as f64 / secs } else { 0.0 };
+                    (rate, now.duration_since(*at).as_millis() as u64)
+                }
+                _ => (0.0, 0),
+            };
+            current.insert(pid, (start, pageins, now));
+            leaders.push(PageinLeader {
+                pid,
+                name: process.name().to_string_lossy().to_string(),
+                pageins_per_sec,
+                total_pageins: pageins,
+                interval_ms,
+            });
+        }
+        // Only keep live processes
+        *previous = current;
+
+        leaders.sort_by(|a, b| {
+            b.pageins_per_sec
+                .total_cmp(&a.pageins_per_sec)
+                .then_with(|| b.total_pageins.cmp(&a.total_pageins))
         });
-    }
+        leaders.truncate(top_n);
+        Ok(leaders)
+    })
+    .await?
+}
 
-    Ok(())
+#[cfg(not(target_os = "macos"))]
+#[tauri::command]
+fn get_pagein_leaders(_top_n: usize) -> Result<Vec<PageinLeader>, OrganizerError> {
+    Err(OrganizerError::Unsupported("Page-in counters are macOS only".into()))
 }
 
-// macOS: use df to get accurate free space (sysinfo includes purgeable space)
-#[cfg(target_os = "macos")]
+#[derive(serde::Serialize)]
+struct SwapLeader {
+    pid: u32,
+    name: String,
+    swap_mb: f64,
+}
+
+// "VmSwap:     1234 kB" in /proc/<pid>/status; None for kernel threads and unreadable pids
+#[cfg(target_os = "linux")]
+fn process_swap_kb(pid: u32) -> Option<u64> {
+    let status = std::fs::read_to_string(format!("/proc/{}/status", pid)).ok()?;
+    let line = status.lines().find(|l| l.starts_with("VmSwap:"))?;
+    line.trim_start_matches("VmSwap:").split_whitespace().next()?.parse().ok()
+}
+
+// Linux only: per-process swap isn't exposed on macOS or Windows
+#[cfg(target_os = "linux")]
 #[tauri::command]
-fn get_disk_space() -> Result<DiskSpace, String> {
-    let output = std::process::Command::new("df")
-        .args(["-k", "/"])
-        .output()
-        .map_err(|e| e.to_string())?;
+async fn get_swap_leaders(top_n: usize) -> Result<Vec<SwapLeader>, OrganizerError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let sys = process_names_system(sysinfo::ProcessesToUpdate::All);
+        let mut leaders: Vec<SwapLeader> = sys
+            .processes()
+            .iter()
+            .filter_map(|(pid, process)| {
+                let swap_kb = process_swap_kb(pid.as_u32()).filter(|kb| *kb > 0)?;
+                Some(SwapLeader {
+                    pid: pid.as_u32(),
+                    name: process.name().to_string_lossy().to_string(),
+                    swap_mb: swap_kb as f64 / 1024.0,
+                })
+            })
+            .collect();
+        leaders.sort_by(|a, b| b.swap_mb.total_cmp(&a.swap_mb).then_with(|| a.pid.cmp(&b.pid)));
+        leaders.truncate(top_n);
+        leaders
+    })
+    .await
+    .map_err(OrganizerError::from)
+}
+
+#[cfg(not(target_os = "linux"))]
+#[tauri::command]
+fn get_swap_leaders(_top_n: usize) -> Result<Vec<SwapLeader>, OrganizerError> {
+    Err(OrganizerError::Unsupported("Per-process swap is Linux only".into()))
+}
+
+// All descendants of a process (children, grandchildren...), breadth first
+fn descendant_pids(sys: &sysinfo::System, root: sysinfo::Pid) -> Vec<sysinfo::Pid> {
+    let mut children: HashMap<sysinfo::Pid, Vec<sysinfo::Pid>> = HashMap::new();
+    for (pid, process) in sys.processes() {
+        if let Some(parent) = process.parent() {
+            if parent != *pid {
+                children.entry(parent).or_default().push(*pid);
+            }
+        }
+    }
+
+    let mut descendants = Vec::new();
+    let mut queue = VecDeque::from([root]);
+    while let Some(pid) = queue.pop_front() {
+        for child in children.get(&pid).into_iter().flatten() {
+            if *child != root && !descendants.contains(child) {
+                descendants.push(*child);
+                queue.push_back(*child);
+            }
+        }
+    }
+    descendants
+}
+
+// Far beyond any real tree; only there in case parent links are inconsistent mid-refresh
+const ANCESTRY_MAX_DEPTH: usize = 64;
+
+// The process then its parent, grandparent... up to the root (launchd / init / System),
+// from one refresh of every process so the chain is consistent
+#[tauri::command]
+async fn get_process_ancestry(pid: u32) -> Result<Vec<ProcessMemory>, OrganizerError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+
+        let mut sys = System::new();
+        sys.refresh_processes_specifics(ProcessesToUpdate::All, true, ProcessRefreshKind::new().with_memory());
+        if sys.process(Pid::from_u32(pid)).is_none() {
+            return Err(OrganizerError::NotFound(format!("Process {}", pid)));
+        }
+
+        let to_mb = |b: u64| b as f64 / 1_048_576.0;
+        let mut chain = Vec::new();
+        let mut seen = HashSet::new();
+        let mut current = Some(Pid::from_u32(pid));
+        while let Some(pid) = current {
+            if chain.len() >= ANCESTRY_MAX_DEPTH || !seen.insert(pid) {
+                break;
+            }
+            let Some(process) = sys.process(pid) else { break };
+            chain.push(ProcessMemory {
+                pid: pid.as_u32(),
+                name: process.name().to_string_lossy().to_string(),
+                cwd: None,
+                memory_mb: to_mb(process_footprint_bytes(pid.as_u32(), process)),
+                virtual_mb: to_mb(process.virtual_memory()),
+                start_time: process.start_time(),
+                cpu_usage: None,
+                disk_read_bytes: None,
+                disk_write_bytes: None,
+            });
+            current = process.parent();
+        }
+        Ok(chain)
+    })
+    .await?
+}
+
+#[derive(serde::Serialize)]
+struct SubtreeMemory {
+    pid: u32,
+    own_mb: f64,          // The process itself
+    total_mb: f64,        // Process plus all descendants
+    descendant_count: usize,
+}
+
+// Real footprint of apps that fork helpers (browsers, IDEs): the process plus its whole subtree
+#[tauri::command]
+async fn get_subtree_memory(pid: u32) -> Result<SubtreeMemory, OrganizerError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        use sysinfo::{Pid, ProcessesToUpdate, System};
+
+        let mut sys = System::new();
+        sys.refresh_processes(ProcessesToUpdate::All, true);
+
+        let root = Pid::from_u32(pid);
+        let process = sys
+            .process(root)
+            .ok_or_else(|| OrganizerError::NotFound(format!("Process {}", pid)))?;
+
+        let to_mb = |b: u64| b as f64 / 1_048_576.0;
+        let own = process_footprint_bytes(pid, process);
+        let descendants = descendant_pids(&sys, root);
+        let descendants_bytes: u64 = descendants
+            .iter()
+            .filter_map(|child| sys.process(*child).map(|p| process_footprint_bytes(child.as_u32(), p)))
+            .sum();
+
+        Ok(SubtreeMemory {
+            pid,
+            own_mb: to_mb(own),
+            total_mb: to_mb(own + descendants_bytes),
+            descendant_count: descendants.len(),
+        })
+    })
+    .await?
+}
+
+// Stateful per-process poller for live charts: one long-lived System so CPU and disk
+// counters are measured between calls, plus the previous sample per PID for deltas
+const POLL_SAMPLE_TTL_SECS: u64 = 60;
+
+struct PollSample {
+    at: Instant,
+    start_time: u64,
+    cpu_usage: f32,
+    memory_mb: f64,
+}
+
+struct ProcessPollState {
+    sys: Mutex<sysinfo::System>,
+    samples: Mutex<HashMap<u32, PollSample>>,
+}
+
+#[derive(serde::Serialize)]
+struct ProcessPoll {
+    pid: u32,
+    cpu_usage: f32,
+    memory_mb: f64,
+    cpu_delta: f32,       // Change since the previous poll
+    mem_delta_mb: f64,
+    disk_read_bps: f64,   // Averaged over the interval since the previous poll
+    disk_write_bps: f64,
+    interval_ms: u64,     // 0 on the first poll (no deltas yet)
+}
+
+#[tauri::command]
+fn poll_process(app: AppHandle, pid: u32, normalize: Option<bool>) -> Result<ProcessPoll, OrganizerError> {
+    use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate};
+
+    let state = app
+        .try_state::<Arc<ProcessPollState>>()
+        .ok_or_else(|| OrganizerError::NotFound("Process poll state".into()))?;
+
+    let sysinfo_pid = Pid::from_u32(pid);
+    let mut sys = state.sys.lock()?;
+    sys.refresh_processes_specifics(
+        ProcessesToUpdate::Some(&[sysinfo_pid]),
+        true,
+        ProcessRefreshKind::new().with_cpu().with_memory().with_disk_usage(),
+    );
+
+    let mut samples = state.samples.lock()?;
+    samples.retain(|_, s| s.at.elapsed() < Duration::from_secs(POLL_SAMPLE_TTL_SECS));
+
+    let Some(process) = sys.process(sysinfo_pid) else {
+        samples.remove(&pid);
+        return Err(OrganizerError::NotFound(format!("Process {}", pid)));
+    };
+
+    let now = Instant::now();
+    let cpu_usage = process.cpu_usage();
+    let memory_mb = process_footprint_bytes(pid, process) as f64 / 1_048_576.0;
+    let start_time = process.start_time();
+    let disk_usage = process.disk_usage();
+
+    // A reused PID starts over
+    let previous = samples.get(&pid).filter(|s| s.start_time == start_time);
+    let poll = match previous {
+        Some(prev) => {
+            let interval = now.duration_since(prev.at);
+            let secs = interval.as_secs_f64().max(0.001);
+            ProcessPoll {
+                pid,
+                cpu_usage,
+                memory_mb,
+                cpu_delta: cpu_usage - prev.cpu_usage,
+                mem_delta_mb: memory_mb - prev.memory_mb,
+                disk_read_bps: disk_usage.read_bytes as f64 / secs,
+                disk_write_bps: disk_usage.written_bytes as f64 / secs,
+                interval_ms: interval.as_millis() as u64,
+            }
+        }
+        None => ProcessPoll {
+            pid,
+            cpu_usage,
+            memory_mb,
+            cpu_delta: 0.0,
+            mem_delta_mb: 0.0,
+            disk_read_bps: 0.0,
+            disk_write_bps: 0.0,
+            interval_ms: 0,
+        },
+    };
+
+    samples.insert(pid, PollSample { at: now, start_time, cpu_usage, memory_mb });
+
+    // Samples stay per core so switching conventions between calls keeps deltas consistent
+    let normalize = normalize.unwrap_or(false);
+    Ok(ProcessPoll {
+        cpu_usage: normalize_cpu(poll.cpu_usage, normalize),
+        cpu_delta: normalize_cpu(poll.cpu_delta, normalize),
+        ..poll
+    })
+}
+
+#[derive(serde::Serialize)]
+struct ProcMemBreakdown {
+    pid: u32,
+    footprint_mb: f64,
+    // What footprint_mb measures: "phys_footprint" (macOS) or "pss" (Linux: Pss + SwapPss).
+    // The process list and ProcessDetails show footprint on macOS but RSS on Linux, which
+    // matches resident_mb here, not the proportional footprint.
+    footprint_metric: &'static str,
+    resident_mb: f64,
+    wired_mb: f64,
+    compressed_mb: Option<f64>, // None when the platform doesn't expose it per process
+    swapped_mb: Option<f64>,
+}
+
+// macOS: proc_pidinfo(PROC_PIDTASKINFO) layout
+#[cfg(target_os = "macos")]
+#[repr(C)]
+struct ProcTaskInfo {
+    pti_virtual_size: u64,
+    pti_resident_size: u64,
+    pti_total_user: u64,
+    pti_total_system: u64,
+    pti_threads_user: u64,
+    pti_threads_system: u64,
+    pti_policy: i32,
+    pti_faults: i32,
+    pti_pageins: i32,
+    pti_cow_faults: i32,
+    pti_messages_sent: i32,
+    pti_messages_received: i32,
+    pti_syscalls_mach: i32,
+    pti_syscalls_unix: i32,
+    pti_csw: i32,
+    pti_threadnum: i32,
+    pti_numrunning: i32,
+    pti_priority: i32,
+}
+
+#[cfg(target_os = "macos")]
+extern "C" {
+    fn proc_pidinfo(pid: i32, flavor: i32, arg: u64, buffer: *mut std::ffi::c_void, size: i32) -> i32;
+}
+
+#[cfg(target_os = "macos")]
+const PROC_PIDLISTFDS: i32 = 1;
+#[cfg(target_os = "macos")]
+const PROC_PIDTASKINFO: i32 = 4;
+
+// macOS: resident from task info, footprint/wired from rusage.
+// Compressed and swapped pages need the target's task port (task_info TASK_VM_INFO),
+// which isn't available for other processes without entitlements, so they stay None.
+#[cfg(target_os = "macos")]
+#[tauri::command]
+fn get_process_memory_breakdown(pid: u32) -> Result<ProcMemBreakdown, OrganizerError> {
+    let size = std::mem::size_of::<ProcTaskInfo>() as i32;
+    let mut info: ProcTaskInfo = unsafe { std::mem::zeroed() };
+    let written = unsafe { proc_pidinfo(pid as i32, PROC_PIDTASKINFO, 0, (&mut info as *mut ProcTaskInfo).cast(), size) };
+    if written != size {
+        let err = std::io::Error::last_os_error();
+        return Err(match err.raw_os_error() {
+            Some(1) | Some(13) => OrganizerError::PermissionDenied(format!("Process {}", pid)), // EPERM/EACCES
+            _ => OrganizerError::NotFound(format!("Process {}", pid)),
+        });
+    }
+
+    let to_mb = |b: u64| b as f64 / 1_048_576.0;
+    let (footprint, wired) = proc_rusage(pid)
+        .map(|r| (r.ri_phys_footprint, r.ri_wired_size))
+        .unwrap_or((info.pti_resident_size, 0));
+
+    Ok(ProcMemBreakdown {
+        pid,
+        footprint_mb: to_mb(footprint),
+        footprint_metric: "phys_footprint",
+        resident_mb: to_mb(info.pti_resident_size),
+        wired_mb: to_mb(wired),
+        compressed_mb: None,
+        swapped_mb: None,
+    })
+}
+
+// Linux: /proc/<pid>/smaps_rollup (values in kB). Footprint is the proportional share
+// (Pss + SwapPss), wired is mlocked memory. zswap isn't accounted per process.
+#[cfg(target_os = "linux")]
+#[tauri::command]
+fn get_process_memory_breakdown(pid: u32) -> Result<ProcMemBreakdown, OrganizerError> {
+    let content = std::fs::read_to_string(format!("/proc/{}/smaps_rollup", pid))?;
+
+    let mut fields: HashMap<&str, u64> = HashMap::new();
+    for line in content.lines() {
+        let mut parts = line.split_whitespace();
+        if let (Some(key), Some(value)) = (parts.next(), parts.next()) {
+            if let (Some(key), Ok(kb)) = (key.strip_suffix(':'), value.parse::<u64>()) {
+                fields.insert(key, kb);
+            }
+        }
+    }
+    if !fields.contains_key("Rss") {
+        return Err(OrganizerError::Parse(format!("No Rss in smaps_rollup for process {}", pid)));
+    }
+
+    let kb = |key: &str| fields.get(key).copied().unwrap_or(0);
+    let to_mb = |kb: u64| kb as f64 / 1024.0;
+
+    Ok(ProcMemBreakdown {
+        pid,
+        footprint_mb: to_mb(kb("Pss") + kb("SwapPss")),
+        footprint_metric: "pss",
+        resident_mb: to_mb(kb("Rss")),
+        wired_mb: to_mb(kb("Locked")),
+        compressed_mb: None,
+        swapped_mb: Some(to_mb(kb("Swap"))),
+    })
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+#[tauri::command]
+fn get_process_memory_breakdown(_pid: u32) -> Result<ProcMemBreakdown, OrganizerError> {
+    Err(OrganizerError::Unsupported("Per-process memory breakdown is not available on this platform".into()))
+}
+
+#[derive(serde::Serialize)]
+struct DiskSpaceDetailed {
+    total_gb: f64,
+    available_gb: f64,              // Real available space (without purgeable on macOS)
+    available_with_purgeable_gb: f64, // Available space including purgeable
+    purgeable_gb: f64,              // macOS only, 0 on Windows
+    used_gb: f64,
+    // Linux filesystems keep reserved blocks (ext4: 5% by default) that only root may write to.
+    // free_for_user_gb is statvfs f_bavail (what an unprivileged process can use),
+    // free_total_gb is f_bfree (includes the reserve). Equal on macOS and Windows.
+    free_for_user_gb: f64,
+    free_total_gb: f64,
+    reserved_gb: f64,
+    unit_base: &'static str,
+}
+
+// Progressive server status step payload
+#[derive(Clone, serde::Serialize)]
+struct ServerStatusStep {
+    step: String,
+    data: Option<serde_json::Value>,
+}
+
+// Server status target, also the values substituted into a custom ssh command template
+const SERVER_STATUS_USER: &str = "ubuntu";
+const SERVER_STATUS_HOST: &str = "51.210.150.25";
+const SERVER_STATUS_PORT: &str = "22";
+const SERVER_STATUS_COMMAND: &str = "/home/ubuntu/server-status.sh";
+
+// Split a command line into argv like a POSIX shell would (quotes and backslash escapes only)
+fn split_command_line(line: &str) -> Result<Vec<String>, String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_arg = false;
+    let mut quote: Option<char> = None;
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some('"'), '\\') | (None, '\\') => {
+                let escaped = chars.next().ok_or("Trailing backslash")?;
+                current.push(escaped);
+                in_arg = true;
+            }
+            (Some(_), c) => current.push(c),
+            (None, '\'') | (None, '"') => {
+                quote = Some(c);
+                in_arg = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_arg {
+                    args.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            (None, c) => {
+                current.push(c);
+                in_arg = true;
+            }
+        }
+    }
+
+    if quote.is_some() {
+        return Err("Unterminated quote".to_string());
+    }
+    if in_arg {
+        args.push(current);
+    }
+    Ok(args)
+}
+
+#[tauri::command]
+fn get_ssh_command_template(app: AppHandle) -> Option<String> {
+    app.store("settings.json")
+        .ok()
+        .and_then(|store| store.get(SETTINGS_SSH_COMMAND_TEMPLATE))
+        .and_then(|v| v.as_str().map(|s| s.to_string()))
+        .filter(|s| !s.trim().is_empty())
+}
+
+// e.g. "gcloud compute ssh {user}@{host} --command {command}"; None restores the built-in ssh call
+#[tauri::command]
+fn set_ssh_command_template(app: AppHandle, template: Option<String>) -> Result<(), String> {
+    let template = template.filter(|t| !t.trim().is_empty());
+    if let Some(template) = &template {
+        if split_command_line(template)?.is_empty() {
+            return Err("Empty command template".to_string());
+        }
+    }
+    save_setting(&app, SETTINGS_SSH_COMMAND_TEMPLATE, serde_json::json!(template));
+    Ok(())
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::metadata(path).is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+// First `name` on PATH (with the usual extensions on Windows)
+fn find_in_path(name: &str) -> Option<std::path::PathBuf> {
+    let extensions: &[&str] = if cfg!(windows) { &["", ".exe", ".cmd", ".bat"] } else { &[""] };
+    std::env::split_paths(&std::env::var_os("PATH")?)
+        .flat_map(|dir| extensions.iter().map(move |ext| dir.join(format!("{}{}", name, ext))))
+        .find(|candidate| is_executable(candidate))
+}
+
+fn check_ssh_binary(path: &str) -> Result<(), String> {
+    let path = Path::new(path);
+    if !path.exists() {
+        return Err(format!("ssh binary not found: {}", path.display()));
+    }
+    if !is_executable(path) {
+        return Err(format!("ssh binary is not executable: {}", path.display()));
+    }
+    Ok(())
+}
+
+#[tauri::command]
+fn get_ssh_binary(app: AppHandle) -> Option<String> {
+    app.store("settings.json")
+        .ok()
+        .and_then(|store| store.get(SETTINGS_SSH_BINARY))
+        .and_then(|v| v.as_str().map(|s| s.to_string()))
+        .filter(|s| !s.trim().is_empty())
+}
+
+// None goes back to the ssh found on PATH
+#[tauri::command]
+fn set_ssh_binary(app: AppHandle, path: Option<String>) -> Result<(), String> {
+    let path = path.map(|p| p.trim().to_string()).filter(|p| !p.is_empty());
+    if let Some(path) = &path {
+        check_ssh_binary(path)?;
+    }
+    save_setting(&app, SETTINGS_SSH_BINARY, serde_json::json!(path));
+    Ok(())
+}
+
+// The configured ssh, checked right before spawning since it can vanish after being saved
+fn resolve_ssh_binary(app: &AppHandle) -> Result<String, String> {
+    match get_ssh_binary(app.clone()) {
+        Some(path) => check_ssh_binary(&path).map(|_| path),
+        None => find_in_path("ssh")
+            .map(|path| path.to_string_lossy().to_string())
+            .ok_or_else(|| "ssh binary not found: no ssh on PATH".to_string()),
+    }
+}
+
+// argv for the server status stream: the custom template if set, plain ssh otherwise.
+// Placeholders are substituted after splitting so values never change the argument boundaries.
+fn server_status_argv(app: &AppHandle) -> Result<Vec<String>, String> {
+    let Some(template) = get_ssh_command_template(app.clone()) else {
+        // Keepalives make a dead connection fail instead of hanging
+        return Ok([
+            resolve_ssh_binary(app)?.as_str(),
+            "-o", "ConnectTimeout=10",
+            "-o", "ServerAliveInterval=15",
+            "-o", "ServerAliveCountMax=3",
+            "-p", SERVER_STATUS_PORT,
+            &format!("{}@{}", SERVER_STATUS_USER, SERVER_STATUS_HOST),
+            SERVER_STATUS_COMMAND,
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect());
+    };
+
+    let mut argv: Vec<String> = split_command_line(&template)?
+        .into_iter()
+        .map(|arg| {
+            arg.replace("{user}", SERVER_STATUS_USER)
+                .replace("{host}", SERVER_STATUS_HOST)
+                .replace("{port}", SERVER_STATUS_PORT)
+                .replace("{command}", SERVER_STATUS_COMMAND)
+        })
+        .collect();
+    if argv.is_empty() {
+        return Err("Empty command template".to_string());
+    }
+    // A template calling bare ssh gets the configured binary too
+    if argv[0] == "ssh" {
+        argv[0] = resolve_ssh_binary(app)?;
+    }
+    Ok(argv)
+}
+
+// Last completed server status per target, kept in memory and in the app data dir
+// so a stale result can be shown while a fresh stream runs (or the server is down)
+const SERVER_STATUS_CACHE_FILE: &str = "last_server_status.json";
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+struct LastStatus {
+    target: String,
+    steps: HashMap<String, serde_json::Value>, // Step name -> data (disk, memory, containers, dirs...)
+    completed_at: u64,                         // Unix timestamp of the "done" step
+}
+
+#[derive(Default)]
+struct ServerStatusCache {
+    last: Mutex<HashMap<String, LastStatus>>,
+}
+
+fn server_status_target() -> String {
+    format!("{}@{}", SERVER_STATUS_USER, SERVER_STATUS_HOST)
+}
+
+fn server_status_cache_path(app: &AppHandle) -> Option<std::path::PathBuf> {
+    app.path().app_data_dir().ok().map(|dir| dir.join(SERVER_STATUS_CACHE_FILE))
+}
+
+fn load_server_status_cache(app: &AppHandle) -> HashMap<String, LastStatus> {
+    server_status_cache_path(app)
+        .and_then(|path| std::fs::read(path).ok())
+        .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+        .unwrap_or_default()
+}
+
+fn cache_server_status(app: &AppHandle, status: LastStatus) {
+    let Some(cache) = app.try_state::<Arc<ServerStatusCache>>() else { return };
+    let Ok(mut last) = cache.last.lock() else { return };
+    last.insert(status.target.clone(), status);
+
+    // Best effort: the in-memory copy is enough for this session
+    if let (Some(path), Ok(json)) = (server_status_cache_path(app), serde_json::to_vec(&*last)) {
+        if let Some(dir) = path.parent() {
+            let _ = std::fs::create_dir_all(dir);
+        }
+        let _ = std::fs::write(path, json);
+    }
+}
+
+// None target means the built-in server
+#[tauri::command]
+fn get_last_server_status(app: AppHandle, target_name: Option<String>) -> Result<Option<LastStatus>, String> {
+    let cache = app
+        .try_state::<Arc<ServerStatusCache>>()
+        .ok_or("Server status cache not found")?;
+    let target = target_name.unwrap_or_else(server_status_target);
+    let last = cache.last.lock().map_err(|e| e.to_string())?;
+    Ok(last.get(&target).cloned())
+}
+
+// Seconds without any line from the server before the stream counts as stalled (0 = never)
+const SERVER_STATUS_DEFAULT_STALL_SECS: u64 = 60;
+const SERVER_STATUS_MAX_RECONNECTS: u32 = 2;
+
+#[tauri::command]
+fn get_server_status_stall_timeout(app: AppHandle) -> u64 {
+    app.store("settings.json")
+        .ok()
+        .and_then(|store| store.get(SETTINGS_SERVER_STATUS_STALL_SECS))
+        .and_then(|v| v.as_u64())
+        .unwrap_or(SERVER_STATUS_DEFAULT_STALL_SECS)
+}
+
+#[tauri::command]
+fn set_server_status_stall_timeout(app: AppHandle, secs: u64) -> Result<(), String> {
+    save_setting(&app, SETTINGS_SERVER_STATUS_STALL_SECS, serde_json::json!(secs));
+    Ok(())
+}
+
+#[tauri::command]
+async fn stream_server_status(app: tauri::AppHandle) -> Result<(), String> {
+    let stall_secs = get_server_status_stall_timeout(app.clone());
+    let argv = server_status_argv(&app)?;
+    let mut reconnects = 0;
+
+    loop {
+        // Emit connecting step
+        let _ = app.emit("server-status:step", ServerStatusStep {
+            step: "connecting".to_string(),
+            data: None,
+        });
+
+        if !stream_server_status_once(&app, &argv, stall_secs)? || shutdown_signal(&app).is_stopping() {
+            return Ok(());
+        }
+
+        // Stalled: the ssh process was killed, reconnect a limited number of times
+        let reconnecting = reconnects < SERVER_STATUS_MAX_RECONNECTS;
+        let _ = app.emit("server-status:step", ServerStatusStep {
+            step: "stalled".to_string(),
+            data: Some(serde_json::json!({ "idle_secs": stall_secs, "reconnecting": reconnecting })),
+        });
+        if !reconnecting {
+            return Err(format!("No data from server for {}s", stall_secs));
+        }
+        reconnects += 1;
+    }
+}
+
+// One run of the status script. Returns true if it stalled and was terminated.
+fn stream_server_status_once(app: &AppHandle, argv: &[String], stall_secs: u64) -> Result<bool, String> {
+    use std::process::{Command, Stdio};
+    use std::sync::mpsc::RecvTimeoutError;
+
+    // Spawn SSH process (or the templated wrapper)
+    let mut child = Command::new(&argv[0])
+        .args(&argv[1..])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to spawn {}: {}", argv[0], e))?;
+
+    let stdout = child.stdout.take().ok_or("Failed to capture stdout")?;
+    let mut steps: HashMap<String, serde_json::Value> = HashMap::new();
+
+    // Killed by the shutdown sequence if the app quits mid-stream
+    let child = Arc::new(Mutex::new(child));
+    let shutdown = shutdown_signal(app);
+    shutdown.track_child(&child);
+
+    // Read on a separate thread so the loop below can time out between lines
+    let (tx, rx) = std::sync::mpsc::channel();
+    forward_lines(stdout, tx, |line| line);
+
+    let result = (|| -> Result<bool, String> {
+        loop {
+            let line = if stall_secs == 0 {
+                match rx.recv() {
+                    Ok(line) => line,
+                    Err(_) => break,
+                }
+            } else {
+                match rx.recv_timeout(Duration::from_secs(stall_secs)) {
+                    Ok(line) => line,
+                    Err(RecvTimeoutError::Disconnected) => break,
+                    Err(RecvTimeoutError::Timeout) => return Ok(true),
+                }
+            };
+
+            let line = line.map_err(|e| format!("Read error: {}", e))?;
+            if line.is_empty() {
+                continue;
+            }
+
+            // Parse the JSON line
+            let parsed: serde_json::Value = serde_json::from_str(&line)
+                .map_err(|e| format!("JSON parse error: {} for line: {}", e, line))?;
+
+            let step = parsed.get("step")
+                .and_then(|v| v.as_str())
+                .unwrap_or("unknown")
+                .to_string();
+
+            let data = parsed.get("data").cloned();
+
+            // Remember data steps; the set becomes the last known status once "done" arrives
+            if step == "done" {
+                cache_server_status(app, LastStatus {
+                    target: server_status_target(),
+                    steps: std::mem::take(&mut steps),
+                    completed_at: unix_now(),
+                });
+            } else if let Some(data) = &data {
+                steps.insert(step.clone(), data.clone());
+            }
+
+            // Emit the step
+            let _ = app.emit("server-status:step", ServerStatusStep {
+                step,
+                data,
+            });
+        }
+        Ok(false)
+    })();
+
+    // Stalled or failed: don't leave ssh running
+    shutdown.untrack_child(&child);
+    if let Ok(mut child) = child.lock() {
+        if !matches!(result, Ok(false)) {
+            let _ = child.kill();
+        }
+        let _ = child.wait();
+    }
+    result
+}
+
+// Forward each line of a child pipe to a channel from a helper thread, so the consumer can time out
+fn forward_lines<R, T>(reader: R, tx: std::sync::mpsc::Sender<T>, wrap: impl Fn(std::io::Result<String>) -> T + Send + 'static)
+where
+    R: std::io::Read + Send + 'static,
+    T: Send + 'static,
+{
+    use std::io::{BufRead, BufReader};
+
+    std::thread::spawn(move || {
+        for line in BufReader::new(reader).lines() {
+            if tx.send(wrap(line)).is_err() {
+                break;
+            }
+        }
+    });
+}
+
+const DEFAULT_COMMAND_TIMEOUT_SECS: u64 = 300;
+
+// Own process group on unix, so kill_process_tree also reaches what the command spawned
+fn isolate_process_group(command: &mut std::process::Command) {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        command.process_group(0);
+    }
+    #[cfg(not(unix))]
+    let _ = command;
+}
+
+// Kill a child and its descendants: its process group on unix (see isolate_process_group),
+// the whole tree on Windows. The direct kill covers children that don't lead a group.
+fn kill_process_tree(child: &mut std::process::Child) {
+    use std::process::{Command, Stdio};
+
+    let pid = child.id().to_string();
+    #[cfg(unix)]
+    let mut tree_kill = Command::new("kill");
+    #[cfg(unix)]
+    tree_kill.args(["-KILL", "--", &format!("-{}", pid)]);
+    #[cfg(windows)]
+    let mut tree_kill = Command::new("taskkill");
+    #[cfg(windows)]
+    tree_kill.args(["/T", "/F", "/PID", &pid]);
+    let _ = tree_kill.stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::null()).status();
+    let _ = child.kill();
+}
+
+// Output kept from a hook command (64 KiB per stream)
+const HOOK_OUTPUT_MAX_BYTES: u64 = 64 * 1024;
+// A background grandchild can keep the pipes open after the command exits: stop waiting
+const HOOK_OUTPUT_DRAIN_SECS: u64 = 1;
+
+struct HookOutput {
+    code: Option<i32>,
+    stdout: String,
+    stderr: String,
+    timed_out: bool,
+}
+
+// Run a user-configured command to completion, killed (with its process tree) after
+// timeout_secs (0 = no limit) or when the app quits
+fn run_hook_command(shutdown: &Shutdown, command: &mut std::process::Command, timeout_secs: u64) -> std::io::Result<HookOutput> {
+    use std::io::Read;
+    use std::process::Stdio;
+
+    isolate_process_group(command);
+    let mut child = command.stdin(Stdio::null()).stdout(Stdio::piped()).stderr(Stdio::piped()).spawn()?;
+    let drain = |stream: Option<Box<dyn Read + Send>>| {
+        let (tx, rx) = std::sync::mpsc::channel();
+        if let Some(stream) = stream {
+            std::thread::spawn(move || {
+                let mut bytes = Vec::new();
+                let _ = stream.take(HOOK_OUTPUT_MAX_BYTES).read_to_end(&mut bytes);
+                let _ = tx.send(String::from_utf8_lossy(&bytes).into_owned());
+            });
+        }
+        rx
+    };
+    let stdout = drain(child.stdout.take().map(|s| Box::new(s) as Box<dyn Read + Send>));
+    let stderr = drain(child.stderr.take().map(|s| Box::new(s) as Box<dyn Read + Send>));
+
+    let child = Arc::new(Mutex::new(child));
+    shutdown.track_child(&child);
+    let deadline = (timeout_secs > 0).then(|| Instant::now() + Duration::from_secs(timeout_secs));
+    let mut timed_out = false;
+    let status = loop {
+        let Ok(mut child) = child.lock() else { break None };
+        match child.try_wait() {
+            Ok(Some(status)) => break Some(status),
+            Ok(None) if deadline.is_some_and(|d| Instant::now() >= d) || shutdown.is_stopping() => {
+                timed_out = !shutdown.is_stopping();
+                kill_process_tree(&mut child);
+                break child.wait().ok();
+            }
+            Ok(None) => {}
+            Err(_) => break None,
+        }
+        drop(child);
+        std::thread::sleep(Duration::from_millis(100));
+    };
+    shutdown.untrack_child(&child);
+
+    let drain_timeout = Duration::from_secs(HOOK_OUTPUT_DRAIN_SECS);
+    Ok(HookOutput {
+        code: status.and_then(|s| s.code()),
+        stdout: stdout.recv_timeout(drain_timeout).unwrap_or_default(),
+        stderr: stderr.recv_timeout(drain_timeout).unwrap_or_default(),
+        timed_out,
+    })
+}
+
+struct RunningCommand {
+    child: Option<Arc<Mutex<std::process::Child>>>, // None while the event name is reserved but not spawned yet
+    cancelled: Arc<AtomicBool>,
+}
+
+// Commands started by run_command, keyed by their event name
+#[derive(Default)]
+struct CommandRunState {
+    runs: Mutex<HashMap<String, RunningCommand>>,
+}
+
+impl CommandRunState {
+    // Past a poisoned lock too, or the event name would stay taken forever
+    fn release(&self, event_name: &str) {
+        self.runs.lock().unwrap_or_else(|e| e.into_inner()).remove(event_name);
+    }
+}
+
+#[derive(Clone, serde::Serialize)]
+struct CommandOutputLine {
+    stream: String, // "stdout" | "stderr"
+    line: String,
+}
+
+#[derive(Clone, serde::Serialize)]
+struct CommandExit {
+    code: Option<i32>, // None when killed by a signal
+    timed_out: bool,
+    cancelled: bool,
+}
+
+#[tauri::command]
+fn get_command_timeout(app: AppHandle) -> u64 {
+    app.store("settings.json")
+        .ok()
+        .and_then(|store| store.get(SETTINGS_COMMAND_TIMEOUT_SECS))
+        .and_then(|v| v.as_u64())
+        .unwrap_or(DEFAULT_COMMAND_TIMEOUT_SECS)
+}
+
+// 0 disables the timeout
+#[tauri::command]
+fn set_command_timeout(app: AppHandle, secs: u64) -> Result<(), String> {
+    save_setting(&app, SETTINGS_COMMAND_TIMEOUT_SECS, serde_json::json!(secs));
+    Ok(())
+}
+
+// Programs run_command may start, exactly as passed to it. Empty (the default) disables it.
+#[tauri::command]
+fn get_run_command_allowlist(app: AppHandle) -> Vec<String> {
+    app.store("settings.json")
+        .ok()
+        .and_then(|store| store.get(SETTINGS_RUN_COMMAND_ALLOWLIST))
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default()
+}
+
+#[tauri::command]
+fn set_run_command_allowlist(app: AppHandle, programs: Vec<String>) -> Result<(), String> {
+    let programs: Vec<String> = programs.into_iter().map(|p| p.trim().to_string()).filter(|p| !p.is_empty()).collect();
+    save_setting(&app, SETTINGS_RUN_COMMAND_ALLOWLIST, serde_json::json!(programs));
+    Ok(())
+}
+
+// Run a maintenance command and stream its output: each stdout/stderr line is emitted as
+// `event_name`, then `<event_name>:exit` once it ends. Only programs on the allowlist run,
+// executed directly, never through a shell, so arguments can't inject extra commands.
+// The command gets its own process group: timeout, cancel and quit kill what it spawned too,
+// and output still held open by a background grandchild is only waited for briefly.
+#[tauri::command]
+fn run_command(
+    app: AppHandle,
+    program: String,
+    args: Vec<String>,
+    cwd: Option<String>,
+    event_name: String,
+) -> Result<(), String> {
+    use std::process::{Command, Stdio};
+    use std::sync::mpsc::RecvTimeoutError;
+
+    if event_name.is_empty()
+        || !event_name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '/' | ':' | '_'))
+    {
+        return Err(format!("Invalid event name: {}", event_name));
+    }
+    if !get_run_command_allowlist(app.clone()).contains(&program) {
+        return Err(format!("{} is not in the allowed commands", program));
+    }
+    let state = app
+        .try_state::<Arc<CommandRunState>>()
+        .ok_or("Command state not initialized")?
+        .inner()
+        .clone();
+    // Check and reserve the event name under one lock, so two calls can't both start
+    let cancelled = Arc::new(AtomicBool::new(false));
+    {
+        let mut runs = state.runs.lock().map_err(|e| e.to_string())?;
+        if runs.contains_key(&event_name) {
+            return Err(format!("A command is already running for {}", event_name));
+        }
+        runs.insert(event_name.clone(), RunningCommand { child: None, cancelled: cancelled.clone() });
+    }
+
+    let mut command = Command::new(&program);
+    command.args(&args).stdin(Stdio::null()).stdout(Stdio::piped()).stderr(Stdio::piped());
+    if let Some(cwd) = &cwd {
+        command.current_dir(cwd);
+    }
+    isolate_process_group(&mut command);
+    let mut child = match command.spawn() {
+        Ok(child) => child,
+        Err(e) => {
+            state.release(&event_name);
+            return Err(format!("Failed to spawn {}: {}", program, e));
+        }
+    };
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    if let Some(stdout) = child.stdout.take() {
+        forward_lines(stdout, tx.clone(), |line| ("stdout", line));
+    }
+    if let Some(stderr) = child.stderr.take() {
+        forward_lines(stderr, tx, |line| ("stderr", line));
+    }
+
+    let child = Arc::new(Mutex::new(child));
+    let shutdown = shutdown_signal(&app);
+    shutdown.track_child(&child);
+    // Registered past a poisoned lock too: the child is running and has to stay reachable
+    if let Some(run) = state.runs.lock().unwrap_or_else(|e| e.into_inner()).get_mut(&event_name) {
+        run.child = Some(child.clone());
+    }
+    // Cancelled while spawning: the loop below sees the exit
+    if cancelled.load(Ordering::SeqCst) {
+        if let Ok(mut child) = child.lock() {
+            kill_process_tree(&mut child);
+        }
+    }
+
+    let timeout_secs = get_command_timeout(app.clone());
+    std::thread::spawn(move || {
+        let deadline = (timeout_secs > 0).then(|| Instant::now() + Duration::from_secs(timeout_secs));
+        let mut timed_out = false;
+        let mut exited_at: Option<Instant> = None;
+        let mut stragglers = false;
+
+        // Both pipes close when the process tree exits (or is killed by cancel/shutdown)
+        loop {
+            let now = Instant::now();
+            if exited_at.is_none() {
+                match child.lock().map(|mut c| c.try_wait()) {
+                    Ok(Ok(None)) => {}
+                    _ => exited_at = Some(now),
+                }
+            }
+            match exited_at {
+                Some(at) if now >= at + Duration::from_secs(HOOK_OUTPUT_DRAIN_SECS) => {
+                    stragglers = true;
+                    break;
+                }
+                None if deadline.is_some_and(|d| now >= d) => {
+                    timed_out = true;
+                    break;
+                }
+                _ => {}
+            }
+            match rx.recv_timeout(Duration::from_millis(100)) {
+                Ok((stream, Ok(line))) => {
+                    let _ = app.emit(&event_name, CommandOutputLine { stream: stream.to_string(), line });
+                }
+                Ok((_, Err(_))) | Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+        }
+
+        shutdown.untrack_child(&child);
+        let code = match child.lock() {
+            Ok(mut child) => {
+                if timed_out || stragglers {
+                    kill_process_tree(&mut child);
+                }
+                child.wait().ok().and_then(|status| status.code())
+            }
+            Err(_) => None,
+        };
+        state.release(&event_name);
+
+        let _ = app.emit(&format!("{}:exit", event_name), CommandExit {
+            code,
+            timed_out,
+            cancelled: cancelled.load(Ordering::SeqCst),
+        });
+    });
+
+    Ok(())
+}
+
+// Kill a command started by run_command; false if nothing is running under that event name
+#[tauri::command]
+fn cancel_command(app: AppHandle, event_name: String) -> Result<bool, String> {
+    let state = app
+        .try_state::<Arc<CommandRunState>>()
+        .ok_or("Command state not initialized")?;
+    let runs = state.runs.lock().map_err(|e| e.to_string())?;
+    let Some(run) = runs.get(&event_name) else {
+        return Ok(false);
+    };
+    run.cancelled.store(true, Ordering::SeqCst);
+    // Not spawned yet: run_command kills it as soon as it's registered
+    if let Some(Ok(mut child)) = run.child.as_ref().map(|c| c.lock()) {
+        kill_process_tree(&mut child);
+    }
+    Ok(true)
+}
+
+// macOS: use df to get accurate free space (sysinfo includes purgeable space)
+#[cfg(target_os = "macos")]
+#[tauri::command]
+fn get_disk_space() -> Result<DiskSpace, OrganizerError> {
+    let output = std::process::Command::new("df")
+        .args(["-k", "/"])
+        .output()?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let line = stdout.lines().nth(1).ok_or_else(|| OrganizerError::Parse("No df output".into()))?;
+    let parts: Vec<&str> = line.split_whitespace().collect();
+
+    let total_kb: f64 = parts.get(1).ok_or_else(|| OrganizerError::Parse("No total".into()))?.parse()?;
+    let available_kb: f64 = parts.get(3).ok_or_else(|| OrganizerError::Parse("No available".into()))?.parse()?;
+
+    Ok(DiskSpace {
+        free_gb: available_kb * 1024.0 / format::gb_divisor(),
+        total_gb: total_kb * 1024.0 / format::gb_divisor(),
+        unit_base: format::unit_base(),
+    })
+}
+
+// macOS: use Swift to get detailed disk space including purgeable via Foundation API
+#[cfg(target_os = "macos")]
+#[tauri::command]
+fn get_disk_space_detailed(for_user: Option<bool>) -> Result<DiskSpaceDetailed, OrganizerError> {
+    // No root-reserved blocks on APFS: for_user makes no difference
+    let _ = for_user;
+
+    // Swift one-liner to get volume capacities via Foundation API
+    // Returns: total|available|availableForImportantUsage
+    let swift_code = r#"
+import Foundation
+let url = URL(fileURLWithPath: "/")
+let values = try! url.resourceValues(forKeys: [.volumeTotalCapacityKey, .volumeAvailableCapacityKey, .volumeAvailableCapacityForImportantUsageKey])
+print("\(values.volumeTotalCapacity ?? 0)|\(values.volumeAvailableCapacity ?? 0)|\(values.volumeAvailableCapacityForImportantUsage ?? 0)")
+"#;
+
+    let output = std::process::Command::new("swift")
+        .args(["-e", swift_code])
+        .output()
+        .map_err(|e| OrganizerError::Io(format!("Failed to run swift: {}", e)))?;
+
+    if !output.status.success() {
+        return Err(OrganizerError::Io(format!("Swift failed: {}", String::from_utf8_lossy(&output.stderr))));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let parts: Vec<&str> = stdout.trim().split('|').collect();
+
+    if parts.len() != 3 {
+        return Err(OrganizerError::Parse(format!("Invalid swift output: {}", stdout)));
+    }
+
+    let total_bytes: u64 = parts[0].parse()?;
+    let available_bytes: u64 = parts[1].parse()?;
+    let available_with_purgeable_bytes: u64 = parts[2].parse()?;
+
+    let purgeable_bytes = available_with_purgeable_bytes.saturating_sub(available_bytes);
+    let used_bytes = total_bytes.saturating_sub(available_with_purgeable_bytes);
+
+    let bytes_to_gb = format::to_gb;
+
+    Ok(DiskSpaceDetailed {
+        total_gb: bytes_to_gb(total_bytes),
+        available_gb: bytes_to_gb(available_bytes),
+        available_with_purgeable_gb: bytes_to_gb(available_with_purgeable_bytes),
+        purgeable_gb: bytes_to_gb(purgeable_bytes),
+        used_gb: bytes_to_gb(used_bytes),
+        free_for_user_gb: bytes_to_gb(available_bytes),
+        free_total_gb: bytes_to_gb(available_bytes),
+        reserved_gb: 0.0,
+        unit_base: format::unit_base(),
+    })
+}
+
+// Windows/Linux: use sysinfo
+#[cfg(not(target_os = "macos"))]
+#[tauri::command]
+fn get_disk_space() -> Result<DiskSpace, OrganizerError> {
+    let disks = Disks::new_with_refreshed_list();
+
+    let disk = disks
+        .iter()
+        .find(|d| {
+            let mount = d.mount_point().to_string_lossy();
+            mount == "/" || mount == "C:\\"
+        })
+        .or_else(|| disks.iter().next())
+        .ok_or_else(|| OrganizerError::NotFound("No disk found".into()))?;
+
+    let total_bytes = disk.total_space() as f64;
+    let available_bytes = disk.available_space() as f64;
+
+    Ok(DiskSpace {
+        free_gb: available_bytes / format::gb_divisor(),
+        total_gb: total_bytes / format::gb_divisor(),
+        unit_base: format::unit_base(),
+    })
+}
+
+// (available to unprivileged users, free including the root reserve) in bytes
+#[cfg(target_os = "linux")]
+fn statvfs_free_bytes(mount_point: &Path) -> Option<(u64, u64)> {
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = std::ffi::CString::new(mount_point.as_os_str().as_bytes()).ok()?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statvfs(path.as_ptr(), &mut stat) } != 0 {
+        return None;
+    }
+    let block = stat.f_frsize as u64;
+    Some((stat.f_bavail as u64 * block, stat.f_bfree as u64 * block))
+}
+
+// Windows/Linux: use sysinfo (no purgeable concept).
+// for_user (default true) picks which free figure drives available_gb and used_gb on Linux.
+#[cfg(not(target_os = "macos"))]
+#[tauri::command]
+fn get_disk_space_detailed(for_user: Option<bool>) -> Result<DiskSpaceDetailed, OrganizerError> {
+    let disks = Disks::new_with_refreshed_list();
+
+    let disk = disks
+        .iter()
+        .find(|d| {
+            let mount = d.mount_point().to_string_lossy();
+            mount == "/" || mount == "C:\\"
+        })
+        .or_else(|| disks.iter().next())
+        .ok_or_else(|| OrganizerError::NotFound("No disk found".into()))?;
+
+    let total_bytes = disk.total_space();
+
+    #[cfg(target_os = "linux")]
+    let (user_free_bytes, total_free_bytes) =
+        statvfs_free_bytes(disk.mount_point()).unwrap_or((disk.available_space(), disk.available_space()));
+    #[cfg(not(target_os = "linux"))]
+    let (user_free_bytes, total_free_bytes) = (disk.available_space(), disk.available_space());
+
+    let available_bytes = if for_user.unwrap_or(true) { user_free_bytes } else { total_free_bytes };
+    let used_bytes = total_bytes.saturating_sub(available_bytes);
+
+    let bytes_to_gb = format::to_gb;
+
+    Ok(DiskSpaceDetailed {
+        total_gb: bytes_to_gb(total_bytes),
+        available_gb: bytes_to_gb(available_bytes),
+        available_with_purgeable_gb: bytes_to_gb(available_bytes), // Same as available on Windows
+        purgeable_gb: 0.0, // No purgeable concept on Windows
+        used_gb: bytes_to_gb(used_bytes),
+        free_for_user_gb: bytes_to_gb(user_free_bytes),
+        free_total_gb: bytes_to_gb(total_free_bytes),
+        reserved_gb: bytes_to_gb(total_free_bytes.saturating_sub(user_free_bytes)),
+        unit_base: format::unit_base(),
+    })
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+struct DiskInfo {
+    name: String,
+    mount_point: String,
+    file_system: String,
+    total_gb: f64,
+    available_gb: f64,
+    used_gb: f64,
+    used_pct: f64, // 0-100
+    is_removable: bool,
+    // Disk snapshots saved before the units setting are in GiB
+    #[serde(default = "binary_unit_base")]
+    unit_base: String,
+}
+
+fn binary_unit_base() -> String {
+    "binary".to_string()
+}
+
+// All mounted volumes via sysinfo (available space includes purgeable on macOS), fullest first
+#[tauri::command]
+fn get_all_disks() -> Vec<DiskInfo> {
+    all_disks()
+}
+
+fn all_disks() -> Vec<DiskInfo> {
+    let disks = sysinfo::Disks::new_with_refreshed_list();
+    let bytes_to_gb = format::to_gb;
+
+    let mut result: Vec<DiskInfo> = disks
+        .iter()
+        .map(|d| {
+            let total = d.total_space();
+            let available = d.available_space();
+            DiskInfo {
+                name: d.name().to_string_lossy().to_string(),
+                mount_point: d.mount_point().to_string_lossy().to_string(),
+                file_system: d.file_system().to_string_lossy().to_string(),
+                total_gb: bytes_to_gb(total),
+                available_gb: bytes_to_gb(available),
+                used_gb: bytes_to_gb(total.saturating_sub(available)),
+                used_pct: if total > 0 { total.saturating_sub(available) as f64 / total as f64 * 100.0 } else { 0.0 },
+                is_removable: d.is_removable(),
+                unit_base: format::unit_base().to_string(),
+            }
+        })
+        .collect();
+
+    result.sort_by(|a, b| b.used_pct.partial_cmp(&a.used_pct).unwrap_or(std::cmp::Ordering::Equal));
+    result
+}
+
+#[derive(serde::Serialize)]
+struct DiskIoEntry {
+    mount_point: String,
+    device: String,
+    read_bps: Option<f64>,  // None where reads and writes aren't reported separately (macOS)
+    write_bps: Option<f64>,
+    total_bps: f64,
+    interval_ms: u64,       // Sampling window, 0 on the first call (rates start at 0)
+}
+
+// Cumulative counters of a block device
+#[cfg_attr(target_os = "windows", allow(dead_code))]
+struct DiskIoCounters {
+    read_bytes: Option<u64>,
+    write_bytes: Option<u64>,
+    total_bytes: u64,
+}
+
+// Previous counters per device, so rates are deltas between two calls
+// (unused on Windows, where typeperf reports rates directly)
+#[derive(Default)]
+#[cfg_attr(target_os = "windows", allow(dead_code))]
+struct DiskIoState {
+    previous: Mutex<HashMap<String, (DiskIoCounters, Instant)>>,
+}
+
+// Linux: /proc/diskstats, sectors are always 512 bytes there
+#[cfg(target_os = "linux")]
+fn disk_io_counters() -> HashMap<String, DiskIoCounters> {
+    let stats = std::fs::read_to_string("/proc/diskstats").unwrap_or_default();
+    stats
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let sectors_read: u64 = fields.get(5)?.parse().ok()?;
+            let sectors_written: u64 = fields.get(9)?.parse().ok()?;
+            let (read, write) = (sectors_read * 512, sectors_written * 512);
+            Some((
+                fields[2].to_string(),
+                DiskIoCounters { read_bytes: Some(read), write_bytes: Some(write), total_bytes: read + write },
+            ))
+        })
+        .collect()
+}
+
+// Kernel name of the device behind a mount ("/dev/mapper/root" -> "dm-0")
+#[cfg(target_os = "linux")]
+fn mount_io_device(disk: &sysinfo::Disk) -> Option<String> {
+    let device = std::path::PathBuf::from(disk.name());
+    let device = std::fs::canonicalize(&device).unwrap_or(device);
+    Some(device.file_name()?.to_string_lossy().to_string())
+}
+
+// macOS: `iostat -d -I -K` shows kilobytes transferred since boot per whole disk,
+// reads and writes combined
+#[cfg(target_os = "macos")]
+fn disk_io_counters() -> HashMap<String, DiskIoCounters> {
+    let Some(out) = command_stdout("iostat", &["-d", "-I", "-K", "-c", "1"]) else { return HashMap::new() };
+    let mut lines = out.lines();
+    let disks: Vec<&str> = lines.next().unwrap_or("").split_whitespace().collect();
+    // Second line is the "KB/t xfrs KB" header, repeated per disk
+    let values: Vec<&str> = lines.nth(1).unwrap_or("").split_whitespace().collect();
+    disks
+        .iter()
+        .enumerate()
+        .filter_map(|(i, name)| {
+            let kb: f64 = values.get(i * 3 + 2)?.parse().ok()?;
+            let total_bytes = (kb * 1024.0) as u64;
+            Some((name.to_string(), DiskIoCounters { read_bytes: None, write_bytes: None, total_bytes }))
+        })
+        .collect()
+}
+
+// "/dev/disk3s1s1" -> "disk3". APFS volumes report the synthesized container disk,
+// which iostat may not list; those mounts then show no traffic.
+#[cfg(target_os = "macos")]
+fn mount_io_device(disk: &sysinfo::Disk) -> Option<String> {
+    let name = disk.name().to_string_lossy();
+    let name = name.strip_prefix("/dev/").unwrap_or(&name);
+    let digits = name.strip_prefix("disk")?.chars().take_while(|c| c.is_ascii_digit()).count();
+    (digits > 0).then(|| name[..4 + digits].to_string())
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn disk_io_counters() -> HashMap<String, DiskIoCounters> {
+    HashMap::new()
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn mount_io_device(_disk: &sysinfo::Disk) -> Option<String> {
+    None
+}
+
+// Rates from the counter deltas since the previous call
+#[cfg(not(target_os = "windows"))]
+fn per_disk_io(state: &DiskIoState) -> Result<Vec<DiskIoEntry>, OrganizerError> {
+    let now = Instant::now();
+    let mut counters = disk_io_counters();
+    let mut previous = state.previous.lock()?;
+    let disks = sysinfo::Disks::new_with_refreshed_list();
+
+    let rate = |now_bytes: u64, before_bytes: u64, secs: f64| {
+        if secs > 0.0 { now_bytes.saturating_sub(before_bytes) as f64 / secs } else { 0.0 }
+    };
+
+    let mut entries = Vec::new();
+    for disk in disks.iter() {
+        let Some(device) = mount_io_device(disk) else { continue };
+        let Some(current) = counters.get(&device) else { continue };
+        let (read_bps, write_bps, total_bps, interval_ms) = match previous.get(&device) {
+            Some((before, at)) => {
+                let secs = now.duration_since(*at).as_secs_f64();
+                let split = |now_bytes: Option<u64>, before_bytes: Option<u64>| {
+                    Some(rate(now_bytes?, before_bytes?, secs))
+                };
+                (
+                    split(current.read_bytes, before.read_bytes),
+                    split(current.write_bytes, before.write_bytes),
+                    rate(current.total_bytes, before.total_bytes, secs),
+                    now.duration_since(*at).as_millis() as u64,
+                )
+            }
+            None => (current.read_bytes.map(|_| 0.0), current.write_bytes.map(|_| 0.0), 0.0, 0),
+        };
+        entries.push(DiskIoEntry {
+            mount_point: disk.mount_point().to_string_lossy().to_string(),
+            device,
+            read_bps,
+            write_bps,
+            total_bps,
+            interval_ms,
+        });
+    }
+
+    // Keep the counters of every device seen (several mounts can share one device)
+    for (device, counters) in counters.drain() {
+        previous.insert(device, (counters, now));
+    }
+    Ok(entries)
+}
+
+// Windows: the LogicalDisk performance counters already are per-volume rates
+#[cfg(target_os = "windows")]
+fn per_disk_io(_state: &DiskIoState) -> Result<Vec<DiskIoEntry>, OrganizerError> {
+    let output = std::process::Command::new("typeperf")
+        .args([
+            r"\LogicalDisk(*)\Disk Read Bytes/sec",
+            r"\LogicalDisk(*)\Disk Write Bytes/sec",
+            "-sc",
+            "1",
+        ])
+        .output()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    // CSV: a header row of counter paths, then one row of quoted values
+    let mut rows = stdout.lines().filter(|l| l.starts_with('"'));
+    let unquote = |row: &str| -> Vec<String> { row.split("\",\"").map(|f| f.trim_matches('"').to_string()).collect() };
+    let header = unquote(rows.next().ok_or_else(|| OrganizerError::Parse("No typeperf header".into()))?);
+    let values = unquote(rows.next().ok_or_else(|| OrganizerError::Parse("No typeperf sample".into()))?);
+
+    let mut rates: Vec<(String, f64, f64)> = Vec::new();
+    for (path, value) in header.iter().zip(values.iter()).skip(1) {
+        // "\\HOST\LogicalDisk(C:)\Disk Read Bytes/sec"
+        let Some(instance) = path.split_once("LogicalDisk(").and_then(|(_, rest)| rest.split_once(')')).map(|(i, _)| i) else { continue };
+        if instance == "_Total" || instance.starts_with("HarddiskVolume") {
+            continue;
+        }
+        let value: f64 = value.trim().parse().unwrap_or(0.0);
+        let mount_point = format!("{}\\", instance);
+        let entry = match rates.iter_mut().find(|(m, _, _)| *m == mount_point) {
+            Some(entry) => entry,
+            None => {
+                rates.push((mount_point, 0.0, 0.0));
+                rates.last_mut().ok_or_else(|| OrganizerError::Parse("typeperf".into()))?
+            }
+        };
+        if path.ends_with("Read Bytes/sec") {
+            entry.1 = value;
+        } else {
+            entry.2 = value;
+        }
+    }
+
+    Ok(rates
+        .into_iter()
+        .map(|(mount_point, read, write)| DiskIoEntry {
+            device: mount_point.trim_end_matches('\\').to_string(),
+            mount_point,
+            read_bps: Some(read),
+            write_bps: Some(write),
+            total_bps: read + write,
+            interval_ms: 1000,
+        })
+        .collect())
+}
+
+// Read/write throughput per mounted volume, for the storage panel next to get_all_disks
+#[tauri::command]
+async fn get_per_disk_io(app: AppHandle) -> Result<Vec<DiskIoEntry>, OrganizerError> {
+    let state = app
+        .try_state::<Arc<DiskIoState>>()
+        .ok_or_else(|| OrganizerError::NotFound("Disk I/O state".into()))?
+        .inner()
+        .clone();
+    tauri::async_runtime::spawn_blocking(move || per_disk_io(&state)).await?
+}
+
+#[derive(serde::Serialize)]
+struct NetworkConfig {
+    dns_servers: Vec<String>,
+    default_gateway: Option<String>,
+    hostname: String,
+}
+
+fn command_stdout(program: &str, args: &[&str]) -> Option<String> {
+    let output = std::process::Command::new(program).args(args).output().ok()?;
+    output.status.success().then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn push_unique(list: &mut Vec<String>, value: &str) {
+    if !value.is_empty() && !list.iter().any(|v| v == value) {
+        list.push(value.to_string());
+    }
+}
+
+// scutil lists every resolver ("nameserver[0] : 1.1.1.1"), netstat the default route
+#[cfg(target_os = "macos")]
+fn read_network_config() -> (Vec<String>, Option<String>) {
+    let mut dns = Vec::new();
+    for line in command_stdout("scutil", &["--dns"]).unwrap_or_default().lines() {
+        let line = line.trim();
+        if line.starts_with("nameserver[") {
+            if let Some((_, server)) = line.split_once(':') {
+                push_unique(&mut dns, server.trim());
+            }
+        }
+    }
+
+    let gateway = command_stdout("netstat", &["-rn", "-f", "inet"])
+        .unwrap_or_default()
+        .lines()
+        .find_map(|line| {
+            let mut cols = line.split_whitespace();
+            (cols.next()? == "default").then(|| cols.next().map(str::to_string))?
+        });
+    (dns, gateway)
+}
+
+#[cfg(target_os = "linux")]
+fn read_network_config() -> (Vec<String>, Option<String>) {
+    let mut dns = Vec::new();
+    for line in std::fs::read_to_string("/etc/resolv.conf").unwrap_or_default().lines() {
+        let mut words = line.split_whitespace();
+        if words.next() == Some("nameserver") {
+            if let Some(server) = words.next() {
+                push_unique(&mut dns, server);
+            }
+        }
+    }
+
+    // "default via 192.168.1.1 dev wlan0 ..."
+    let gateway = command_stdout("ip", &["route", "show", "default"])
+        .unwrap_or_default()
+        .lines()
+        .find_map(|line| {
+            let words: Vec<&str> = line.split_whitespace().collect();
+            let via = words.iter().position(|w| *w == "via")?;
+            words.get(via + 1).map(|g| g.to_string())
+        });
+    (dns, gateway)
+}
+
+// ipconfig /all: "   DNS Servers . . . : 1.1.1.1" with extra servers on indented continuation lines.
+// Labels are localized, so match on fragments (French: "Serveurs DNS", "Passerelle par défaut").
+#[cfg(target_os = "windows")]
+fn read_network_config() -> (Vec<String>, Option<String>) {
+    #[derive(PartialEq)]
+    enum Field {
+        Dns,
+        Gateway,
+        Other,
+    }
+
+    let mut dns = Vec::new();
+    let mut gateways = Vec::new();
+    let mut field = Field::Other;
+    for line in command_stdout("ipconfig", &["/all"]).unwrap_or_default().lines() {
+        let value = match line.split_once(" : ") {
+            Some((label, value)) => {
+                let label = label.to_lowercase();
+                field = if label.contains("dns") && !label.contains("suffix") {
+                    Field::Dns
+                } else if label.contains("gateway") || label.contains("passerelle") {
+                    Field::Gateway
+                } else {
+                    Field::Other
+                };
+                value.trim()
+            }
+            None if line.starts_with(' ') && !line.trim().is_empty() => line.trim(),
+            None => {
+                field = Field::Other;
+                continue;
+            }
+        };
+        // Skip non-address values such as "Enabled" or an empty gateway
+        let addr = value.split('%').next().unwrap_or(value);
+        if addr.parse::<std::net::IpAddr>().is_err() {
+            continue;
+        }
+        match field {
+            Field::Dns => push_unique(&mut dns, value),
+            Field::Gateway => push_unique(&mut gateways, value),
+            Field::Other => {}
+        }
+    }
+
+    // Prefer an IPv4 gateway when the adapter also lists a link-local IPv6 one
+    let gateway = gateways
+        .iter()
+        .find(|g| g.parse::<std::net::Ipv4Addr>().is_ok())
+        .or(gateways.first())
+        .cloned();
+    (dns, gateway)
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+fn read_network_config() -> (Vec<String>, Option<String>) {
+    (Vec::new(), None)
+}
+
+// Basic connectivity facts; any source that can't be read just leaves its field empty
+#[tauri::command]
+async fn get_network_config() -> Result<NetworkConfig, OrganizerError> {
+    tauri::async_runtime::spawn_blocking(|| {
+        let (dns_servers, default_gateway) = read_network_config();
+        NetworkConfig {
+            dns_servers,
+            default_gateway,
+            hostname: sysinfo::System::host_name().unwrap_or_default(),
+        }
+    })
+    .await
+    .map_err(OrganizerError::from)
+}
+
+// TCP sockets by state; total also counts the states not broken out (SYN_SENT, FIN_WAIT...)
+#[derive(Default, serde::Serialize)]
+struct SocketSummary {
+    listening: u32,
+    established: u32,
+    time_wait: u32,
+    close_wait: u32,
+    total: u32,
+}
+
+// Linux: `ss -tan`, state first ("ESTAB", "TIME-WAIT"). macOS/Windows: `netstat -an`,
+// state last on the tcp rows ("ESTABLISHED", "LISTENING", "TIME_WAIT").
+fn socket_summary(output: &str, linux: bool) -> SocketSummary {
+    let mut summary = SocketSummary::default();
+    for line in output.lines() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let state = if linux {
+            match fields.first() {
+                Some(state) if *state != "State" => *state,
+                _ => continue,
+            }
+        } else {
+            if !fields.first().is_some_and(|proto| proto.to_ascii_lowercase().starts_with("tcp")) {
+                continue;
+            }
+            match fields.last() {
+                Some(state) => *state,
+                None => continue,
+            }
+        };
+        match state.to_ascii_uppercase().replace('-', "_").as_str() {
+            "LISTEN" | "LISTENING" => summary.listening += 1,
+            "ESTAB" | "ESTABLISHED" => summary.established += 1,
+            "TIME_WAIT" => summary.time_wait += 1,
+            "CLOSE_WAIT" => summary.close_wait += 1,
+            _ => {}
+        }
+        summary.total += 1;
+    }
+    summary
+}
+
+// Counts only, to spot leaks (a growing close_wait) without listing every connection
+#[tauri::command]
+async fn get_socket_summary() -> Result<SocketSummary, OrganizerError> {
+    tauri::async_runtime::spawn_blocking(|| {
+        let linux = cfg!(target_os = "linux");
+        let output = if linux {
+            command_stdout("ss", &["-tan"])
+        } else {
+            command_stdout("netstat", &["-an"])
+        };
+        let output = output.ok_or_else(|| OrganizerError::Unsupported("Socket table unavailable".into()))?;
+        Ok(socket_summary(&output, linux))
+    })
+    .await?
+}
+
+#[derive(serde::Serialize)]
+struct NetMount {
+    mount_point: String,   // Drive letter on Windows
+    server: String,
+    protocol: String,      // smb, nfs, afp, webdav, sshfs
+    connected: bool,
+    latency_ms: Option<f64>, // TCP connect time to the server, only when probing
+}
+
+const NET_MOUNT_PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+// Filesystem type -> (protocol, TCP port used for the reachability probe)
+#[cfg(not(target_os = "windows"))]
+fn network_protocol(fs_type: &str) -> Option<(&'static str, u16)> {
+    match fs_type {
+        "smbfs" | "cifs" | "smb3" => Some(("smb", 445)),
+        "nfs" | "nfs4" => Some(("nfs", 2049)),
+        "afpfs" => Some(("afp", 548)),
+        "webdav" | "davfs" | "fuse.davfs2" => Some(("webdav", 443)),
+        "fuse.sshfs" | "sshfs" | "macfuse" | "osxfuse" => Some(("sshfs", 22)),
+        _ => None,
+    }
+}
+
+// "//user@server/share", "server:/export", "user@host:/path", "\\server\share" -> server
+fn mount_source_server(source: &str) -> String {
+    let s = source.trim_start_matches(['/', '\\']);
+    let s = s.rsplit_once('@').map(|(_, host)| host).unwrap_or(s);
+    s.split(['/', '\\', ':']).next().unwrap_or(s).to_string()
+}
+
+// One `mount` line: "SRC on /mnt/x type cifs (opts)" (Linux) or "SRC on /Volumes/x (smbfs, opts)" (macOS)
+#[cfg(not(target_os = "windows"))]
+fn parse_mount_line(line: &str) -> Option<(String, String, String)> {
+    let (source, rest) = line.split_once(" on ")?;
+    let (mount_point, fs_type) = match rest.split_once(" type ") {
+        Some((mount_point, after)) => (mount_point, after.split_whitespace().next()?),
+        None => {
+            let (mount_point, opts) = rest.rsplit_once(" (")?;
+            (mount_point, opts.split([',', ')']).next()?.trim())
+        }
+    };
+    Some((source.to_string(), mount_point.to_string(), fs_type.to_string()))
+}
+
+#[cfg(not(target_os = "windows"))]
+fn list_network_mounts() -> Result<Vec<(String, String, &'static str, u16)>, OrganizerError> {
+    let output = std::process::Command::new("mount").output()?;
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(parse_mount_line)
+        .filter_map(|(source, mount_point, fs_type)| {
+            let (protocol, port) = network_protocol(&fs_type)?;
+            Some((mount_point, mount_source_server(&source), protocol, port))
+        })
+        .collect())
+}
+
+// Windows: `net use` table (Status, Local, Remote, Network); only SMB shares show up there
+#[cfg(target_os = "windows")]
+fn list_network_mounts() -> Result<Vec<(String, String, &'static str, u16)>, OrganizerError> {
+    let output = std::process::Command::new("net").arg("use").output()?;
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let remote_start = line.find("\\\\")?;
+            let remote = line[remote_start..].split_whitespace().next()?;
+            let local = line[..remote_start]
+                .split_whitespace()
+                .find(|w| w.ends_with(':'))
+                .unwrap_or("")
+                .to_string();
+            Some((local, mount_source_server(remote), "smb", 445))
+        })
+        .collect())
+}
+
+// A dead network mount can block stat for minutes, so probe it on a throwaway thread
+fn mount_responds(mount_point: &str) -> bool {
+    if mount_point.is_empty() {
+        return false;
+    }
+    let path = mount_point.to_string();
+    let (tx, rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        let _ = tx.send(std::fs::metadata(&path).is_ok());
+    });
+    rx.recv_timeout(NET_MOUNT_PROBE_TIMEOUT).unwrap_or(false)
+}
+
+fn tcp_latency_ms(server: &str, port: u16) -> Option<f64> {
+    use std::net::{TcpStream, ToSocketAddrs};
+
+    let addr = (server, port).to_socket_addrs().ok()?.next()?;
+    let started = Instant::now();
+    TcpStream::connect_timeout(&addr, NET_MOUNT_PROBE_TIMEOUT).ok()?;
+    Some(started.elapsed().as_secs_f64() * 1000.0)
+}
+
+// Mounted network shares, so slow or dead ones can be excluded before scanning.
+// With probe, each server also gets a TCP connect round trip (no ICMP privileges needed).
+#[tauri::command]
+async fn get_network_mounts(probe: bool) -> Result<Vec<NetMount>, OrganizerError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let mounts = list_network_mounts()?;
+        Ok(parallel_map(&mounts, default_thread_count(), |(mount_point, server, protocol, port)| {
+            NetMount {
+                mount_point: mount_point.clone(),
+                server: server.clone(),
+                protocol: protocol.to_string(),
+                connected: mount_responds(mount_point),
+                latency_ms: if probe { tcp_latency_ms(server, *port) } else { None },
+            }
+        }))
+    })
+    .await?
+}
+
+#[derive(Clone, serde::Serialize)]
+struct TrashInfo {
+    size_bytes: u64,
+    item_count: u64,
+}
+
+#[derive(Clone, serde::Serialize)]
+struct TrashProgress {
+    phase: String, // "measuring" | "emptying"
+    items_done: u64,
+    item_count: u64,
+    bytes_done: u64,
+}
+
+#[cfg(unix)]
+const TRASH_PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
+// Directories whose children are the trashed items
+#[cfg(target_os = "macos")]
+fn trash_dirs(app: &AppHandle) -> Vec<std::path::PathBuf> {
+    use std::os::unix::fs::MetadataExt;
+
+    let Ok(home) = app.path().home_dir() else { return Vec::new() };
+    let mut dirs = vec![home.join(".Trash")];
+    if let Ok(uid) = std::fs::metadata(&home).map(|m| m.uid()) {
+        if let Ok(volumes) = std::fs::read_dir("/Volumes") {
+            for volume in volumes.flatten() {
+                dirs.push(volume.path().join(".Trashes").join(uid.to_string()));
+            }
+        }
+    }
+    dirs
+}
+
+// XDG trash: files/ holds the items, info/ their .trashinfo records
+#[cfg(all(unix, not(target_os = "macos")))]
+fn trash_dirs(app: &AppHandle) -> Vec<std::path::PathBuf> {
+    let data_home = std::env::var_os("XDG_DATA_HOME")
+        .filter(|v| !v.is_empty())
+        .map(std::path::PathBuf::from)
+        .or_else(|| app.path().home_dir().ok().map(|home| home.join(".local/share")));
+    match data_home {
+        Some(dir) => vec![dir.join("Trash/files"), dir.join("Trash/info")],
+        None => Vec::new(),
+    }
+}
+
+// Bookkeeping entries count toward the size but are not items the user trashed
+#[cfg(unix)]
+fn is_trash_bookkeeping(name: &std::ffi::OsStr) -> bool {
+    let name = name.to_string_lossy();
+    name == ".DS_Store" || name.ends_with(".trashinfo")
+}
+
+// Emits trash:progress at most every TRASH_PROGRESS_INTERVAL so large trashes report as they go
+#[cfg(unix)]
+struct TrashReporter<'a> {
+    app: &'a AppHandle,
+    phase: &'static str,
+    item_count: u64,
+    last: Instant,
+}
+
+#[cfg(unix)]
+impl TrashReporter<'_> {
+    fn report(&mut self, items_done: u64, bytes_done: u64, force: bool) {
+        if !force && self.last.elapsed() < TRASH_PROGRESS_INTERVAL {
+            return;
+        }
+        self.last = Instant::now();
+        let _ = self.app.emit("trash:progress", TrashProgress {
+            phase: self.phase.to_string(),
+            items_done,
+            item_count: self.item_count,
+            bytes_done,
+        });
+    }
+}
+
+#[cfg(unix)]
+fn trash_entries(app: &AppHandle) -> Vec<std::fs::DirEntry> {
+    trash_dirs(app)
+        .iter()
+        .filter_map(|dir| std::fs::read_dir(dir).ok())
+        .flat_map(|entries| entries.flatten())
+        .collect()
+}
+
+#[cfg(unix)]
+fn trash_size(app: &AppHandle) -> Result<TrashInfo, String> {
+    let entries = trash_entries(app);
+    let item_count = entries.iter().filter(|e| !is_trash_bookkeeping(&e.file_name())).count() as u64;
+    let mut reporter = TrashReporter { app, phase: "measuring", item_count, last: Instant::now() };
+
+    let mut info = TrashInfo { size_bytes: 0, item_count };
+    let mut items_done = 0;
+    for entry in &entries {
+        info.size_bytes += path_size_until(&entry.path(), None, None).0;
+        if !is_trash_bookkeeping(&entry.file_name()) {
+            items_done += 1;
+        }
+        reporter.report(items_done, info.size_bytes, false);
+    }
+    Ok(info)
+}
+
+// Deletes every entry it can; entries that fail (permissions, busy volume) are left in place
+#[cfg(unix)]
+fn empty_trash_blocking(app: &AppHandle) -> Result<u64, String> {
+    let entries = trash_entries(app);
+    let item_count = entries.iter().filter(|e| !is_trash_bookkeeping(&e.file_name())).count() as u64;
+    let mut reporter = TrashReporter { app, phase: "emptying", item_count, last: Instant::now() };
+
+    let mut freed = 0;
+    let mut items_done = 0;
+    for entry in &entries {
+        let path = entry.path();
+        let size = path_size_until(&path, None, None).0;
+        let is_dir = std::fs::symlink_metadata(&path).map(|m| m.is_dir()).unwrap_or(false);
+        let removed = if is_dir { std::fs::remove_dir_all(&path) } else { std::fs::remove_file(&path) };
+        if removed.is_ok() {
+            freed += size;
+        }
+        if !is_trash_bookkeeping(&entry.file_name()) {
+            items_done += 1;
+        }
+        reporter.report(items_done, freed, false);
+    }
+    reporter.report(items_done, freed, true);
+    Ok(freed)
+}
+
+// Recycle Bin across all drives
+#[cfg(target_os = "windows")]
+fn trash_size(_app: &AppHandle) -> Result<TrashInfo, String> {
+    use windows_sys::Win32::UI::Shell::{SHQueryRecycleBinW, SHQUERYRBINFO};
+
+    let mut info = SHQUERYRBINFO {
+        cbSize: std::mem::size_of::<SHQUERYRBINFO>() as u32,
+        i64Size: 0,
+        i64NumItems: 0,
+    };
+    let hr = unsafe { SHQueryRecycleBinW(std::ptr::null(), &mut info) };
+    if hr != 0 {
+        return Err(format!("SHQueryRecycleBinW failed: 0x{:08x}", hr));
+    }
+    Ok(TrashInfo {
+        size_bytes: info.i64Size.max(0) as u64,
+        item_count: info.i64NumItems.max(0) as u64,
+    })
+}
+
+// The shell empties the bin in one call, so progress is only reported before and after
+#[cfg(target_os = "windows")]
+fn empty_trash_blocking(app: &AppHandle) -> Result<u64, String> {
+    use windows_sys::Win32::UI::Shell::{
+        SHEmptyRecycleBinW, SHERB_NOCONFIRMATION, SHERB_NOPROGRESSUI, SHERB_NOSOUND,
+    };
+
+    let before = trash_size(app)?;
+    if before.item_count == 0 {
+        return Ok(0);
+    }
+    let progress = |items_done, bytes_done| TrashProgress {
+        phase: "emptying".to_string(),
+        items_done,
+        item_count: before.item_count,
+        bytes_done,
+    };
+    let _ = app.emit("trash:progress", progress(0, 0));
+
+    let flags = SHERB_NOCONFIRMATION | SHERB_NOPROGRESSUI | SHERB_NOSOUND;
+    let hr = unsafe { SHEmptyRecycleBinW(std::ptr::null_mut(), std::ptr::null(), flags) };
+    if hr != 0 {
+        return Err(format!("SHEmptyRecycleBinW failed: 0x{:08x}", hr));
+    }
+
+    let after = trash_size(app).map(|t| t.size_bytes).unwrap_or(0);
+    let freed = before.size_bytes.saturating_sub(after);
+    let _ = app.emit("trash:progress", progress(before.item_count, freed));
+    Ok(freed)
+}
+
+#[cfg(not(any(unix, target_os = "windows")))]
+fn trash_size(_app: &AppHandle) -> Result<TrashInfo, String> {
+    Err("Trash is not supported on this platform".to_string())
+}
+
+#[cfg(not(any(unix, target_os = "windows")))]
+fn empty_trash_blocking(_app: &AppHandle) -> Result<u64, String> {
+    Err("Trash is not supported on this platform".to_string())
+}
+
+#[tauri::command]
+async fn get_trash_size(app: AppHandle) -> Result<TrashInfo, String> {
+    tauri::async_runtime::spawn_blocking(move || trash_size(&app))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+// Permanently deletes the Trash / Recycle Bin contents, returns bytes freed
+#[tauri::command]
+async fn empty_trash(app: AppHandle) -> Result<u64, String> {
+    tauri::async_runtime::spawn_blocking(move || empty_trash_blocking(&app))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+// Point-in-time disk snapshots, oldest evicted first
+const DISK_SNAPSHOTS_MAX: usize = 20;
+
+struct DiskSnapshot {
+    id: String,
+    taken_at: u64,
+    disks: Vec<DiskInfo>,
+}
+
+#[derive(Default)]
+struct DiskSnapshots {
+    snapshots: Mutex<VecDeque<DiskSnapshot>>,
+    next_id: AtomicU64,
+}
+
+#[derive(serde::Serialize)]
+struct DiskDiff {
+    mount_point: String,
+    free_before_gb: Option<f64>, // None: volume mounted since the snapshot
+    free_now_gb: Option<f64>,    // None: volume unmounted since the snapshot
+    free_delta_gb: f64,          // Negative: volume filled up
+    snapshot_taken_at: u64,      // Unix timestamp of the compared snapshot
+}
+
+#[tauri::command]
+fn snapshot_disks(app: AppHandle) -> Result<String, String> {
+    let state = app
+        .try_state::<Arc<DiskSnapshots>>()
+        .ok_or("Disk snapshots state not found")?;
+
+    push_disk_snapshot(&state, unix_now(), get_all_disks())
+}
+
+// Compare a snapshot with the current state, biggest changes first
+#[tauri::command]
+fn diff_disks(app: AppHandle, id: String) -> Result<Vec<DiskDiff>, String> {
+    let state = app
+        .try_state::<Arc<DiskSnapshots>>()
+        .ok_or("Disk snapshots state not found")?;
+
+    let snapshots = state.snapshots.lock().map_err(|e| e.to_string())?;
+    let snapshot = snapshots
+        .iter()
+        .find(|s| s.id == id)
+        .ok_or_else(|| format!("Snapshot {} not found", id))?;
+
+    let current = get_all_disks();
+    let mut diffs: Vec<DiskDiff> = snapshot
+        .disks
+        .iter()
+        .map(|before| {
+            let now = current.iter().find(|d| d.mount_point == before.mount_point);
+            // The units setting may have changed since the snapshot
+            let free_before_gb = format::gb_in_current_base(before.available_gb, &before.unit_base);
+            DiskDiff {
+                mount_point: before.mount_point.clone(),
+                free_before_gb: Some(free_before_gb),
+                free_now_gb: now.map(|d| d.available_gb),
+                free_delta_gb: now.map(|d| d.available_gb - free_before_gb).unwrap_or(0.0),
+                snapshot_taken_at: snapshot.taken_at,
+            }
+        })
+        .collect();
+
+    for now in current.iter().filter(|d| !snapshot.disks.iter().any(|b| b.mount_point == d.mount_point)) {
+        diffs.push(DiskDiff {
+            mount_point: now.mount_point.clone(),
+            free_before_gb: None,
+            free_now_gb: Some(now.available_gb),
+            free_delta_gb: 0.0,
+            snapshot_taken_at: snapshot.taken_at,
+        });
+    }
+
+    diffs.sort_by(|a, b| b.free_delta_gb.abs().partial_cmp(&a.free_delta_gb.abs()).unwrap_or(std::cmp::Ordering::Equal));
+    Ok(diffs)
+}
+
+// Point-in-time process table snapshots, oldest evicted first
+const PROCESS_SNAPSHOTS_MAX: usize = 20;
+
+struct ProcessSnapshot {
+    id: String,
+    taken_at: u64,
+    processes: Vec<ProcessMemory>,
+}
+
+#[derive(Default)]
+struct ProcessSnapshots {
+    snapshots: Mutex<VecDeque<ProcessSnapshot>>,
+    next_id: AtomicU64,
+}
+
+#[derive(serde::Serialize)]
+struct ProcessDiff {
+    pid: u32,
+    name: String,
+    memory_before_mb: Option<f64>, // None: started since the snapshot
+    memory_now_mb: Option<f64>,    // None: exited since the snapshot
+    memory_delta_mb: f64,
+}
+
+fn push_process_snapshot(state: &ProcessSnapshots, taken_at: u64, processes: Vec<ProcessMemory>) -> Result<String, String> {
+    let id = format!("procs-{}", state.next_id.fetch_add(1, Ordering::SeqCst) + 1);
+    let mut snapshots = state.snapshots.lock().map_err(|e| e.to_string())?;
+    snapshots.push_back(ProcessSnapshot { id: id.clone(), taken_at, processes });
+    while snapshots.len() > PROCESS_SNAPSHOTS_MAX {
+        snapshots.pop_front();
+    }
+    Ok(id)
+}
+
+fn push_disk_snapshot(state: &DiskSnapshots, taken_at: u64, disks: Vec<DiskInfo>) -> Result<String, String> {
+    let id = format!("disks-{}", state.next_id.fetch_add(1, Ordering::SeqCst) + 1);
+    let mut snapshots = state.snapshots.lock().map_err(|e| e.to_string())?;
+    snapshots.push_back(DiskSnapshot { id: id.clone(), taken_at, disks });
+    while snapshots.len() > DISK_SNAPSHOTS_MAX {
+        snapshots.pop_front();
+    }
+    Ok(id)
+}
+
+#[tauri::command]
+fn snapshot_processes(app: AppHandle) -> Result<String, String> {
+    let state = app
+        .try_state::<Arc<ProcessSnapshots>>()
+        .ok_or("Process snapshots state not found")?;
+    let processes = with_shared_processes(&app, DetailLevel::Light, |sys| top_processes(sys, usize::MAX, None, &[], DetailLevel::Light))
+        .map_err(|e| e.to_string())?
+        .processes;
+    push_process_snapshot(&state, unix_now(), processes)
+}
+
+// Compare a process snapshot with the current table, biggest memory changes first.
+// Processes are matched on PID and start time so a reused PID counts as exited + started.
+#[tauri::command]
+fn diff_processes(app: AppHandle, id: String) -> Result<Vec<ProcessDiff>, String> {
+    let state = app
+        .try_state::<Arc<ProcessSnapshots>>()
+        .ok_or("Process snapshots state not found")?;
+    let current = with_shared_processes(&app, DetailLevel::Light, |sys| top_processes(sys, usize::MAX, None, &[], DetailLevel::Light))
+        .map_err(|e| e.to_string())?
+        .processes;
+
+    let snapshots = state.snapshots.lock().map_err(|e| e.to_string())?;
+    let snapshot = snapshots
+        .iter()
+        .find(|s| s.id == id)
+        .ok_or_else(|| format!("Snapshot {} not found", id))?;
+
+    let same = |a: &ProcessMemory, b: &ProcessMemory| a.pid == b.pid && a.start_time == b.start_time;
+    let mut diffs: Vec<ProcessDiff> = snapshot
+        .processes
+        .iter()
+        .map(|before| {
+            let now = current.iter().find(|p| same(p, before));
+            ProcessDiff {
+                pid: before.pid,
+                name: before.name.clone(),
+                memory_before_mb: Some(before.memory_mb),
+                memory_now_mb: now.map(|p| p.memory_mb),
+                memory_delta_mb: now.map(|p| p.memory_mb).unwrap_or(0.0) - before.memory_mb,
+            }
+        })
+        .collect();
+    for now in current.iter().filter(|p| !snapshot.processes.iter().any(|b| same(b, p))) {
+        diffs.push(ProcessDiff {
+            pid: now.pid,
+            name: now.name.clone(),
+            memory_before_mb: None,
+            memory_now_mb: Some(now.memory_mb),
+            memory_delta_mb: now.memory_mb,
+        });
+    }
+
+    diffs.sort_by(|a, b| b.memory_delta_mb.abs().partial_cmp(&a.memory_delta_mb.abs()).unwrap_or(std::cmp::Ordering::Equal));
+    Ok(diffs)
+}
+
+// On-disk snapshot: machine metadata plus either kind of snapshot, tagged by "kind"
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+enum SnapshotContent {
+    Processes { processes: Vec<ProcessMemory> },
+    Disks { disks: Vec<DiskInfo> },
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SnapshotFile {
+    hostname: String,
+    os_version: String,
+    app_version: String,
+    taken_at: u64, // Unix timestamp of the original snapshot
+    #[serde(flatten)]
+    content: SnapshotContent,
+}
+
+// Relative paths land in <app data>/snapshots/: only plain names are allowed in them,
+// so "../x.json" can't climb out of that directory
+fn snapshot_file_path(app: &AppHandle, path: &str) -> Result<std::path::PathBuf, String> {
+    let path = std::path::PathBuf::from(path);
+    if path.is_absolute() {
+        return Ok(path);
+    }
+    if !path.components().all(|c| matches!(c, std::path::Component::Normal(_))) {
+        return Err(format!("Invalid snapshot path: {}", path.display()));
+    }
+    let dir = app.path().app_data_dir().map_err(|e| e.to_string())?.join("snapshots");
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    Ok(dir.join(path))
+}
+
+// Write a process ("procs-N") or disk ("disks-N") snapshot to JSON so it survives a restart
+#[tauri::command]
+fn save_snapshot_to_disk(app: AppHandle, id: String, path: String) -> Result<(), String> {
+    let (taken_at, content) = if id.starts_with("procs-") {
+        let state = app
+            .try_state::<Arc<ProcessSnapshots>>()
+            .ok_or("Process snapshots state not found")?;
+        let snapshots = state.snapshots.lock().map_err(|e| e.to_string())?;
+        let snapshot = snapshots.iter().find(|s| s.id == id).ok_or_else(|| format!("Snapshot {} not found", id))?;
+        (snapshot.taken_at, SnapshotContent::Processes { processes: snapshot.processes.clone() })
+    } else {
+        let state = app
+            .try_state::<Arc<DiskSnapshots>>()
+            .ok_or("Disk snapshots state not found")?;
+        let snapshots = state.snapshots.lock().map_err(|e| e.to_string())?;
+        let snapshot = snapshots.iter().find(|s| s.id == id).ok_or_else(|| format!("Snapshot {} not found", id))?;
+        (snapshot.taken_at, SnapshotContent::Disks { disks: snapshot.disks.clone() })
+    };
+
+    let file = SnapshotFile {
+        hostname: sysinfo::System::host_name().unwrap_or_default(),
+        os_version: sysinfo::System::long_os_version().unwrap_or_default(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        taken_at,
+        content,
+    };
+    let json = serde_json::to_string_pretty(&file).map_err(|e| e.to_string())?;
+    let target = snapshot_file_path(&app, &path)?;
+    std::fs::write(&target, json).map_err(|e| format!("Failed to write {}: {}", target.display(), e))
+}
+
+// Load a saved snapshot back into memory; returns its new id for diff_processes / diff_disks
+#[tauri::command]
+fn load_snapshot_from_disk(app: AppHandle, path: String) -> Result<String, String> {
+    let source = snapshot_file_path(&app, &path)?;
+    let json = std::fs::read_to_string(&source).map_err(|e| format!("Failed to read {}: {}", source.display(), e))?;
+    let file: SnapshotFile = serde_json::from_str(&json).map_err(|e| format!("Invalid snapshot file: {}", e))?;
+
+    match file.content {
+        SnapshotContent::Processes { processes } => {
+            let state = app
+                .try_state::<Arc<ProcessSnapshots>>()
+                .ok_or("Process snapshots state not found")?;
+            push_process_snapshot(&state, file.taken_at, processes)
+        }
+        SnapshotContent::Disks { disks } => {
+            let state = app
+                .try_state::<Arc<DiskSnapshots>>()
+                .ok_or("Disk snapshots state not found")?;
+            push_disk_snapshot(&state, file.taken_at, disks)
+        }
+    }
+}
+
+// Directory size scan results, cached per path
+#[derive(Clone, serde::Serialize)]
+struct DirEntry {
+    path: String,
+    name: String,
+    size_bytes: u64,
+    is_dir: bool,
+    incomplete: bool, // Size is a lower bound: the time budget ran out while counting
+}
+
+#[derive(Clone, serde::Serialize)]
+struct DirectoryScan {
+    id: String,             // Stable id of this scan, usable with diff_directory_scans
+    path: String,
+    entries: Vec<DirEntry>, // Immediate children, sorted by size descending
+    total_bytes: u64,
+    duration_ms: u64,       // Time the actual scan took (not the cache lookup)
+    scanned_at: u64,        // Unix timestamp of the scan
+    cached: bool,
+}
+
+// LRU cache of scan results: most recently used at the back.
+// Older scans of a path are kept (until evicted) so they can be diffed against newer ones.
+#[derive(Default)]
+struct ScanCache {
+    scans: Mutex<VecDeque<DirectoryScan>>,
+    next_id: AtomicU64,
+}
+
+const SCAN_CACHE_TTL_SECS: u64 = 300;
+const SCAN_CACHE_MAX_ENTRIES: usize = 16;
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+// Unix timestamp -> "2024-05-01T12:34:56Z" (UTC)
+fn iso8601_utc(secs: u64) -> String {
+    chrono::DateTime::from_timestamp(secs as i64, 0)
+        .unwrap_or_default()
+        .to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+}
+
+#[cfg(unix)]
+fn device_id(meta: &std::fs::Metadata) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+    Some(meta.dev())
+}
+
+#[cfg(not(unix))]
+fn device_id(_meta: &std::fs::Metadata) -> Option<u64> {
+    None
+}
+
+// (device, inode): the same for every hard link to one file
+#[cfg(unix)]
+fn file_id(meta: &std::fs::Metadata) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    Some((meta.dev(), meta.ino()))
+}
+
+#[cfg(not(unix))]
+fn file_id(_meta: &std::fs::Metadata) -> Option<(u64, u64)> {
+    None
+}
+
+// Recursive size of a path: symlinks are not followed and other mounts are not entered.
+// Gives up at the deadline: (bytes counted so far, finished)
+#[cfg(unix)]
+fn path_size_until(path: &Path, root_dev: Option<u64>, deadline: Option<Instant>) -> (u64, bool) {
+    let meta = match std::fs::symlink_metadata(path) {
+        Ok(m) => m,
+        Err(_) => return (0, true),
+    };
+
+    if meta.file_type().is_symlink() {
+        return (0, true);
+    }
+    if !meta.is_dir() {
+        return (meta.len(), true);
+    }
+    if root_dev.is_some() && device_id(&meta) != root_dev {
+        return (0, true);
+    }
+
+    let entries = match std::fs::read_dir(path) {
+        Ok(entries) => entries,
+        Err(_) => return (0, true),
+    };
+
+    let mut total = 0;
+    for entry in entries.flatten() {
+        if deadline.is_some_and(|d| Instant::now() >= d) {
+            return (total, false);
+        }
+        let (size, finished) = path_size_until(&entry.path(), root_dev, deadline);
+        total += size;
+        if !finished {
+            return (total, false);
+        }
+    }
+    (total, true)
+}
+
+// Run f over items on up to `threads` worker threads, results in input order
+fn parallel_map<T: Sync, R: Send>(items: &[T], threads: usize, f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    let next = std::sync::atomic::AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<R>>> = Mutex::new((0..items.len()).map(|_| None).collect());
+
+    std::thread::scope(|scope| {
+        for _ in 0..threads.clamp(1, items.len().max(1)) {
+            scope.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::SeqCst);
+                let Some(item) = items.get(i) else { break };
+                let result = f(item);
+                if let Ok(mut results) = results.lock() {
+                    results[i] = Some(result);
+                }
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap_or_else(|e| e.into_inner())
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+fn default_thread_count() -> usize {
+    std::thread::available_parallelism().map(|n| n.get()).unwrap_or(4)
+}
+
+// Worker threads for directory scans: the saved setting, or one per CPU
+fn scan_thread_count(app: &AppHandle) -> usize {
+    app.store("settings.json")
+        .ok()
+        .and_then(|store| store.get(SETTINGS_SCAN_THREADS))
+        .and_then(|v| v.as_u64())
+        .map(|n| n as usize)
+        .filter(|n| *n > 0)
+        .unwrap_or_else(default_thread_count)
+}
+
+#[tauri::command]
+fn get_scan_threads(app: AppHandle) -> usize {
+    scan_thread_count(&app)
+}
+
+// None (or 0) goes back to one thread per CPU
+#[tauri::command]
+fn set_scan_threads(app: AppHandle, threads: Option<usize>) -> Result<(), String> {
+    let threads = threads.filter(|n| *n > 0);
+    save_setting(&app, SETTINGS_SCAN_THREADS, serde_json::json!(threads));
+    Ok(())
+}
+
+// Recursive sizes of several paths at once. Workers share one queue of directories
+// (work stealing), so a single huge child doesn't serialize the scan. Same guards as
+// path_size_until: symlinks are not followed and other mounts are not entered.
+// on_finished(index) runs once per path, when no work is left for it. Past the deadline,
+// queued directories are dropped: (bytes counted so far, finished) per path.
+fn parallel_path_sizes(
+    paths: &[std::path::PathBuf],
+    root_dev: Option<u64>,
+    threads: usize,
+    deadline: Option<Instant>,
+    on_finished: impl Fn(usize) + Sync,
+) -> Vec<(u64, bool)> {
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::Condvar;
+
+    let totals: Vec<AtomicU64> = paths.iter().map(|_| AtomicU64::new(0)).collect();
+    let cut: Vec<AtomicBool> = paths.iter().map(|_| AtomicBool::new(false)).collect();
+    let queue: Mutex<Vec<(std::path::PathBuf, usize)>> =
+        Mutex::new(paths.iter().cloned().enumerate().map(|(i, p)| (p, i)).collect());
+    // Queued or in progress, overall and per path; only changed under the queue lock
+    let pending = AtomicUsize::new(paths.len());
+    let remaining: Vec<AtomicUsize> = paths.iter().map(|_| AtomicUsize::new(1)).collect();
+    let wake = Condvar::new();
+
+    std::thread::scope(|scope| {
+        for _ in 0..threads.clamp(1, paths.len().max(1)) {
+            scope.spawn(|| loop {
+                let job = {
+                    let Ok(mut jobs) = queue.lock() else { return };
+                    loop {
+                        if let Some(job) = jobs.pop() {
+                            break job;
+                        }
+                        if pending.load(Ordering::SeqCst) == 0 {
+                            return;
+                        }
+                        jobs = match wake.wait(jobs) {
+                            Ok(jobs) => jobs,
+                            Err(_) => return,
+                        };
+                    }
+                };
+
+                let (path, idx) = job;
+                let mut subdirs = Vec::new();
+                if deadline.is_some_and(|d| Instant::now() >= d) {
+                    cut[idx].store(true, Ordering::Relaxed);
+                } else if let Ok(meta) = std::fs::symlink_metadata(&path) {
+                    if meta.file_type().is_symlink() {
+                        // Not followed
+                    } else if !meta.is_dir() {
+                        totals[idx].fetch_add(meta.len(), Ordering::Relaxed);
+                    } else if root_dev.is_none() || device_id(&meta) == root_dev {
+                        for entry in std::fs::read_dir(&path).into_iter().flatten().flatten() {
+                            match entry.file_type() {
+                                Ok(t) if t.is_symlink() => {}
+                                Ok(t) if t.is_dir() => subdirs.push(entry.path()),
+                                Ok(_) => {
+                                    let len = entry.metadata().map(|m| m.len()).unwrap_or(0);
+                                    totals[idx].fetch_add(len, Ordering::Relaxed);
+                                }
+                                Err(_) => {}
+                            }
+                        }
+                    }
+                }
+
+                let path_done = {
+                    let Ok(mut jobs) = queue.lock() else { return };
+                    pending.fetch_add(subdirs.len(), Ordering::SeqCst);
+                    remaining[idx].fetch_add(subdirs.len(), Ordering::SeqCst);
+                    jobs.extend(subdirs.into_iter().map(|p| (p, idx)));
+                    pending.fetch_sub(1, Ordering::SeqCst);
+                    wake.notify_all();
+                    remaining[idx].fetch_sub(1, Ordering::SeqCst) == 1
+                };
+                if path_done {
+                    on_finished(idx);
+                }
+            });
+        }
+    });
+
+    totals
+        .into_iter()
+        .zip(cut)
+        .map(|(total, cut)| (total.into_inner(), !cut.into_inner()))
+        .collect()
+}
+
+#[derive(Clone, serde::Serialize)]
+struct UsageProgress {
+    mount: String,
+    entries_done: u64,
+    entries_total: u64,
+}
+
+const USAGE_PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
+// Quick "du -s /mount/*": sizes of the immediate children, counted in parallel.
+// With a budget, entries still being counted when it runs out come back marked incomplete.
+// Emits usage:progress as entries finish.
+#[tauri::command]
+async fn get_top_level_usage(app: AppHandle, mount: String, budget_ms: Option<u64>) -> Result<Vec<DirEntry>, OrganizerError> {
+    let threads = scan_thread_count(&app);
+    tauri::async_runtime::spawn_blocking(move || {
+        let root = Path::new(&mount);
+        let root_meta = std::fs::metadata(root)?;
+        if !root_meta.is_dir() {
+            return Err(OrganizerError::InvalidInput(format!("{} is not a directory", mount)));
+        }
+        let root_dev = device_id(&root_meta);
+        let deadline = budget_ms.map(|ms| Instant::now() + Duration::from_millis(ms));
+
+        let children: Vec<(std::path::PathBuf, String, bool)> = std::fs::read_dir(root)?
+            .flatten()
+            .map(|entry| {
+                let is_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
+                (entry.path(), entry.file_name().to_string_lossy().to_string(), is_dir)
+            })
+            .collect();
+
+        let entries_total = children.len() as u64;
+        let progress = |entries_done| UsageProgress { mount: mount.clone(), entries_done, entries_total };
+        let _ = app.emit("usage:progress", progress(0));
+        let done = AtomicU64::new(0);
+        let last_emit = Mutex::new(Instant::now());
+        let paths: Vec<std::path::PathBuf> = children.iter().map(|(p, _, _)| p.clone()).collect();
+        let sizes = parallel_path_sizes(&paths, root_dev, threads, deadline, |_| {
+            let entries_done = done.fetch_add(1, Ordering::Relaxed) + 1;
+            let Ok(mut last) = last_emit.lock() else { return };
+            if last.elapsed() >= USAGE_PROGRESS_INTERVAL {
+                *last = Instant::now();
+                let _ = app.emit("usage:progress", progress(entries_done));
+            }
+        });
+        let _ = app.emit("usage:progress", progress(entries_total));
+
+        let mut entries: Vec<DirEntry> = children
+            .into_iter()
+            .zip(sizes)
+            .map(|((path, name, is_dir), (size_bytes, finished))| DirEntry {
+                path: path.to_string_lossy().to_string(),
+                name,
+                size_bytes,
+                is_dir,
+                incomplete: !finished,
+            })
+            .collect();
+
+        entries.sort_by(|a, b| b.size_bytes.cmp(&a.size_bytes).then_with(|| a.name.cmp(&b.name)));
+        Ok(entries)
+    })
+    .await?
+}
+
+// Roots for find_space_hogs when none are given
+fn default_space_hog_roots(app: &AppHandle) -> Vec<std::path::PathBuf> {
+    let mut roots: Vec<std::path::PathBuf> = app.path().home_dir().into_iter().collect();
+    #[cfg(target_os = "macos")]
+    roots.extend(["/Applications", "/Library/Caches"].map(std::path::PathBuf::from));
+    #[cfg(target_os = "linux")]
+    roots.extend(["/opt", "/var/cache"].map(std::path::PathBuf::from));
+    #[cfg(target_os = "windows")]
+    roots.extend(["ProgramFiles", "ProgramData"].iter().filter_map(|var| std::env::var_os(var).map(std::path::PathBuf::from)));
+    roots
+}
+
+// Post-order walk calling offer(dir, recursive size, incomplete) for every directory.
+// Same guards as path_size_until: no symlinks, no other mounts, stops at the deadline.
+fn walk_dir_sizes(
+    path: &Path,
+    root_dev: Option<u64>,
+    deadline: Instant,
+    offer: &impl Fn(&Path, u64, bool),
+) -> (u64, bool) {
+    let Ok(meta) = std::fs::symlink_metadata(path) else { return (0, true) };
+    if meta.file_type().is_symlink() {
+        return (0, true);
+    }
+    if !meta.is_dir() {
+        return (meta.len(), true);
+    }
+    if root_dev.is_some() && device_id(&meta) != root_dev {
+        return (0, true);
+    }
+
+    let mut total = 0;
+    let mut finished = true;
+    for entry in std::fs::read_dir(path).into_iter().flatten().flatten() {
+        if Instant::now() >= deadline {
+            finished = false;
+            break;
+        }
+        let (size, done) = match entry.file_type() {
+            Ok(t) if t.is_dir() => walk_dir_sizes(&entry.path(), root_dev, deadline, offer),
+            Ok(t) if t.is_symlink() => (0, true),
+            Ok(_) => (entry.metadata().map(|m| m.len()).unwrap_or(0), true),
+            Err(_) => (0, true),
+        };
+        total += size;
+        if !done {
+            finished = false;
+            break;
+        }
+    }
+    offer(path, total, !finished);
+    (total, finished)
+}
+
+// "Top N space hogs anywhere": biggest directories (any depth, roots themselves excluded)
+// under the given roots, or home + apps + caches by default. Top-level subtrees are
+// scanned in parallel into one shared top N; when the budget runs out the partial
+// results come back, with sizes still being counted flagged incomplete.
+#[tauri::command]
+async fn find_space_hogs(
+    app: AppHandle,
+    roots: Vec<String>,
+    top_n: usize,
+    time_budget_secs: u64,
+) -> Result<Vec<DirEntry>, OrganizerError> {
+    let threads = scan_thread_count(&app);
+    let mut roots: Vec<std::path::PathBuf> = if roots.is_empty() {
+        default_space_hog_roots(&app)
+    } else {
+        roots.into_iter().map(std::path::PathBuf::from).collect()
+    };
+    tauri::async_runtime::spawn_blocking(move || {
+        let deadline = Instant::now() + Duration::from_secs(time_budget_secs);
+
+        // A root inside another root would be counted twice
+        roots.retain(|root| root.is_dir());
+        roots.sort();
+        roots.dedup();
+        let nested: Vec<std::path::PathBuf> = roots
+            .iter()
+            .filter(|root| roots.iter().any(|other| other != *root && root.starts_with(other)))
+            .cloned()
+            .collect();
+        roots.retain(|root| !nested.contains(root));
+
+        // Work items: the directories right under each root, with that root's device
+        let subtrees: Vec<(std::path::PathBuf, Option<u64>)> = roots
+            .iter()
+            .flat_map(|root| {
+                let root_dev = std::fs::metadata(root).ok().and_then(|m| device_id(&m));
+                std::fs::read_dir(root)
+                    .into_iter()
+                    .flatten()
+                    .flatten()
+                    .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+                    .map(move |entry| (entry.path(), root_dev))
+            })
+            .collect();
+
+        let top: Mutex<Vec<DirEntry>> = Mutex::new(Vec::new());
+        let offer = |path: &Path, size_bytes: u64, incomplete: bool| {
+            let Ok(mut top) = top.lock() else { return };
+            let entry = || DirEntry {
+                path: path.to_string_lossy().to_string(),
+                name: path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default(),
+                size_bytes,
+                is_dir: true,
+                incomplete,
+            };
+            if top.len() < top_n {
+                top.push(entry());
+            } else if let Some((idx, smallest)) = top.iter().enumerate().min_by_key(|(_, e)| e.size_bytes) {
+                if size_bytes > smallest.size_bytes {
+                    top[idx] = entry();
+                }
+            }
+        };
+        parallel_map(&subtrees, threads, |(path, root_dev)| {
+            walk_dir_sizes(path, *root_dev, deadline, &offer);
+        });
+
+        let mut top = top.into_inner().unwrap_or_else(|e| e.into_inner());
+        top.sort_by(|a, b| b.size_bytes.cmp(&a.size_bytes).then_with(|| a.path.cmp(&b.path)));
+        top
+    })
+    .await
+    .map_err(OrganizerError::from)
+}
+
+fn scan_directory(path: &str, threads: usize) -> Result<DirectoryScan, OrganizerError> {
+    let started = Instant::now();
+    let root = Path::new(path);
+    let root_meta = std::fs::metadata(root)?;
+    if !root_meta.is_dir() {
+        return Err(OrganizerError::InvalidInput(format!("{} is not a directory", path)));
+    }
+    let root_dev = device_id(&root_meta);
+
+    let children: Vec<(std::path::PathBuf, String, bool)> = std::fs::read_dir(root)?
+        .flatten()
+        .map(|entry| {
+            let is_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
+            (entry.path(), entry.file_name().to_string_lossy().to_string(), is_dir)
+        })
+        .collect();
+    let paths: Vec<std::path::PathBuf> = children.iter().map(|(p, _, _)| p.clone()).collect();
+    let sizes = parallel_path_sizes(&paths, root_dev, threads, None, |_| {});
+
+    let mut entries: Vec<DirEntry> = children
+        .into_iter()
+        .zip(sizes)
+        .map(|((entry_path, name, is_dir), (size_bytes, _))| DirEntry {
+            path: entry_path.to_string_lossy().to_string(),
+            name,
+            size_bytes,
+            is_dir,
+            incomplete: false,
+        })
+        .collect();
+
+    // Name as tie-breaker so equal sizes come back in a stable order
+    entries.sort_by(|a, b| b.size_bytes.cmp(&a.size_bytes).then_with(|| a.name.cmp(&b.name)));
+
+    Ok(DirectoryScan {
+        id: String::new(), // Assigned when the scan is cached
+        path: path.to_string(),
+        total_bytes: entries.iter().map(|e| e.size_bytes).sum(),
+        entries,
+        duration_ms: started.elapsed().as_millis() as u64,
+        scanned_at: unix_now(),
+        cached: false,
+    })
+}
+
+// Scan immediate children of a directory with their recursive sizes.
+// Results are cached for SCAN_CACHE_TTL_SECS unless force is set.
+#[tauri::command]
+async fn scan_directory_sizes(app: AppHandle, path: String, force: bool) -> Result<DirectoryScan, OrganizerError> {
+    let cache = app
+        .try_state::<Arc<ScanCache>>()
+        .ok_or_else(|| OrganizerError::NotFound("Scan cache".into()))?
+        .inner()
+        .clone();
+
+    if !force {
+        let mut scans = cache.scans.lock()?;
+        let latest = scans
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| s.path == path)
+            .max_by_key(|(_, s)| s.scanned_at)
+            .map(|(idx, s)| (idx, s.scanned_at));
+        if let Some((idx, scanned_at)) = latest {
+            if unix_now().saturating_sub(scanned_at) < SCAN_CACHE_TTL_SECS {
+                // Move to back (most recently used)
+                let hit = scans.remove(idx).ok_or_else(|| OrganizerError::NotFound("Cache entry".into()))?;
+                scans.push_back(hit.clone());
+                return Ok(DirectoryScan { cached: true, ..hit });
+            }
+        }
+    }
+
+    let threads = scan_thread_count(&app);
+    let mut scan = tauri::async_runtime::spawn_blocking(move || scan_directory(&path, threads)).await??;
+    scan.id = format!("scan-{}", cache.next_id.fetch_add(1, Ordering::SeqCst) + 1);
+
+    let mut scans = cache.scans.lock()?;
+    scans.push_back(scan.clone());
+    while scans.len() > SCAN_CACHE_MAX_ENTRIES {
+        scans.pop_front();
+    }
+
+    Ok(scan)
+}
+
+#[derive(serde::Serialize)]
+struct DuplicateGroup {
+    size_bytes: u64,    // Size of each copy
+    paths: Vec<String>,
+}
+
+#[derive(Clone, serde::Serialize)]
+struct DuplicateProgress {
+    phase: String, // "walking" | "hashing"
+    files_done: u64,
+    files_total: u64,
+}
+
+// Bytes hashed first to rule out same-size files cheaply before a full hash
+const DUPLICATE_PREFIX_BYTES: u64 = 64 * 1024;
+const DUPLICATE_PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
+// SHA-256 of the whole file, or of its first `limit` bytes
+fn file_sha256(path: &Path, limit: Option<u64>) -> std::io::Result<[u8; 32]> {
+    use sha2::{Digest, Sha256};
+    use std::io::Read;
+
+    let file = std::fs::File::open(path)?;
+    let mut reader: Box<dyn Read> = match limit {
+        Some(limit) => Box::new(file.take(limit)),
+        None => Box::new(file),
+    };
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0u8; 256 * 1024];
+    loop {
+        let n = reader.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Ok(hasher.finalize().into())
+}
+
+// Split each group by hash, keeping only buckets that still hold duplicates
+fn split_by_hash(
+    groups: Vec<(u64, Vec<std::path::PathBuf>)>,
+    limit: Option<u64>,
+    mut on_file: impl FnMut(),
+) -> Vec<(u64, Vec<std::path::PathBuf>)> {
+    let mut confirmed = Vec::new();
+    for (size, paths) in groups {
+        let mut by_hash: HashMap<[u8; 32], Vec<std::path::PathBuf>> = HashMap::new();
+        for path in paths {
+            // Unreadable files can't be confirmed, leave them out
+            if let Ok(hash) = file_sha256(&path, limit) {
+                by_hash.entry(hash).or_default().push(path);
+            }
+            on_file();
+        }
+        confirmed.extend(by_hash.into_values().filter(|p| p.len() > 1).map(|p| (size, p)));
+    }
+    confirmed
+}
+
+// Identical files of at least min_size_mb under path: grouped by size first, then hashed
+// (a 64 KB prefix, then the whole file) only when sizes collide. Symlinks and other mounts
+// are skipped, and hard links to one file count once (they share its storage, so removing
+// one frees nothing). Emits duplicates:progress; biggest waste first.
+#[tauri::command]
+async fn find_duplicate_files(app: AppHandle, path: String, min_size_mb: u64) -> Result<Vec<DuplicateGroup>, OrganizerError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let root = std::path::PathBuf::from(&path);
+        let root_meta = std::fs::metadata(&root)?;
+        if !root_meta.is_dir() {
+            return Err(OrganizerError::InvalidInput(format!("{} is not a directory", path)));
+        }
+        let root_dev = device_id(&root_meta);
+        let min_bytes = min_size_mb.saturating_mul(1_048_576).max(1);
+
+        let mut last_emit = Instant::now();
+        let mut emit = |phase: &str, files_done: u64, files_total: u64, force: bool| {
+            if force || last_emit.elapsed() >= DUPLICATE_PROGRESS_INTERVAL {
+                last_emit = Instant::now();
+                let _ = app.emit("duplicates:progress", DuplicateProgress {
+                    phase: phase.to_string(),
+                    files_done,
+                    files_total,
+                });
+            }
+        };
+
+        let mut by_size: HashMap<u64, Vec<std::path::PathBuf>> = HashMap::new();
+        let mut linked: HashSet<(u64, u64)> = HashSet::new();
+        let mut seen: u64 = 0;
+        let mut stack = vec![root];
+        while let Some(dir) = stack.pop() {
+            let Ok(entries) = std::fs::read_dir(&dir) else { continue };
+            for entry in entries.flatten() {
+                let Ok(meta) = std::fs::symlink_metadata(entry.path()) else { continue };
+                if meta.file_type().is_symlink() {
+                    continue;
+                }
+                if meta.is_dir() {
+                    if root_dev.is_none() || device_id(&meta) == root_dev {
+                        stack.push(entry.path());
+                    }
+                } else if meta.is_file() && meta.len() >= min_bytes {
+                    if file_id(&meta).is_some_and(|id| !linked.insert(id)) {
+                        continue;
+                    }
+                    by_size.entry(meta.len()).or_default().push(entry.path());
+                    seen += 1;
+                    emit("walking", seen, 0, false);
+                }
+            }
+        }
+
+        let candidates: Vec<(u64, Vec<std::path::PathBuf>)> =
+            by_size.into_iter().filter(|(_, paths)| paths.len() > 1).collect();
+        let mut files_total: u64 = candidates.iter().map(|(_, p)| p.len() as u64).sum();
+        let mut files_done = 0;
+        emit("hashing", 0, files_total, true);
+
+        let candidates = split_by_hash(candidates, Some(DUPLICATE_PREFIX_BYTES), || {
+            files_done += 1;
+            emit("hashing", files_done, files_total, false);
+        });
+        // Files no bigger than the prefix are already fully hashed
+        let (small, large): (Vec<_>, Vec<_>) =
+            candidates.into_iter().partition(|(size, _)| *size <= DUPLICATE_PREFIX_BYTES);
+        files_total += large.iter().map(|(_, p)| p.len() as u64).sum::<u64>();
+        let mut confirmed = split_by_hash(large, None, || {
+            files_done += 1;
+            emit("hashing", files_done, files_total, false);
+        });
+        confirmed.extend(small);
+        emit("hashing", files_total, files_total, true);
+
+        let mut groups: Vec<DuplicateGroup> = confirmed
+            .into_iter()
+            .map(|(size_bytes, paths)| {
+                let mut paths: Vec<String> = paths.iter().map(|p| p.to_string_lossy().to_string()).collect();
+                paths.sort();
+                DuplicateGroup { size_bytes, paths }
+            })
+            .collect();
+        groups.sort_by_key(|g| std::cmp::Reverse(g.size_bytes * (g.paths.len() as u64 - 1)));
+        Ok(groups)
+    })
+    .await?
+}
+
+#[derive(serde::Serialize)]
+struct DirDiff {
+    path: String,
+    name: String,
+    size_before_bytes: Option<u64>, // None: entry is new in the newer scan
+    size_now_bytes: Option<u64>,    // None: entry was removed since the older scan
+    size_delta_bytes: i64,          // Positive: entry grew
+}
+
+// Compare two cached scans entry by entry, biggest changes first
+#[tauri::command]
+fn diff_directory_scans(app: AppHandle, old_id: String, new_id: String) -> Result<Vec<DirDiff>, OrganizerError> {
+    let cache = app
+        .try_state::<Arc<ScanCache>>()
+        .ok_or_else(|| OrganizerError::NotFound("Scan cache".into()))?;
+    let scans = cache.scans.lock()?;
+    let find = |id: &str| {
+        scans
+            .iter()
+            .find(|s| s.id == id)
+            .ok_or_else(|| OrganizerError::NotFound(format!("Scan {}", id)))
+    };
+    let old = find(&old_id)?;
+    let new = find(&new_id)?;
+
+    let mut diffs: Vec<DirDiff> = old
+        .entries
+        .iter()
+        .map(|before| {
+            let now = new.entries.iter().find(|e| e.path == before.path);
+            DirDiff {
+                path: before.path.clone(),
+                name: before.name.clone(),
+                size_before_bytes: Some(before.size_bytes),
+                size_now_bytes: now.map(|e| e.size_bytes),
+                size_delta_bytes: now.map(|e| e.size_bytes as i64).unwrap_or(0) - before.size_bytes as i64,
+            }
+        })
+        .collect();
+
+    for now in new.entries.iter().filter(|e| !old.entries.iter().any(|b| b.path == e.path)) {
+        diffs.push(DirDiff {
+            path: now.path.clone(),
+            name: now.name.clone(),
+            size_before_bytes: None,
+            size_now_bytes: Some(now.size_bytes),
+            size_delta_bytes: now.size_bytes as i64,
+        });
+    }
+
+    diffs.sort_by_key(|d| std::cmp::Reverse(d.size_delta_bytes.unsigned_abs()));
+    Ok(diffs)
+}
+
+#[tauri::command]
+fn clear_scan_cache(app: AppHandle) -> Result<(), String> {
+    let cache = app
+        .try_state::<Arc<ScanCache>>()
+        .ok_or("Scan cache not found")?;
+    cache.scans.lock().map_err(|e| e.to_string())?.clear();
+    Ok(())
+}
+
+#[derive(Clone, serde::Serialize)]
+struct FileEntry {
+    path: String,
+    name: String,
+    size_bytes: u64,
+    modified: Option<u64>, // Unix timestamp
+}
+
+// Directories file walkers never enter (OS internals, pseudo filesystems)
+const SYSTEM_DIRS: &[&str] = &[
+    "/System", "/private", "/dev", "/proc", "/sys", "/run", "/Volumes",
+    "C:\\Windows", "C:\\$Recycle.Bin", "C:\\System Volume Information",
+];
+
+fn is_skipped_dir(path: &Path) -> bool {
+    let hidden = path
+        .file_name()
+        .map(|n| n.to_string_lossy().starts_with('.'))
+        .unwrap_or(false);
+    hidden || SYSTEM_DIRS.iter().any(|d| path == Path::new(d))
+}
+
+// Visit every regular file under root, skipping symlinks, other mounts, hidden and system dirs
+fn walk_files(root: &Path, mut visit: impl FnMut(&Path, &std::fs::Metadata)) {
+    let root_dev = std::fs::metadata(root).ok().and_then(|m| device_id(&m));
+    let mut stack = vec![root.to_path_buf()];
+
+    while let Some(dir) = stack.pop() {
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let meta = match std::fs::symlink_metadata(&path) {
+                Ok(m) => m,
+                Err(_) => continue,
+            };
+
+            if meta.file_type().is_symlink() {
+                continue;
+            }
+            if meta.is_dir() {
+                if !is_skipped_dir(&path) && (root_dev.is_none() || device_id(&meta) == root_dev) {
+                    stack.push(path);
+                }
+            } else if meta.is_file() {
+                visit(&path, &meta);
+            }
+        }
+    }
+}
+
+fn file_entry(path: &Path, meta: &std::fs::Metadata) -> FileEntry {
+    FileEntry {
+        path: path.to_string_lossy().to_string(),
+        name: path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        size_bytes: meta.len(),
+        modified: meta
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_secs()),
+    }
+}
+
+// Files that are both large and old: "what can I safely delete"
+#[tauri::command]
+async fn find_cleanup_candidates(
+    path: String,
+    min_size_mb: u64,
+    older_than_days: u64,
+) -> Result<Vec<FileEntry>, OrganizerError> {
+    let root = std::path::PathBuf::from(&path);
+    if !std::fs::metadata(&root)?.is_dir() {
+        return Err(OrganizerError::InvalidInput(format!("{} is not a directory", path)));
+    }
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let min_bytes = min_size_mb.saturating_mul(1_048_576);
+        let cutoff = unix_now().saturating_sub(older_than_days.saturating_mul(86_400));
+
+        let mut files = Vec::new();
+        walk_files(&root, |file_path, meta| {
+            if meta.len() < min_bytes {
+                return;
+            }
+            let entry = file_entry(file_path, meta);
+            // Files without a readable mtime are never considered old
+            if entry.modified.map(|m| m < cutoff).unwrap_or(false) {
+                files.push(entry);
+            }
+        });
+
+        files.sort_by_key(|f| std::cmp::Reverse(f.size_bytes));
+        files
+    })
+    .await
+    .map_err(OrganizerError::from)
+}
+
+// One-click diagnostic bundle for bug reports: markdown, copied to the clipboard.
+// Only hardware/OS facts and process names/sizes: no env vars, args or paths.
+#[tauri::command]
+async fn generate_system_report(app: AppHandle) -> Result<String, OrganizerError> {
+    use std::fmt::Write;
+    use sysinfo::System;
+    use tauri_plugin_clipboard_manager::ClipboardExt;
+
+    let shared = app.clone();
+    let report = tauri::async_runtime::spawn_blocking(move || {
+        let mut sys = System::new();
+        sys.refresh_cpu_all();
+
+        let cpu_brand = sys
+            .cpus()
+            .first()
+            .map(|c| c.brand().trim().to_string())
+            .unwrap_or_else(|| "Unknown".to_string());
+
+        let uptime = System::uptime();
+        let memory = with_shared_system(&shared, memory_info);
+        let disk = get_disk_space_detailed(None).ok();
+        let processes = with_shared_processes(&shared, DetailLevel::Light, |sys| top_processes(sys, 10, None, &[], DetailLevel::Light))
+            .map(|t| t.processes)
+            .unwrap_or_default();
+
+        let mut out = String::new();
+        let _ = writeln!(out, "# Organizer system report\n");
+        let _ = writeln!(out, "- App version: {}", env!("CARGO_PKG_VERSION"));
+        let _ = writeln!(out, "- OS: {}", System::long_os_version().unwrap_or_else(|| "Unknown".to_string()));
+        let _ = writeln!(out, "- Kernel: {}", System::kernel_version().unwrap_or_else(|| "Unknown".to_string()));
+        let _ = writeln!(out, "- Arch: {}", System::cpu_arch().unwrap_or_else(|| "Unknown".to_string()));
+        let _ = writeln!(
+            out,
+            "- CPU: {} ({} logical, {} physical cores)",
+            cpu_brand,
+            sys.cpus().len(),
+            sys.physical_core_count().map(|n| n.to_string()).unwrap_or_else(|| "?".to_string()),
+        );
+        let _ = writeln!(out, "- Uptime: {}d {}h {}m", uptime / 86_400, (uptime % 86_400) / 3_600, (uptime % 3_600) / 60);
+
+        // Sizes come in GB floats of the chosen base, the report shows them like the UI does
+        let binary = format::binary_base();
+        let gb = |gb: f64| format::format_bytes((gb * format::gb_divisor()) as u64, binary);
+        let _ = writeln!(out, "\n## Memory\n");
+        let _ = writeln!(out, "- Total: {}", gb(memory.total_gb));
+        let _ = writeln!(out, "- Used: {} (app {}, wired {}, compressed {})", gb(memory.used_gb), gb(memory.app_gb), gb(memory.wired_gb), gb(memory.compressed_gb));
+        let _ = writeln!(out, "- Available: {}", gb(memory.available_gb));
+        let _ = writeln!(out, "- Swap: {} / {}", gb(memory.swap_used_gb), gb(memory.swap_total_gb));
+
+        let _ = writeln!(out, "\n## Disk\n");
+        match disk {
+            Some(d) => {
+                let _ = writeln!(out, "- Total: {}", gb(d.total_gb));
+                let _ = writeln!(out, "- Used: {}", gb(d.used_gb));
+                let _ = writeln!(out, "- Available: {} (+{} purgeable)", gb(d.available_gb), gb(d.purgeable_gb));
+            }
+            None => {
+                let _ = writeln!(out, "- Unavailable");
+            }
+        }
+
+        let _ = writeln!(out, "\n## Top processes (memory)\n");
+        let _ = writeln!(out, "| PID | Name | Memory |");
+        let _ = writeln!(out, "|---|---|---|");
+        for p in &processes {
+            let memory = format::format_bytes((p.memory_mb * 1_048_576.0) as u64, binary);
+            let _ = writeln!(out, "| {} | {} | {} |", p.pid, p.name, memory);
+        }
+
+        out
+    })
+    .await?;
+
+    app.clipboard()
+        .write_text(report.clone())
+        .map_err(|e| OrganizerError::Io(e.to_string()))?;
+
+    Ok(report)
+}
+
+// Shareable PNG card of the current CPU/RAM/disk usage and top 3 processes
+#[tauri::command]
+async fn render_dashboard_png(app: AppHandle, path: String) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        use sysinfo::System;
+
+        // CPU usage needs two samples; a local System so the shared one isn't held across the wait
+        let mut sys = System::new();
+        sys.refresh_cpu_usage();
+        std::thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+        sys.refresh_cpu_usage();
+
+        let memory = with_shared_system(&app, memory_info);
+        let top = with_shared_processes(&app, DetailLevel::Light, |sys| top_processes(sys, 3, None, &[], DetailLevel::Light));
+        let disk = get_disk_space().map_err(|e| e.to_string())?;
+        let percent = |part: f64, total: f64| if total > 0.0 { part / total * 100.0 } else { 0.0 };
+
+        let data = render::DashboardData {
+            metrics: vec![
+                render::Metric { label: "CPU".to_string(), percent: sys.global_cpu_usage() as f64 },
+                render::Metric { label: "RAM".to_string(), percent: percent(memory.used_gb, memory.total_gb) },
+                render::Metric { label: "DISK".to_string(), percent: percent(disk.total_gb - disk.free_gb, disk.total_gb) },
+            ],
+            top_processes: top
+                .map(|t| t.processes)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|p| {
+                    let value = if p.memory_mb >= 1024.0 {
+                        format!("{:.1} GB", p.memory_mb / 1024.0)
+                    } else {
+                        format!("{:.0} MB", p.memory_mb)
+                    };
+                    (p.name, value)
+                })
+                .collect(),
+        };
+
+        render::render_dashboard(&data)
+            .save_with_format(&path, image::ImageFormat::Png)
+            .map_err(|e| format!("Failed to write {}: {}", path, e))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+fn set_tray_badge(app: AppHandle, has_badge: bool) -> Result<(), String> {
+    let icon_state = app
+        .try_state::<Arc<TrayIconState>>()
+        .ok_or("Icon state not found")?;
+
+    let tray = app
+        .tray_by_id("main")
+        .ok_or("Tray not found")?;
+
+    if has_badge {
+        // Create icon with badge
+        let icon_with_badge = create_badge_icon(
+            &icon_state.original_icon,
+            icon_state.width,
+            icon_state.height,
+        )
+        .map_err(|e| e.to_string())?;
+
+        let new_icon = Image::new_owned(
+            icon_with_badge,
+            icon_state.width,
+            icon_state.height,
+        );
+        tray.set_icon(Some(new_icon)).map_err(|e| e.to_string())?;
+    } else {
+        // Restore original icon
+        let original = Image::new_owned(
+            icon_state.original_icon.clone(),
+            icon_state.width,
+            icon_state.height,
+        );
+        tray.set_icon(Some(original)).map_err(|e| e.to_string())?;
+    }
+
+    Ok(())
+}
+
+// Badge showing how many alerts are pending (0 restores the plain icon)
+#[tauri::command]
+fn set_tray_badge_count(app: AppHandle, count: u32) -> Result<(), String> {
+    let icon_state = app
+        .try_state::<Arc<TrayIconState>>()
+        .ok_or("Icon state not found")?;
+
+    let tray = app
+        .tray_by_id("main")
+        .ok_or("Tray not found")?;
+
+    let pixels = if count == 0 {
+        icon_state.original_icon.clone()
+    } else {
+        create_count_badge_icon(
+            &icon_state.original_icon,
+            icon_state.width,
+            icon_state.height,
+            count,
+        )?
+    };
+
+    let new_icon = Image::new_owned(pixels, icon_state.width, icon_state.height);
+    tray.set_icon(Some(new_icon)).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn get_alert_count(app: AppHandle) -> Result<u32, String> {
+    let alerts = app
+        .try_state::<Arc<AlertState>>()
+        .ok_or("Alert state not found")?;
+    Ok(alerts.count.load(Ordering::SeqCst))
+}
+
+// Called by every monitor when it fires: bump the pending count and refresh the badge
+fn raise_alert(app: &AppHandle) {
+    if let Some(alerts) = app.try_state::<Arc<AlertState>>() {
+        let count = alerts.count.fetch_add(1, Ordering::SeqCst) + 1;
+        let _ = set_tray_badge_count(app.clone(), count);
+    }
+}
+
+// Unminimize if minimized, then show and focus the main window
+fn show_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+    // Clear badge when showing window
+    clear_tray_badge(app);
+    refresh_show_hide_item(app);
+}
+
+fn is_main_window_visible(app: &AppHandle) -> bool {
+    app.get_webview_window("main")
+        .map(|w| w.is_visible().unwrap_or(false) && !w.is_minimized().unwrap_or(false))
+        .unwrap_or(false)
+}
+
+// Keep the first tray item in sync with the window: "Hide" while visible, "Show" otherwise
+fn refresh_show_hide_item(app: &AppHandle) {
+    if let Some(state) = app.try_state::<Arc<TrayMenuState>>() {
+        let key = if is_main_window_visible(app) { "tray.hide" } else { "tray.show" };
+        let _ = state.show.set_text(tr(app, key, &[]));
+    }
+}
+
+struct LocaleState {
+    locale: Mutex<String>,
+}
+
+// English until the locale state is managed
+fn current_locale(app: &AppHandle) -> String {
+    app.try_state::<Arc<LocaleState>>()
+        .and_then(|state| state.locale.lock().ok().map(|l| l.clone()))
+        .unwrap_or_else(|| i18n::DEFAULT_LOCALE.to_string())
+}
+
+// Translated string in the current locale
+fn tr(app: &AppHandle, key: &str, args: &[(&str, &str)]) -> String {
+    i18n::translate(&current_locale(app), key, args)
+}
+
+// The canonical size formatter, with the current locale's decimal separator
+#[tauri::command]
+fn format_bytes(app: AppHandle, bytes: u64, binary: bool) -> String {
+    let separator = format::decimal_separator(&current_locale(&app));
+    format::format_bytes_with_separator(bytes, binary, separator)
+}
+
+#[tauri::command]
+fn get_units() -> &'static str {
+    format::unit_base()
+}
+
+// "binary" (GiB, 1024^3) or "decimal" (GB, 10^9, like Finder): the divisor of every *_gb field
+#[tauri::command]
+fn set_units(app: AppHandle, units: String) -> Result<(), String> {
+    let binary = format::parse_unit_base(&units).ok_or_else(|| format!("Unknown units: {}", units))?;
+    format::set_binary_base(binary);
+    save_setting(&app, SETTINGS_UNITS, serde_json::json!(units));
+    Ok(())
+}
+
+#[tauri::command]
+fn get_locale(app: AppHandle) -> Result<String, String> {
+    let state = app
+        .try_state::<Arc<LocaleState>>()
+        .ok_or("Locale state not found")?;
+    let locale = state.locale.lock().map_err(|e| e.to_string())?.clone();
+    Ok(locale)
+}
+
+#[tauri::command]
+fn get_supported_locales() -> Vec<&'static str> {
+    i18n::supported_locales()
+}
+
+// Accepts "fr", "fr-FR"...; tray labels switch immediately, notifications from the next one
+#[tauri::command]
+fn set_locale(app: AppHandle, locale: String) -> Result<(), String> {
+    let normalized = i18n::normalize_locale(&locale)
+        .ok_or_else(|| format!("Unsupported locale: {}", locale))?;
+    let state = app
+        .try_state::<Arc<LocaleState>>()
+        .ok_or("Locale state not found")?;
+    *state.locale.lock().map_err(|e| e.to_string())? = normalized.to_string();
+    save_setting(&app, SETTINGS_LOCALE, serde_json::json!(normalized));
+    relabel_tray_menu(&app);
+    Ok(())
+}
+
+fn system_locale() -> &'static str {
+    sys_locale::get_locale()
+        .as_deref()
+        .and_then(i18n::normalize_locale)
+        .unwrap_or(i18n::DEFAULT_LOCALE)
+}
+
+fn relabel_tray_menu(app: &AppHandle) {
+    if let Some(tray) = app.try_state::<Arc<TrayMenuState>>() {
+        let _ = tray.autostart.set_text(tr(app, "tray.autostart", &[]));
+        let _ = tray.minimize_to_tray.set_text(tr(app, "tray.minimize_to_tray", &[]));
+        let _ = tray.always_on_top.set_text(tr(app, "tray.always_on_top", &[]));
+        let _ = tray.pause_monitoring.set_text(tr(app, "tray.pause_monitoring", &[]));
+        let _ = tray.quit.set_text(tr(app, "tray.quit", &[]));
+    }
+    refresh_show_hide_item(app);
+}
+
+// Tray menu layout, in order. The action items are created once in setup and reused by
+// every rebuild (toggles and relabeling keep working); separators and metrics are per menu.
+#[derive(Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum MenuEntry {
+    Show,
+    Autostart,
+    Minimize,
+    AlwaysOnTop,
+    PauseMonitoring,
+    Separator,
+    Cpu,    // Disabled item showing total CPU, refreshed with the metric trays
+    Memory, // Same for memory used
+    Quit,
+}
+
+const DEFAULT_TRAY_MENU: &[MenuEntry] = &[
+    MenuEntry::Show,
+    MenuEntry::Separator,
+    MenuEntry::Autostart,
+    MenuEntry::Minimize,
+    MenuEntry::AlwaysOnTop,
+    MenuEntry::PauseMonitoring,
+    MenuEntry::Separator,
+    MenuEntry::Quit,
+];
+
+// Quit must stay reachable, and an item can only sit once in a menu
+fn validate_tray_menu(entries: &[MenuEntry]) -> Result<(), String> {
+    if !entries.contains(&MenuEntry::Quit) {
+        return Err("The tray menu must contain quit".to_string());
+    }
+    for (i, entry) in entries.iter().enumerate() {
+        if *entry != MenuEntry::Separator && entries[..i].contains(entry) {
+            return Err("Tray menu entries can only appear once (except separators)".to_string());
+        }
+    }
+    Ok(())
+}
+
+// Saved layout, or the default when missing or invalid
+fn tray_menu_entries(app: &AppHandle) -> Vec<MenuEntry> {
+    app.store("settings.json")
+        .ok()
+        .and_then(|store| store.get(SETTINGS_TRAY_MENU))
+        .and_then(|v| serde_json::from_value::<Vec<MenuEntry>>(v).ok())
+        .filter(|entries| validate_tray_menu(entries).is_ok())
+        .unwrap_or_else(|| DEFAULT_TRAY_MENU.to_vec())
+}
+
+fn build_tray_menu(app: &AppHandle, state: &TrayMenuState, entries: &[MenuEntry]) -> tauri::Result<Menu<Wry>> {
+    let menu = Menu::new(app)?;
+    let mut metrics = Vec::new();
+    for entry in entries {
+        match entry {
+            MenuEntry::Show => menu.append(&state.show)?,
+            MenuEntry::Autostart => menu.append(&state.autostart)?,
+            MenuEntry::Minimize => menu.append(&state.minimize_to_tray)?,
+            MenuEntry::AlwaysOnTop => menu.append(&state.always_on_top)?,
+            MenuEntry::PauseMonitoring => menu.append(&state.pause_monitoring)?,
+            MenuEntry::Quit => menu.append(&state.quit)?,
+            MenuEntry::Separator => menu.append(&tauri::menu::PredefinedMenuItem::separator(app)?)?,
+            MenuEntry::Cpu | MenuEntry::Memory => {
+                let item = MenuItem::new(app, tray_metric_label(app, *entry, None), false, None::<&str>)?;
+                menu.append(&item)?;
+                metrics.push((*entry, item));
+            }
+        }
+    }
+    if let Ok(mut current) = state.metrics.lock() {
+        *current = metrics;
+    }
+    Ok(menu)
+}
+
+fn tray_metric_label(app: &AppHandle, entry: MenuEntry, pct: Option<u32>) -> String {
+    let pct = pct.map(|p| p.to_string()).unwrap_or_else(|| "…".to_string());
+    let key = if entry == MenuEntry::Cpu { "tray.cpu" } else { "tray.memory" };
+    tr(app, key, &[("pct", &pct)])
+}
+
+#[tauri::command]
+fn get_tray_menu(app: AppHandle) -> Vec<MenuEntry> {
+    tray_menu_entries(&app)
+}
+
+// Saved only: rebuild_tray_menu applies it
+#[tauri::command]
+fn set_tray_menu(app: AppHandle, entries: Vec<MenuEntry>) -> Result<(), String> {
+    validate_tray_menu(&entries)?;
+    save_setting(&app, SETTINGS_TRAY_MENU, serde_json::to_value(&entries).map_err(|e| e.to_string())?);
+    Ok(())
+}
+
+#[tauri::command]
+fn rebuild_tray_menu(app: AppHandle) -> Result<(), String> {
+    let state = app
+        .try_state::<Arc<TrayMenuState>>()
+        .ok_or("Tray menu state not found")?;
+    let menu = build_tray_menu(&app, &state, &tray_menu_entries(&app)).map_err(|e| e.to_string())?;
+    let tray = app.tray_by_id("main").ok_or("Tray not found")?;
+    tray.set_menu(Some(menu)).map_err(|e| e.to_string())?;
+    refresh_show_hide_item(&app);
+    Ok(())
+}
+
+fn clear_tray_badge(app: &AppHandle) {
+    if let Some(alerts) = app.try_state::<Arc<AlertState>>() {
+        alerts.count.store(0, Ordering::SeqCst);
+    }
+    if let Some(icon_state) = app.try_state::<Arc<TrayIconState>>() {
+        if let Some(tray) = app.tray_by_id("main") {
+            let original = Image::new_owned(
+                icon_state.original_icon.clone(),
+                icon_state.width,
+                icon_state.height,
+            );
+            let _ = tray.set_icon(Some(original));
+        }
+    }
+}
+
+fn create_badge_icon(original: &[u8], width: u32, height: u32) -> Result<Vec<u8>, String> {
+    // Clone the original RGBA data
+    let mut pixels = original.to_vec();
+
+    // Calculate badge position (top-right corner)
+    let badge_center_x = width - BADGE_RADIUS - 2;
+    let badge_center_y = BADGE_RADIUS + 2;
+
+    // Draw filled circle for badge
+    for y in 0..height {
+        for x in 0..width {
+            let dx = x as i32 - badge_center_x as i32;
+            let dy = y as i32 - badge_center_y as i32;
+            let distance_sq = dx * dx + dy * dy;
+
+            if distance_sq <= (BADGE_RADIUS * BADGE_RADIUS) as i32 {
+                let idx = ((y * width + x) * 4) as usize;
+                if idx + 3 < pixels.len() {
+                    pixels[idx] = BADGE_COLOR[0];     // R
+                    pixels[idx + 1] = BADGE_COLOR[1]; // G
+                    pixels[idx + 2] = BADGE_COLOR[2]; // B
+                    pixels[idx + 3] = BADGE_COLOR[3]; // A
+                }
+            }
+        }
+    }
+
+    Ok(pixels)
+}
+
+// Badge circle with the count drawn in white on top (capped at 99 to stay legible)
+fn create_count_badge_icon(original: &[u8], width: u32, height: u32, count: u32) -> Result<Vec<u8>, String> {
+    let mut pixels = create_badge_icon(original, width, height)?;
+
+    // Render the digits into a square inside the circle, then copy the lit pixels over
+    let side = BADGE_RADIUS * 2 - 2;
+    let mut digits = vec![0u8; (side * side * 4) as usize];
+    draw_number(&mut digits, side, side, count.min(99), [255, 255, 255, 255]);
+
+    let left = (width - BADGE_RADIUS - 2).saturating_sub(side / 2);
+    let top = (BADGE_RADIUS + 2).saturating_sub(side / 2);
+    for y in 0..side {
+        for x in 0..side {
+            let src = ((y * side + x) * 4) as usize;
+            if digits[src + 3] == 0 {
+                continue;
+            }
+            let dst = (((top + y) * width + left + x) * 4) as usize;
+            if dst + 3 < pixels.len() {
+                pixels[dst..dst + 4].copy_from_slice(&digits[src..src + 4]);
+            }
+        }
+    }
+
+    Ok(pixels)
+}
+
+// Draw a number centered in an RGBA buffer, scaled up to fit the width
+fn draw_number(pixels: &mut [u8], width: u32, height: u32, value: u32, color: [u8; 4]) {
+    let digits: Vec<char> = value.to_string().chars().collect();
+    let count = digits.len() as u32;
+
+    // Each glyph is 3 columns plus 1 column of spacing (none after the last one)
+    let text_cols = count * 4 - 1;
+    let scale = (width / text_cols).min(height / 5).max(1);
+    let origin_x = width.saturating_sub(text_cols * scale) / 2;
+    let origin_y = height.saturating_sub(5 * scale) / 2;
+
+    for (i, digit) in digits.iter().enumerate() {
+        for (row, bits) in render::glyph(*digit).iter().enumerate() {
+            for col in 0..3u32 {
+                if bits & (0b100 >> col) == 0 {
+                    continue;
+                }
+                let x0 = origin_x + (i as u32 * 4 + col) * scale;
+                let y0 = origin_y + row as u32 * scale;
+                for y in y0..(y0 + scale).min(height) {
+                    for x in x0..(x0 + scale).min(width) {
+                        let idx = ((y * width + x) * 4) as usize;
+                        if idx + 3 < pixels.len() {
+                            pixels[idx..idx + 4].copy_from_slice(&color);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+// Extra per-metric tray icons next to the main one, showing a live percentage
+const METRIC_TRAY_IDS: &[&str] = &["cpu", "mem"];
+const METRIC_TRAY_SIZE: u32 = 32;
+const METRIC_TRAY_INTERVAL_SECS: u64 = 2;
+const METRIC_TRAY_COLORS: [(&str, [u8; 4]); 2] = [
+    ("cpu", [52, 199, 89, 255]),  // Green
+    ("mem", [10, 132, 255, 255]), // Blue
+];
+
+#[derive(Clone, Default, serde::Serialize, serde::Deserialize)]
+struct MetricTraysConfig {
+    cpu: bool,
+    mem: bool,
+}
+
+// Base (blank) icon per metric tray id, drawn over on every update
+#[derive(Default)]
+struct MetricTrayState {
+    icons: Mutex<HashMap<String, TrayIconState>>,
+}
+
+fn create_metric_tray(app: &AppHandle, state: &MetricTrayState, id: &str) -> Result<(), String> {
+    let mut icons = state.icons.lock().map_err(|e| e.to_string())?;
+    if icons.contains_key(id) {
+        return Ok(());
+    }
+
+    let base = TrayIconState {
+        original_icon: vec![0; (METRIC_TRAY_SIZE * METRIC_TRAY_SIZE * 4) as usize],
+        width: METRIC_TRAY_SIZE,
+        height: METRIC_TRAY_SIZE,
+    };
+
+    TrayIconBuilder::with_id(id)
+        .icon(Image::new_owned(base.original_icon.clone(), base.width, base.height))
+        .tooltip(id.to_uppercase())
+        .on_tray_icon_event(|tray, event| {
+            if let TrayIconEvent::Click {
+                button: MouseButton::Left,
+                button_state: MouseButtonState::Up,
+                ..
+            } = event
+            {
+                show_main_window(tray.app_handle());
+            }
+        })
+        .build(app)
+        .map_err(|e| e.to_string())?;
+
+    icons.insert(id.to_string(), base);
+    Ok(())
+}
+
+#[tauri::command]
+fn set_metric_tray(app: AppHandle, metric: String, enabled: bool) -> Result<(), String> {
+    if !METRIC_TRAY_IDS.contains(&metric.as_str()) {
+        return Err(format!("Unknown metric tray: {}", metric));
+    }
+    let state = app
+        .try_state::<Arc<MetricTrayState>>()
+        .ok_or("Metric tray state not found")?;
+
+    if enabled {
+        create_metric_tray(&app, &state, &metric)?;
+    } else {
+        state.icons.lock().map_err(|e| e.to_string())?.remove(&metric);
+        let _ = app.remove_tray_by_id(metric.as_str());
+    }
+
+    let config = {
+        let icons = state.icons.lock().map_err(|e| e.to_string())?;
+        MetricTraysConfig {
+            cpu: icons.contains_key("cpu"),
+            mem: icons.contains_key("mem"),
+        }
+    };
+    save_setting(&app, SETTINGS_METRIC_TRAYS, serde_json::to_value(config).map_err(|e| e.to_string())?);
+    Ok(())
+}
+
+// Live CPU/memory for the per-metric tray icons and the tray menu's metric items:
+// one sample per tick feeds both
+fn spawn_metric_tray_updater(app: AppHandle) {
+    let shutdown = shutdown_signal(&app);
+    std::thread::spawn(move || {
+        use sysinfo::System;
+
+        let mut sys = System::new();
+
+        loop {
+            if !shutdown.sampler_sleep(Duration::from_secs(METRIC_TRAY_INTERVAL_SECS)) {
+                return;
+            }
+
+            let state = match app.try_state::<Arc<MetricTrayState>>() {
+                Some(state) => state,
+                None => continue,
+            };
+            // Menu items are cloned out of the lock: set_text hops to the main thread, which may
+            // be rebuilding the menu and waiting on it
+            let menu_items = match app.try_state::<Arc<TrayMenuState>>() {
+                Some(menu) => menu.metrics.lock().map(|items| items.clone()).unwrap_or_default(),
+                None => Vec::new(),
+            };
+
+            // Render under the lock, but update the trays after releasing it: tray calls hop to
+            // the main thread, which may itself be waiting on this lock in set_metric_tray
+            let (rendered, cpu_pct, mem_pct) = {
+                let icons = match state.icons.lock() {
+                    Ok(icons) => icons,
+                    Err(_) => continue,
+                };
+                if icons.is_empty() && menu_items.is_empty() {
+                    continue;
+                }
+
+                // CPU usage is a delta between refreshes, so refresh on every tick
+                sys.refresh_cpu_usage();
+                let memory = memory_info(&mut sys);
+                let cpu_pct = (sys.global_cpu_usage().round() as u32).min(100);
+                let mem_pct = if memory.total_gb > 0.0 {
+                    ((memory.used_gb / memory.total_gb * 100.0).round() as u32).min(100)
+                } else {
+                    0
+                };
+
+                let rendered: Vec<(String, Vec<u8>, u32, u32, u32)> = icons
+                    .iter()
+                    .filter_map(|(id, base)| {
+                        let value = match id.as_str() {
+                            "cpu" => cpu_pct,
+                            "mem" => mem_pct,
+                            _ => return None,
+                        };
+                        let color = METRIC_TRAY_COLORS
+                            .iter()
+                            .find(|(metric, _)| metric == id)
+                            .map(|(_, color)| *color)
+                            .unwrap_or(BADGE_COLOR);
+
+                        let mut pixels = base.original_icon.clone();
+                        draw_number(&mut pixels, base.width, base.height, value, color);
+                        Some((id.clone(), pixels, base.width, base.height, value))
+                    })
+                    .collect();
+                (rendered, cpu_pct, mem_pct)
+            };
+
+            for (id, pixels, width, height, value) in rendered {
+                if let Some(tray) = app.tray_by_id(&id) {
+                    let _ = tray.set_icon(Some(Image::new_owned(pixels, width, height)));
+                    let _ = tray.set_tooltip(Some(format!("{} {}%", id.to_uppercase(), value)));
+                }
+            }
+            for (entry, item) in menu_items {
+                let pct = if matches!(entry, MenuEntry::Cpu) { cpu_pct } else { mem_pct };
+                let _ = item.set_text(tray_metric_label(&app, entry, Some(pct)));
+            }
+        }
+    });
+}
+
+// Background samples kept for export_history: HISTORY_MAX_SAMPLES x HISTORY_INTERVAL_SECS = 1 hour
+const HISTORY_INTERVAL_SECS: u64 = 10;
+const HISTORY_MAX_SAMPLES: usize = 360;
+
+#[derive(Default)]
+struct MetricHistory {
+    cpu: Mutex<VecDeque<(u64, f32, Vec<f32>)>>, // (timestamp, global %, per-core %)
+    memory: Mutex<VecDeque<(u64, MemoryInfo)>>,
+    disk: Mutex<VecDeque<(u64, Vec<DiskInfo>)>>,
+}
+
+fn push_sample<T>(buffer: &Mutex<VecDeque<T>>, sample: T) {
+    if let Ok(mut buffer) = buffer.lock() {
+        buffer.push_back(sample);
+        while buffer.len() > HISTORY_MAX_SAMPLES {
+            buffer.pop_front();
+        }
+    }
+}
+
+fn spawn_history_sampler(app: AppHandle) {
+    let shutdown = shutdown_signal(&app);
+    std::thread::spawn(move || {
+        use sysinfo::System;
+
+        let mut sys = System::new();
+        // First refresh only primes the CPU delta
+        sys.refresh_cpu_usage();
+
+        loop {
+            if !shutdown.sampler_sleep(Duration::from_secs(HISTORY_INTERVAL_SECS)) {
+                return;
+            }
+            let Some(history) = app.try_state::<Arc<MetricHistory>>() else { continue };
+
+            sys.refresh_cpu_usage();
+            let now = unix_now();
+            let cores = sys.cpus().iter().map(|c| c.cpu_usage()).collect();
+            push_sample(&history.cpu, (now, sys.global_cpu_usage(), cores));
+            push_sample(&history.memory, (now, memory_info(&mut sys)));
+            push_sample(&history.disk, (now, get_all_disks()));
+        }
+    });
+}
+
+// Quote a CSV field when it contains a separator, quote or newline
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+// Write the sampled history to a CSV file (kind: "cpu", "memory" or "disk").
+// Done here rather than in the frontend so the samples never cross IPC.
+#[tauri::command]
+fn export_history(app: AppHandle, kind: String, path: String) -> Result<(), String> {
+    use std::fmt::Write;
+
+    let history = app
+        .try_state::<Arc<MetricHistory>>()
+        .ok_or("Metric history not found")?;
+
+    let mut csv = String::new();
+    match kind.as_str() {
+        "cpu" => {
+            let samples = history.cpu.lock().map_err(|e| e.to_string())?;
+            let cores = samples.iter().map(|(_, _, c)| c.len()).max().unwrap_or(0);
+            csv.push_str("timestamp,total_pct");
+            for core in 0..cores {
+                let _ = write!(csv, ",core{}_pct", core);
+            }
+            csv.push('\n');
+            for (at, total, per_core) in samples.iter() {
+                let _ = write!(csv, "{},{:.1}", iso8601_utc(*at), total);
+                for core in 0..cores {
+                    match per_core.get(core) {
+                        Some(pct) => { let _ = write!(csv, ",{:.1}", pct); }
+                        None => csv.push(','),
+                    }
+                }
+                csv.push('\n');
+            }
+        }
+        "memory" => {
+            let samples = history.memory.lock().map_err(|e| e.to_string())?;
+            csv.push_str("timestamp,total_gb,used_gb,available_gb,free_gb,app_gb,wired_gb,compressed_gb,cached_gb,swap_total_gb,swap_used_gb\n");
+            // Samples taken before a units toggle are converted so the column stays in one base
+            for (at, m) in samples.iter() {
+                let gb = |value: f64| format::gb_in_current_base(value, m.unit_base);
+                let _ = writeln!(
+                    csv,
+                    "{},{:.3},{:.3},{:.3},{:.3},{:.3},{:.3},{:.3},{:.3},{:.3},{:.3}",
+                    iso8601_utc(*at), gb(m.total_gb), gb(m.used_gb), gb(m.available_gb), gb(m.free_gb), gb(m.app_gb),
+                    gb(m.wired_gb), gb(m.compressed_gb), gb(m.cached_gb), gb(m.swap_total_gb), gb(m.swap_used_gb)
+                );
+            }
+        }
+        "disk" => {
+            // One row per volume per sample
+            let samples = history.disk.lock().map_err(|e| e.to_string())?;
+            csv.push_str("timestamp,mount_point,name,file_system,total_gb,used_gb,available_gb,used_pct\n");
+            for (at, disks) in samples.iter() {
+                for d in disks {
+                    let gb = |value: f64| format::gb_in_current_base(value, &d.unit_base);
+                    let _ = writeln!(
+                        csv,
+                        "{},{},{},{},{:.3},{:.3},{:.3},{:.1}",
+                        iso8601_utc(*at), csv_field(&d.mount_point), csv_field(&d.name), csv_field(&d.file_system),
+                        gb(d.total_gb), gb(d.used_gb), gb(d.available_gb), d.used_pct
+                    );
+                }
+            }
+        }
+        _ => return Err(format!("Unknown history kind: {} (expected cpu, memory or disk)", kind)),
+    }
+
+    std::fs::write(&path, csv).map_err(|e| format!("Failed to write {}: {}", path, e))
+}
+
+// Session CPU time per program, for "what's been busy today". sysinfo 0.32 has no cumulative
+// CPU time, so each tick adds usage x elapsed. Keyed by exe path (name when unreadable) so
+// short-lived processes that keep restarting add up under one entry.
+const CPU_ACCOUNTING_INTERVAL_SECS: u64 = 5;
+
+struct CpuTimeTotal {
+    name: String,
+    exe_path: Option<String>,
+    cpu_seconds: f64,
+    instances: HashSet<(u32, u64)>, // (pid, start time) seen under this key
+}
+
+struct CpuAccountingState {
+    since: Mutex<u64>,
+    totals: Mutex<HashMap<String, CpuTimeTotal>>,
+}
+
+#[derive(serde::Serialize)]
+struct CpuTimeLeader {
+    name: String,
+    exe_path: Option<String>,
+    cpu_seconds: f64, // One core busy for one second = 1.0
+    instances: usize,
+    since: u64,       // Unix timestamp accounting started (app start or last reset)
+}
+
+fn spawn_cpu_accounting(app: AppHandle) {
+    let shutdown = shutdown_signal(&app);
+    std::thread::spawn(move || {
+        use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind};
+
+        let kind = ProcessRefreshKind::new().with_cpu().with_exe(UpdateKind::OnlyIfNotSet);
+        let mut sys = System::new();
+        // First refresh only primes the CPU delta
+        sys.refresh_processes_specifics(ProcessesToUpdate::All, true, kind);
+        let mut last = Instant::now();
+
+        loop {
+            if !shutdown.sampler_sleep(Duration::from_secs(CPU_ACCOUNTING_INTERVAL_SECS)) {
+                return;
+            }
+            let Some(state) = app.try_state::<Arc<CpuAccountingState>>() else { continue };
+
+            sys.refresh_processes_specifics(ProcessesToUpdate::All, true, kind);
+            let elapsed = last.elapsed().as_secs_f64();
+            last = Instant::now();
+
+            let Ok(mut totals) = state.totals.lock() else { continue };
+            for (pid, process) in sys.processes() {
+                let usage = process.cpu_usage();
+                if usage <= 0.0 {
+                    continue;
+                }
+                let name = process.name().to_string_lossy().to_string();
+                let exe_path = process.exe().map(|p| p.to_string_lossy().to_string());
+                let key = exe_path.clone().unwrap_or_else(|| name.clone());
+                let total = totals.entry(key).or_insert_with(|| CpuTimeTotal {
+                    name,
+                    exe_path,
+                    cpu_seconds: 0.0,
+                    instances: HashSet::new(),
+                });
+                total.cpu_seconds += usage as f64 / 100.0 * elapsed;
+                total.instances.insert((pid.as_u32(), process.start_time()));
+            }
+        }
+    });
+}
+
+#[tauri::command]
+fn get_cpu_time_leaders(app: AppHandle, top_n: usize) -> Result<Vec<CpuTimeLeader>, String> {
+    let state = app
+        .try_state::<Arc<CpuAccountingState>>()
+        .ok_or("CPU accounting state not found")?;
+    let since = *state.since.lock().map_err(|e| e.to_string())?;
+    let totals = state.totals.lock().map_err(|e| e.to_string())?;
+    let mut leaders: Vec<CpuTimeLeader> = totals
+        .values()
+        .map(|t| CpuTimeLeader {
+            name: t.name.clone(),
+            exe_path: t.exe_path.clone(),
+            cpu_seconds: t.cpu_seconds,
+            instances: t.instances.len(),
+            since,
+        })
+        .collect();
+    leaders.sort_by(|a, b| b.cpu_seconds.partial_cmp(&a.cpu_seconds).unwrap_or(std::cmp::Ordering::Equal));
+    leaders.truncate(top_n);
+    Ok(leaders)
+}
+
+#[tauri::command]
+fn reset_cpu_accounting(app: AppHandle) -> Result<(), String> {
+    let state = app
+        .try_state::<Arc<CpuAccountingState>>()
+        .ok_or("CPU accounting state not found")?;
+    state.totals.lock().map_err(|e| e.to_string())?.clear();
+    *state.since.lock().map_err(|e| e.to_string())? = unix_now();
+    Ok(())
+}
+
+// Service restart tracking: per executable, the start time of its oldest live instance.
+// When that instance disappears and a newer one shows up, the program was restarted
+// (entries are kept while it's down, so a later relaunch still counts).
+#[derive(Default)]
+struct ServiceRestartState {
+    last_seen: Mutex<HashMap<String, ServiceInstance>>, // Keyed by exe path
+    pending: Mutex<Vec<RestartEvent>>,                 // Drained by get_service_restarts
+}
+
+struct ServiceInstance {
+    pid: u32,
+    start_time: u64,
+    seen_at: u64, // Last tick it was alive, to tell services from short-lived tools
+}
+
+#[derive(Clone, serde::Serialize)]
+struct RestartEvent {
+    exe_path: String,
+    name: String,
+    old_pid: u32,
+    pid: u32,
+    old_start_time: u64, // Unix timestamps
+    new_start_time: u64,
+    detected_at: u64,
+}
+
+const SERVICE_RESTART_INTERVAL_SECS: u64 = 10;
+// Short-lived tools (git, ls...) come and go constantly; only long runners count as services
+const SERVICE_MIN_UPTIME_SECS: u64 = 60;
+const SERVICE_RESTART_MAX_PENDING: usize = 200;
+
+fn spawn_service_restart_tracker(app: AppHandle) {
+    let shutdown = shutdown_signal(&app);
+    std::thread::spawn(move || {
+        use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind};
+
+        let kind = ProcessRefreshKind::new().with_exe(UpdateKind::OnlyIfNotSet);
+        let mut sys = System::new();
+
+        loop {
+            if let Some(state) = app.try_state::<Arc<ServiceRestartState>>() {
+                sys.refresh_processes_specifics(ProcessesToUpdate::All, true, kind);
+
+                // Oldest instance per executable: it only changes once the previous oldest is gone,
+                // so helpers spawning and exiting alongside it don't count
+                let mut current: HashMap<String, (u32, u64, String)> = HashMap::new();
+                for (pid, process) in sys.processes() {
+                    let Some(exe) = process.exe() else { continue };
+                    let exe = exe.to_string_lossy().to_string();
+                    let start_time = process.start_time();
+                    let entry = current
+                        .entry(exe)
+                        .or_insert_with(|| (pid.as_u32(), start_time, process.name().to_string_lossy().to_string()));
+                    if start_time < entry.1 {
+                        *entry = (pid.as_u32(), start_time, process.name().to_string_lossy().to_string());
+                    }
+                }
+
+                let now = unix_now();
+                match (state.last_seen.lock(), state.pending.lock()) {
+                    (Ok(mut last_seen), Ok(mut pending)) => {
+                        for (exe, (pid, start_time, name)) in current {
+                            // Same identity rule as the exit watchers: a new start time is a new process
+                            if let Some(previous) = last_seen.get(&exe) {
+                                if start_time > previous.start_time
+                                    && previous.seen_at.saturating_sub(previous.start_time) >= SERVICE_MIN_UPTIME_SECS
+                                {
+                                    pending.push(RestartEvent {
+                                        exe_path: exe.clone(),
+                                        name,
+                                        old_pid: previous.pid,
+                                        pid,
+                                        old_start_time: previous.start_time,
+                                        new_start_time: start_time,
+                                        detected_at: now,
+                                    });
+                                }
+                            }
+                            last_seen.insert(exe, ServiceInstance { pid, start_time, seen_at: now });
+                        }
+                        if pending.len() > SERVICE_RESTART_MAX_PENDING {
+                            let excess = pending.len() - SERVICE_RESTART_MAX_PENDING;
+                            pending.drain(..excess);
+                        }
+                    }
+                    _ => log_monitor_error("service restart tracker", "state lock poisoned"),
+                }
+            }
+
+            if !shutdown.sampler_sleep(Duration::from_secs(SERVICE_RESTART_INTERVAL_SECS)) {
+                return;
+            }
+        }
+    });
+}
+
+// Restarts detected since the previous call, oldest first
+#[tauri::command]
+fn get_service_restarts(app: AppHandle) -> Result<Vec<RestartEvent>, String> {
+    let state = app
+        .try_state::<Arc<ServiceRestartState>>()
+        .ok_or("Service restart state not found")?;
+    let mut pending = state.pending.lock().map_err(|e| e.to_string())?;
+    Ok(std::mem::take(&mut *pending))
+}
+
+// Payload for the settings:changed event
+#[derive(Clone, serde::Serialize)]
+struct SettingsChanged {
+    key: String,
+    value: serde_json::Value,
+}
+
+// Single entry point for writing settings.json: persists the value and
+// broadcasts settings:changed so every window (and the tray) can resync
+fn save_setting(app: &AppHandle, key: &str, value: serde_json::Value) {
+    if let Ok(store) = app.store("settings.json") {
+        store.set(key.to_string(), value.clone());
+        let _ = store.save();
+    }
+
+    let _ = app.emit("settings:changed", SettingsChanged {
+        key: key.to_string(),
+        value,
+    });
+}
+
+// Update minimize-to-tray everywhere: atomic, tray checkbox, store, and open windows
+fn apply_minimize_to_tray(app: &AppHandle, state: &TrayMenuState, enabled: bool) {
+    state.minimize_enabled.store(enabled, Ordering::SeqCst);
+
+    // Update checkbox visual
+    let _ = state.minimize_to_tray.set_checked(enabled);
+
+    save_setting(app, SETTINGS_MINIMIZE_TO_TRAY, serde_json::json!(enabled));
+}
+
+#[tauri::command]
+fn get_minimize_to_tray(app: AppHandle) -> bool {
+    app.try_state::<Arc<TrayMenuState>>()
+        .map(|state| state.minimize_enabled.load(Ordering::SeqCst))
+        .unwrap_or(false)
+}
+
+#[tauri::command]
+fn set_minimize_to_tray(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let state = app
+        .try_state::<Arc<TrayMenuState>>()
+        .ok_or("Tray menu state not found")?;
+    apply_minimize_to_tray(&app, &state, enabled);
+    Ok(())
+}
+
+// Update always-on-top everywhere: main window, atomic, tray checkbox, store
+fn apply_always_on_top(app: &AppHandle, state: &TrayMenuState, on: bool) -> Result<(), String> {
+    let window = app
+        .get_webview_window("main")
+        .ok_or("Main window not found")?;
+    window.set_always_on_top(on).map_err(|e| e.to_string())?;
+
+    state.always_on_top_enabled.store(on, Ordering::SeqCst);
+    let _ = state.always_on_top.set_checked(on);
+
+    save_setting(app, SETTINGS_ALWAYS_ON_TOP, serde_json::json!(on));
+    Ok(())
+}
+
+#[tauri::command]
+fn set_always_on_top(app: AppHandle, on: bool) -> Result<(), String> {
+    let state = app
+        .try_state::<Arc<TrayMenuState>>()
+        .ok_or("Tray menu state not found")?;
+    apply_always_on_top(&app, &state, on)
+}
+
+// Update the monitoring pause everywhere: samplers, tray checkbox, store
+fn apply_monitoring_paused(app: &AppHandle, paused: bool) {
+    shutdown_signal(app).set_paused(paused);
+    if let Some(state) = app.try_state::<Arc<TrayMenuState>>() {
+        let _ = state.pause_monitoring.set_checked(paused);
+    }
+    save_setting(app, SETTINGS_MONITORING_PAUSED, serde_json::json!(paused));
+}
+
+#[tauri::command]
+fn get_monitoring_paused(app: AppHandle) -> bool {
+    shutdown_signal(&app).is_paused()
+}
+
+// Stops the background samplers (history, alerts, tray metrics, clipboard, update check...) to save battery.
+// Commands still sample on demand.
+#[tauri::command]
+fn set_monitoring_paused(app: AppHandle, paused: bool) {
+    apply_monitoring_paused(&app, paused);
+}
+
+fn is_backend_setting(key: &str) -> bool {
+    key.starts_with(BACKEND_SETTINGS_PREFIX)
+}
+
+// Copy of every entry next to the app data, overwritten on each compact/reset
+fn backup_settings(app: &AppHandle) -> Result<std::path::PathBuf, String> {
+    let store = app.store("settings.json").map_err(|e| e.to_string())?;
+    let entries: serde_json::Map<String, serde_json::Value> = store.entries().into_iter().collect();
+    let dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let path = dir.join(SETTINGS_BACKUP_FILE);
+    let json = serde_json::to_string_pretty(&entries).map_err(|e| e.to_string())?;
+    std::fs::write(&path, json).map_err(|e| e.to_string())?;
+    Ok(path)
+}
+
+// Drop backend keys left behind by removed features, after a backup
+#[tauri::command]
+fn compact_settings(app: AppHandle) -> Result<(), String> {
+    backup_settings(&app)?;
+    let store = app.store("settings.json").map_err(|e| e.to_string())?;
+    for key in store.keys() {
+        if is_backend_setting(&key) && !KNOWN_SETTINGS_KEYS.contains(&key.as_str()) {
+            store.delete(&key);
+        }
+    }
+    store.save().map_err(|e| e.to_string())
+}
+
+// Back every backend setting to its default (frontend keys are kept), after a backup.
+// Defaults are applied right away: autostart off, minimize/always-on-top off, monitoring
+// resumed, no metric trays, alerts and guards back to their default config, system locale.
+#[tauri::command]
+fn reset_settings(app: AppHandle) -> Result<(), String> {
+    use tauri_plugin_autostart::ManagerExt;
+
+    backup_settings(&app)?;
+    let store = app.store("settings.json").map_err(|e| e.to_string())?;
+    for key in store.keys().into_iter().filter(|k| is_backend_setting(k)) {
+        store.delete(&key);
+    }
+    store.save().map_err(|e| e.to_string())?;
+
+    let _ = app.autolaunch().disable();
+    if let Some(tray) = app.try_state::<Arc<TrayMenuState>>() {
+        tray.autostart_enabled.store(false, Ordering::SeqCst);
+        let _ = tray.autostart.set_checked(false);
+        save_setting(&app, SETTINGS_AUTOSTART, serde_json::json!(false));
+        apply_minimize_to_tray(&app, &tray, false);
+        let _ = apply_always_on_top(&app, &tray, false);
+    }
+    apply_monitoring_paused(&app, false);
+    for metric in METRIC_TRAY_IDS {
+        let _ = set_metric_tray(app.clone(), metric.to_string(), false);
+    }
+    let _ = rebuild_tray_menu(app.clone());
+    if let Some(state) = app.try_state::<Arc<MemoryGuardState>>() {
+        *state.rule.lock().map_err(|e| e.to_string())? = MemoryGuardRule::default();
+    }
+    if let Some(state) = app.try_state::<Arc<SwapAlertState>>() {
+        *state.config.lock().map_err(|e| e.to_string())? = SwapAlertConfig::default();
+    }
+    if let Some(state) = app.try_state::<Arc<HighLoadHookState>>() {
+        *state.config.lock().map_err(|e| e.to_string())? = HighLoadHookConfig::default();
+    }
+    if let Some(state) = app.try_state::<Arc<BatteryAlertState>>() {
+        *state.config.lock().map_err(|e| e.to_string())? = BatteryAlertConfig::default();
+    }
+    if let Some(state) = app.try_state::<Arc<DiskAlertState>>() {
+        *state.config.lock().map_err(|e| e.to_string())? = DiskAlertConfig::default();
+    }
+    if let Some(state) = app.try_state::<Arc<UpdateCheckState>>() {
+        *state.config.lock().map_err(|e| e.to_string())? = UpdateCheckConfig::default();
+    }
+    if let Some(state) = app.try_state::<Arc<HiddenProcessesState>>() {
+        state.names.lock().map_err(|e| e.to_string())?.clear();
+    }
+    if let Some(state) = app.try_state::<Arc<ClipboardHistoryState>>() {
+        *state.config.lock().map_err(|e| e.to_string())? = ClipboardHistoryConfig::default();
+        state.entries.lock().map_err(|e| e.to_string())?.clear();
+    }
+    if let Some(state) = app.try_state::<Arc<ProfilingState>>() {
+        state.enabled.store(false, Ordering::SeqCst);
+    }
+    if let Some(state) = app.try_state::<Arc<LocaleState>>() {
+        *state.locale.lock().map_err(|e| e.to_string())? = system_locale().to_string();
+        relabel_tray_menu(&app);
+    }
+    format::set_binary_base(true);
+    Ok(())
+}
+
+// Gap kept between a snapped window and the screen edges (logical pixels)
+const WINDOW_SNAP_MARGIN: f64 = 16.0;
+
+// Snap a window to a corner of the given display (index into available_monitors),
+// inside its work area so the menu bar / taskbar stay uncovered
+#[tauri::command]
+fn position_window(app: AppHandle, window_label: String, monitor_index: usize, corner: String) -> Result<(), String> {
+    let window = app
+        .get_webview_window(&window_label)
+        .ok_or_else(|| format!("Window {} not found", window_label))?;
+    let monitors = window.available_monitors().map_err(|e| e.to_string())?;
+    let monitor = monitors.get(monitor_index).ok_or_else(|| {
+        format!("Monitor {} out of range ({} available)", monitor_index, monitors.len())
+    })?;
+
+    let area = monitor.work_area();
+    let size = window.outer_size().map_err(|e| e.to_string())?;
+    let margin = (WINDOW_SNAP_MARGIN * monitor.scale_factor()).round() as i32;
+
+    let left = area.position.x + margin;
+    let top = area.position.y + margin;
+    let right = area.position.x + area.size.width as i32 - size.width as i32 - margin;
+    let bottom = area.position.y + area.size.height as i32 - size.height as i32 - margin;
+    let (x, y) = match corner.as_str() {
+        "top-left" => (left, top),
+        "top-right" => (right, top),
+        "bottom-left" => (left, bottom),
+        "bottom-right" => (right, bottom),
+        _ => return Err(format!("Unknown corner: {} (expected top-left, top-right, bottom-left or bottom-right)", corner)),
+    };
+
+    // A window larger than the work area is pinned to its top-left instead of going off screen
+    window
+        .set_position(tauri::PhysicalPosition::new(x.max(left), y.max(top)))
+        .map_err(|e| e.to_string())
+}
+
+// Quick maintenance actions: each runs one fixed, auditable command (no user input reaches a shell)
+fn run_maintenance_command(program: &str, args: &[&str]) -> Result<(), OrganizerError> {
+    let output = std::process::Command::new(program)
+        .args(args)
+        .output()
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => OrganizerError::Unsupported(format!("{} is not available", program)),
+            _ => OrganizerError::from(e),
+        })?;
+
+    if output.status.success() {
+        return Ok(());
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    let lower = stderr.to_lowercase();
+    // osascript reports a cancelled admin prompt as error -128
+    if lower.contains("denied")
+        || lower.contains("not authorized")
+        || lower.contains("permission")
+        || lower.contains("elevation")
+        || lower.contains("-128")
+    {
+        Err(OrganizerError::PermissionDenied(format!("{}: {}", program, stderr)))
+    } else {
+        Err(OrganizerError::Io(format!("{} failed: {}", program, stderr)))
+    }
+}
+
+// macOS: needs root, so go through the standard administrator prompt
+#[cfg(target_os = "macos")]
+#[tauri::command]
+fn flush_dns_cache() -> Result<(), OrganizerError> {
+    run_maintenance_command(
+        "osascript",
+        &["-e", "do shell script \"dscacheutil -flushcache; killall -HUP mDNSResponder\" with administrator privileges"],
+    )
+}
+
+#[cfg(target_os = "windows")]
+#[tauri::command]
+fn flush_dns_cache() -> Result<(), OrganizerError> {
+    run_maintenance_command("ipconfig", &["/flushdns"])
+}
+
+// Linux: systemd-resolved (polkit decides whether the user may flush)
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+#[tauri::command]
+fn flush_dns_cache() -> Result<(), OrganizerError> {
+    run_maintenance_command("resolvectl", &["flush-caches"])
+}
+
+// Terminal used by open_terminal_at (e.g. "iTerm" on macOS, "kitty" on Linux); unset = platform default
+#[tauri::command]
+fn get_terminal_app(app: AppHandle) -> Option<String> {
+    app.store("settings.json")
+        .ok()
+        .and_then(|store| store.get(SETTINGS_TERMINAL_APP))
+        .and_then(|v| v.as_str().map(|s| s.to_string()))
+        .filter(|s| !s.is_empty())
+}
+
+#[tauri::command]
+fn set_terminal_app(app: AppHandle, name: Option<String>) -> Result<(), String> {
+    let name = name.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+    save_setting(&app, SETTINGS_TERMINAL_APP, serde_json::json!(name));
+    Ok(())
+}
+
+// Open a terminal window in a directory (typically a process cwd from get_process_details)
+#[tauri::command]
+fn open_terminal_at(app: AppHandle, path: String) -> Result<(), OrganizerError> {
+    if !std::fs::metadata(&path)?.is_dir() {
+        return Err(OrganizerError::InvalidInput(format!("{} is not a directory", path)));
+    }
+    spawn_terminal(get_terminal_app(app).as_deref(), &path)
+}
+
+#[cfg(target_os = "macos")]
+fn spawn_terminal(terminal: Option<&str>, path: &str) -> Result<(), OrganizerError> {
+    std::process::Command::new("open")
+        .args(["-a", terminal.unwrap_or("Terminal"), path])
+        .spawn()?;
+    Ok(())
+}
+
+// Windows Terminal when available, plain cmd otherwise
+#[cfg(target_os = "windows")]
+fn spawn_terminal(terminal: Option<&str>, path: &str) -> Result<(), OrganizerError> {
+    use std::process::Command;
+
+    if let Some(terminal) = terminal {
+        Command::new(terminal).current_dir(path).spawn()?;
+        return Ok(());
+    }
+    if Command::new("wt").args(["-d", path]).spawn().is_ok() {
+        return Ok(());
+    }
+    Command::new("cmd")
+        .args(["/C", "start", "", "cmd", "/K", "cd", "/d", path])
+        .spawn()?;
+    Ok(())
+}
+
+// Linux: the configured terminal first, then the usual suspects (each with its working dir flag)
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn spawn_terminal(terminal: Option<&str>, path: &str) -> Result<(), OrganizerError> {
+    use std::process::Command;
+
+    let workdir_flag = |name: &str| match name {
+        "gnome-terminal" | "xfce4-terminal" | "alacritty" | "tilix" => Some(format!("--working-directory={}", path)),
+        "konsole" => Some(format!("--workdir={}", path)),
+        "kitty" => Some(format!("--directory={}", path)),
+        _ => None, // Falls back to inheriting the spawn cwd
+    };
+
+    let candidates = terminal.into_iter().chain([
+        "x-terminal-emulator",
+        "gnome-terminal",
+        "konsole",
+        "xfce4-terminal",
+        "alacritty",
+        "kitty",
+        "tilix",
+        "xterm",
+    ]);
+    for name in candidates {
+        let mut cmd = Command::new(name);
+        cmd.current_dir(path);
+        if let Some(flag) = workdir_flag(name) {
+            cmd.arg(flag);
+        }
+        if cmd.spawn().is_ok() {
+            return Ok(());
+        }
+    }
+
+    Err(OrganizerError::Unsupported("No terminal emulator found".into()))
+}
+
+#[derive(serde::Serialize)]
+struct Container {
+    id: String,
+    name: String,
+    image: String,
+    status: String,       // e.g. "Up 3 hours", "Exited (0) 2 days ago"
+    running: bool,
+    cpu_pct: Option<f64>, // None for stopped containers
+    mem_mb: Option<f64>,
+}
+
+// Run a docker subcommand, telling "no CLI" apart from "daemon not running"
+fn docker_json_lines(args: &[&str]) -> Result<Vec<serde_json::Value>, OrganizerError> {
+    let output = match std::process::Command::new("docker").args(args).output() {
+        Ok(o) => o,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Err(OrganizerError::Unsupported("docker CLI not found".into()));
+        }
+        Err(e) => return Err(e.into()),
+    };
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        if stderr.contains("Cannot connect to the Docker daemon") || stderr.contains("docker daemon is not running") {
+            return Err(OrganizerError::Io("Docker daemon is not running".into()));
+        }
+        return Err(OrganizerError::Io(stderr));
+    }
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| serde_json::from_str(line).map_err(OrganizerError::from))
+        .collect()
+}
+
+// "12.5MiB" / "1.2GB" -> MB
+fn parse_docker_size_mb(value: &str) -> Option<f64> {
+    let value = value.trim();
+    let split = value.find(|c: char| c.is_ascii_alphabetic())?;
+    let (number, unit) = value.split_at(split);
+    let number: f64 = number.trim().parse().ok()?;
+    let bytes = match unit {
+        "B" => number,
+        "kB" | "KB" => number * 1e3,
+        "KiB" => number * 1024.0,
+        "MB" => number * 1e6,
+        "MiB" => number * 1_048_576.0,
+        "GB" => number * 1e9,
+        "GiB" => number * 1_073_741_824.0,
+        "TB" => number * 1e12,
+        "TiB" => number * 1_099_511_627_776.0,
+        _ => return None,
+    };
+    Some(bytes / 1_048_576.0)
+}
+
+// All containers (running first, biggest memory first) with live usage for the running ones
+#[tauri::command]
+async fn list_containers() -> Result<Vec<Container>, OrganizerError> {
+    tauri::async_runtime::spawn_blocking(|| {
+        let str_field = |v: &serde_json::Value, key: &str| {
+            v.get(key).and_then(|f| f.as_str()).unwrap_or("").to_string()
+        };
+
+        let ps = docker_json_lines(&["ps", "-a", "--no-trunc", "--format", "{{json .}}"])?;
+        let stats = docker_json_lines(&["stats", "--no-stream", "--format", "{{json .}}"])?;
+
+        let mut containers: Vec<Container> = ps
+            .iter()
+            .map(|c| {
+                let id = str_field(c, "ID");
+                // stats reports short ids
+                let usage = stats.iter().find(|s| {
+                    let short = str_field(s, "ID");
+                    !short.is_empty() && id.starts_with(&short)
+                });
+                Container {
+                    name: str_field(c, "Names"),
+                    image: str_field(c, "Image"),
+                    status: str_field(c, "Status"),
+                    running: str_field(c, "State") == "running",
+                    cpu_pct: usage.and_then(|s| str_field(s, "CPUPerc").trim_end_matches('%').parse().ok()),
+                    mem_mb: usage.and_then(|s| {
+                        parse_docker_size_mb(str_field(s, "MemUsage").split('/').next().unwrap_or(""))
+                    }),
+                    id,
+                }
+            })
+            .collect();
+
+        containers.sort_by(|a, b| {
+            b.running
+                .cmp(&a.running)
+                .then(b.mem_mb.unwrap_or(0.0).partial_cmp(&a.mem_mb.unwrap_or(0.0)).unwrap_or(std::cmp::Ordering::Equal))
+        });
+        Ok(containers)
+    })
+    .await?
+}
+
+#[derive(serde::Serialize)]
+struct ServiceInfo {
+    name: String,
+    running: bool,
+    pid: Option<u32>,
+    scope: String, // "user" or "system"
+}
+
+// Service names go straight to launchctl/systemctl: reject anything that could be read as an option
+fn validate_service_name(name: &str) -> Result<(), OrganizerError> {
+    if name.is_empty() || name.starts_with('-') || name.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        return Err(OrganizerError::InvalidInput(format!("Invalid service name: {:?}", name)));
+    }
+    Ok(())
+}
+
+// macOS: launchd jobs in the current user's domain ("PID\tStatus\tLabel", PID is "-" when stopped).
+// System daemons are only listed with root, so everything here is user scope.
+#[cfg(target_os = "macos")]
+#[tauri::command]
+fn list_services() -> Result<Vec<ServiceInfo>, OrganizerError> {
+    let output = std::process::Command::new("launchctl").arg("list").output()?;
+    if !output.status.success() {
+        return Err(OrganizerError::Io(String::from_utf8_lossy(&output.stderr).trim().to_string()));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut services: Vec<ServiceInfo> = stdout
+        .lines()
+        .skip(1) // Header
+        .filter_map(|line| {
+            let mut parts = line.split('\t');
+            let pid = parts.next()?.trim().parse::<u32>().ok();
+            let _status = parts.next()?;
+            let label = parts.next()?.trim();
+            Some(ServiceInfo {
+                name: label.to_string(),
+                running: pid.is_some(),
+                pid,
+                scope: "user".to_string(),
+            })
+        })
+        .collect();
+
+    services.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(services)
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+fn toggle_service(name: String, start: bool, scope: String) -> Result<(), OrganizerError> {
+    validate_service_name(&name)?;
+    if scope != "user" {
+        return Err(OrganizerError::Unsupported("Only user launchd jobs can be controlled".into()));
+    }
+    run_maintenance_command("launchctl", &[if start { "start" } else { "stop" }, &name])
+}
+
+// Linux: systemd units for the system manager and the user manager
+#[cfg(target_os = "linux")]
+fn list_systemd_services(user: bool) -> Vec<ServiceInfo> {
+    use std::process::Command;
+
+    let scope_args: &[&str] = if user { &["--user"] } else { &[] };
+    let output = match Command::new("systemctl")
+        .args(scope_args)
+        .args(["list-units", "--type=service", "--all", "--no-legend", "--plain", "--no-pager"])
+        .output()
+    {
+        Ok(o) if o.status.success() => o,
+        _ => return Vec::new(),
+    };
+
+    // UNIT LOAD ACTIVE SUB DESCRIPTION...
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut services: Vec<ServiceInfo> = stdout
+        .lines()
+        .filter_map(|line| {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            let unit = parts.first()?;
+            let sub = parts.get(3)?;
+            Some(ServiceInfo {
+                name: unit.to_string(),
+                running: *sub == "running",
+                pid: None,
+                scope: if user { "user" } else { "system" }.to_string(),
+            })
+        })
+        .collect();
+
+    // Resolve main PIDs of running units in a single call
+    let running: Vec<String> = services.iter().filter(|s| s.running).map(|s| s.name.clone()).collect();
+    if !running.is_empty() {
+        if let Ok(show) = Command::new("systemctl")
+            .args(scope_args)
+            .args(["show", "--property=Id,MainPID", "--no-pager"])
+            .args(&running)
+            .output()
+        {
+            // Blocks of "MainPID=123\nId=foo.service" separated by blank lines
+            let stdout = String::from_utf8_lossy(&show.stdout);
+            for block in stdout.split("\n\n") {
+                let mut id = None;
+                let mut pid = None;
+                for line in block.lines() {
+                    if let Some(v) = line.strip_prefix("Id=") {
+                        id = Some(v.to_string());
+                    } else if let Some(v) = line.strip_prefix("MainPID=") {
+                        pid = v.parse::<u32>().ok().filter(|p| *p > 0);
+                    }
+                }
+                if let (Some(id), Some(pid)) = (id, pid) {
+                    if let Some(service) = services.iter_mut().find(|s| s.name == id) {
+                        service.pid = Some(pid);
+                    }
+                }
+            }
+        }
+    }
+
+    services
+}
+
+#[cfg(target_os = "linux")]
+#[tauri::command]
+fn list_services() -> Result<Vec<ServiceInfo>, OrganizerError> {
+    let mut services = list_systemd_services(false);
+    services.extend(list_systemd_services(true));
+    if services.is_empty() {
+        return Err(OrganizerError::Unsupported("systemd is not available".into()));
+    }
+    services.sort_by(|a, b| a.scope.cmp(&b.scope).then(a.name.cmp(&b.name)));
+    Ok(services)
+}
+
+// System units usually need polkit authorization, surfaced as PermissionDenied
+#[cfg(target_os = "linux")]
+#[tauri::command]
+fn toggle_service(name: String, start: bool, scope: String) -> Result<(), OrganizerError> {
+    validate_service_name(&name)?;
+    let action = if start { "start" } else { "stop" };
+    match scope.as_str() {
+        "user" => run_maintenance_command("systemctl", &["--user", action, &name]),
+        "system" => run_maintenance_command("systemctl", &[action, &name]),
+        _ => Err(OrganizerError::InvalidInput(format!("Unknown service scope: {}", scope))),
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+#[tauri::command]
+fn list_services() -> Result<Vec<ServiceInfo>, OrganizerError> {
+    Err(OrganizerError::Unsupported("Service listing is not available on this platform".into()))
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+#[tauri::command]
+fn toggle_service(name: String, _start: bool, _scope: String) -> Result<(), OrganizerError> {
+    validate_service_name(&name)?;
+    Err(OrganizerError::Unsupported("Service control is not available on this platform".into()))
+}
+
+#[derive(serde::Serialize)]
+struct Permissions {
+    full_disk_access: bool, // Needed for lsof/scans on protected paths (Mail, Safari, TCC...)
+    accessibility: bool,    // Needed to query other apps' windows
+}
+
+#[cfg(target_os = "macos")]
+#[link(name = "ApplicationServices", kind = "framework")]
+extern "C" {
+    fn AXIsProcessTrusted() -> bool;
+}
+
+// macOS: probe TCC-protected resources instead of failing silently later.
+// The system TCC database is only readable with Full Disk Access.
+#[cfg(target_os = "macos")]
+#[tauri::command]
+fn check_permissions() -> Permissions {
+    let full_disk_access = std::fs::File::open("/Library/Application Support/com.apple.TCC/TCC.db").is_ok();
+    Permissions {
+        full_disk_access,
+        accessibility: unsafe { AXIsProcessTrusted() },
+    }
+}
+
+// No TCC equivalent elsewhere
+#[cfg(not(target_os = "macos"))]
+#[tauri::command]
+fn check_permissions() -> Permissions {
+    Permissions {
+        full_disk_access: true,
+        accessibility: true,
+    }
+}
+
+#[derive(serde::Serialize)]
+struct AutostartInfo {
+    enabled: bool,
+    registered_path: Option<String>, // Executable the login item launches
+    expected_path: String,           // What it should launch: this copy of the app
+    method: String,                  // Where the login item lives (plist, registry value, .desktop)
+    stale: bool,                     // Registered but pointing elsewhere, e.g. after moving the app
+}
+
+// Same path the autostart plugin registers
+#[cfg(target_os = "macos")]
+fn autostart_expected_path(_app: &AppHandle) -> Option<String> {
+    let exe = std::env::current_exe().ok()?.canonicalize().ok()?;
+    Some(exe.to_string_lossy().to_string())
+}
+
+#[cfg(target_os = "linux")]
+fn autostart_expected_path(app: &AppHandle) -> Option<String> {
+    if let Some(appimage) = app.env().appimage {
+        return Some(appimage.to_string_lossy().to_string());
+    }
+    std::env::current_exe().ok().map(|exe| exe.to_string_lossy().to_string())
+}
+
+#[cfg(target_os = "windows")]
+fn autostart_expected_path(_app: &AppHandle) -> Option<String> {
+    std::env::current_exe().ok().map(|exe| exe.to_string_lossy().to_string())
+}
+
+// First word of a command line, honoring double quotes
+#[cfg(not(target_os = "macos"))]
+fn command_line_program(line: &str) -> Option<String> {
+    let line = line.trim();
+    match line.strip_prefix('"') {
+        Some(rest) => rest.split('"').next().map(str::to_string),
+        None => line.split_whitespace().next().map(str::to_string),
+    }
+}
+
+// (method, registered executable) for the login item named after the app
+#[cfg(target_os = "macos")]
+fn autostart_registration(app: &AppHandle) -> (String, Option<String>) {
+    let Ok(home) = app.path().home_dir() else { return ("LaunchAgent".to_string(), None) };
+    let plist = home
+        .join("Library/LaunchAgents")
+        .join(format!("{}.plist", app.package_info().name));
+    // First ProgramArguments entry
+    let registered = std::fs::read_to_string(&plist).ok().and_then(|content| {
+        let after = content.split("<key>ProgramArguments</key>").nth(1)?;
+        let start = after.find("<string>")? + "<string>".len();
+        let end = after[start..].find("</string>")?;
+        Some(after[start..start + end].to_string())
+    });
+    (format!("LaunchAgent: {}", plist.display()), registered)
+}
+
+#[cfg(target_os = "linux")]
+fn autostart_registration(app: &AppHandle) -> (String, Option<String>) {
+    let Ok(home) = app.path().home_dir() else { return ("XDG autostart".to_string(), None) };
+    let desktop = home
+        .join(".config/autostart")
+        .join(format!("{}.desktop", app.package_info().name));
+    let registered = std::fs::read_to_string(&desktop).ok().and_then(|content| {
+        content
+            .lines()
+            .find_map(|line| line.strip_prefix("Exec="))
+            .and_then(command_line_program)
+    });
+    (format!("XDG autostart: {}", desktop.display()), registered)
+}
+
+#[cfg(target_os = "windows")]
+fn autostart_registration(app: &AppHandle) -> (String, Option<String>) {
+    const RUN_KEY: &str = r"HKCU\SOFTWARE\Microsoft\Windows\CurrentVersion\Run";
+    let name = app.package_info().name.clone();
+    // "    Organizer    REG_SZ    "C:\...\organizer.exe" args"
+    let registered = command_stdout("reg", &["query", RUN_KEY, "/v", &name]).and_then(|out| {
+        out.lines()
+            .find_map(|line| line.split_once("REG_SZ").map(|(_, value)| value.to_string()))
+            .and_then(|value| command_line_program(&value))
+    });
+    (format!("Registry: {}\\{}", RUN_KEY, name), registered)
+}
+
+#[tauri::command]
+fn get_autostart_details(app: AppHandle) -> AutostartInfo {
+    use tauri_plugin_autostart::ManagerExt;
+    let enabled = app.autolaunch().is_enabled().unwrap_or(false);
+    let (method, registered_path) = autostart_registration(&app);
+    let expected_path = autostart_expected_path(&app).unwrap_or_default();
+    let stale = registered_path
+        .as_deref()
+        .is_some_and(|path| path != expected_path || !Path::new(path).exists());
+    AutostartInfo {
+        enabled,
+        registered_path,
+        expected_path,
+        method,
+        stale,
+    }
+}
+
+// Re-register the login item for this copy of the app when it points elsewhere, or when
+// the setting is on but nothing is registered anymore
+#[tauri::command]
+fn repair_autostart(app: AppHandle) -> Result<AutostartInfo, String> {
+    use tauri_plugin_autostart::ManagerExt;
+    let wanted = app
+        .store("settings.json")
+        .ok()
+        .and_then(|store| store.get(SETTINGS_AUTOSTART))
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    let details = get_autostart_details(app.clone());
+    if details.stale || (wanted && details.registered_path.is_none()) {
+        let manager = app.autolaunch();
+        let _ = manager.disable();
+        manager.enable().map_err(|e| e.to_string())?;
+    }
+    Ok(get_autostart_details(app))
+}
+
+// Deep links for the panes a missing permission sends the user to
+#[cfg(target_os = "macos")]
+fn system_settings_url(pane: &str) -> Option<&'static str> {
+    match pane {
+        "full_disk_access" => Some("x-apple.systempreferences:com.apple.preference.security?Privacy_AllFiles"),
+        "accessibility" => Some("x-apple.systempreferences:com.apple.preference.security?Privacy_Accessibility"),
+        "notifications" => Some("x-apple.systempreferences:com.apple.preference.notifications"),
+        "login_items" => Some("x-apple.systempreferences:com.apple.LoginItems-Settings.extension"),
+        _ => None,
+    }
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+fn open_system_settings(app: AppHandle, pane: String) -> Result<(), OrganizerError> {
+    use tauri_plugin_opener::OpenerExt;
+
+    let url = system_settings_url(&pane)
+        .ok_or_else(|| OrganizerError::NotFound(format!("Settings pane {}", pane)))?;
+    app.opener()
+        .open_url(url, None::<&str>)
+        .map_err(|e| OrganizerError::Io(e.to_string()))
+}
+
+#[cfg(not(target_os = "macos"))]
+#[tauri::command]
+fn open_system_settings(_app: AppHandle, pane: String) -> Result<(), OrganizerError> {
+    Err(OrganizerError::Unsupported(format!("Settings pane {} is macOS only", pane)))
+}
+
+#[derive(serde::Serialize)]
+struct AppPaths {
+    config_dir: Option<String>,
+    data_dir: Option<String>,
+    log_dir: Option<String>,
+    settings_file: Option<String>, // settings.json (the store plugin resolves it under the data dir)
+}
+
+#[tauri::command]
+fn get_app_paths(app: AppHandle) -> AppPaths {
+    use tauri::path::BaseDirectory;
+
+    let to_string = |p: std::path::PathBuf| p.to_string_lossy().to_string();
+    let path = app.path();
+
+    AppPaths {
+        config_dir: path.app_config_dir().ok().map(to_string),
+        data_dir: path.app_data_dir().ok().map(to_string),
+        log_dir: path.app_log_dir().ok().map(to_string),
+        settings_file: path.resolve("settings.json", BaseDirectory::AppData).ok().map(to_string),
+    }
+}
+
+// Open the app data dir (where settings.json lives) in the system file manager
+#[tauri::command]
+fn reveal_app_data(app: AppHandle) -> Result<(), String> {
+    use tauri_plugin_opener::OpenerExt;
+
+    let data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    std::fs::create_dir_all(&data_dir).map_err(|e| e.to_string())?;
+
+    app.opener()
+        .open_path(data_dir.to_string_lossy(), None::<&str>)
+        .map_err(|e| e.to_string())
+}
+
+// Desktop notification from background monitors
+fn notify(app: &AppHandle, title: &str, body: &str) {
+    use tauri_plugin_notification::NotificationExt;
+    // Not granted: ask the UI to prompt instead of pretending the alert was shown. Only
+    // reached on mobile today, see notification_permission_state.
+    let state = notification_permission_state(app.clone());
+    if state != "granted" {
+        emit_or_log(app, "notifications", "notifications:permission", &state);
+        return;
+    }
+    if let Err(e) = app.notification().builder().title(title).body(body).show() {
+        log_monitor_error("notifications", e);
+    }
+}
+
+// "granted", "denied" or "default" (not asked yet), like the web Notification API
+fn permission_state_name(state: tauri::plugin::PermissionState) -> String {
+    use tauri::plugin::PermissionState;
+    match state {
+        PermissionState::Granted => "granted",
+        PermissionState::Denied => "denied",
+        PermissionState::Prompt | PermissionState::PromptWithRationale => "default",
+    }
+    .to_string()
+}
+
+// On desktop the notification plugin always reports (and "requests") granted: it doesn't
+// query the OS, so a user who turned notifications off in System Settings is not detected
+// and the prompt path in notify never runs there. Only mobile reports the real state.
+#[tauri::command]
+fn notification_permission_state(app: AppHandle) -> String {
+    use tauri_plugin_notification::NotificationExt;
+    app.notification()
+        .permission_state()
+        .map(permission_state_name)
+        .unwrap_or_else(|_| "default".to_string())
+}
+
+#[tauri::command]
+fn request_notification_permission(app: AppHandle) -> Result<String, String> {
+    use tauri_plugin_notification::NotificationExt;
+    app.notification()
+        .request_permission()
+        .map(permission_state_name)
+        .map_err(|e| e.to_string())
+}
+
+// Processes that bulk actions (memory guard, batch kills) must never signal
+const CRITICAL_PROCESS_NAMES: &[&str] = &[
+    // macOS
+    "kernel_task", "launchd", "WindowServer", "loginwindow", "Finder", "Dock", "SystemUIServer",
+    // Linux
+    "systemd", "init", "kthreadd", "Xorg", "Xwayland", "gnome-shell", "dbus-daemon", "sshd",
+    // Windows
+    "System", "Registry", "smss.exe", "csrss.exe", "wininit.exe", "winlogon.exe", "services.exe",
+    "lsass.exe", "svchost.exe", "dwm.exe", "explorer.exe",
+];
+
+fn is_protected_process(pid: u32, name: &str) -> bool {
+    pid <= 1
+        || pid == std::process::id()
+        || CRITICAL_PROCESS_NAMES.iter().any(|n| n.eq_ignore_ascii_case(name))
+}
+
+// SIGTERM, or SIGKILL with force. There is no SIGTERM on Windows: kill_with returns None
+// there and this falls back to a hard kill. Returns the signal sent, None if it failed.
+fn send_terminate(process: &sysinfo::Process, force: bool) -> Option<&'static str> {
+    if !force {
+        if let Some(sent) = process.kill_with(sysinfo::Signal::Term) {
+            return sent.then_some("SIGTERM");
+        }
+    }
+    process.kill().then_some("SIGKILL")
+}
+
+// Shortest pattern kill_matching accepts, so "n" can't take out half the machine
+const KILL_MATCHING_MIN_PATTERN: usize = 3;
+
+// Signal every process whose name contains pattern (case-insensitive): SIGTERM, or SIGKILL with
+// force. Protected processes, this app and other users' processes are skipped. Returns the
+// PIDs that were signalled.
+#[tauri::command]
+fn kill_matching(pattern: String, force: bool) -> Result<Vec<u32>, String> {
+    use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind};
+
+    let pattern = pattern.trim().to_string();
+    if pattern.chars().count() < KILL_MATCHING_MIN_PATTERN {
+        return Err(format!("Pattern must be at least {} characters", KILL_MATCHING_MIN_PATTERN));
+    }
+
+    let mut sys = System::new();
+    sys.refresh_processes_specifics(
+        ProcessesToUpdate::All,
+        true,
+        ProcessRefreshKind::new().with_user(UpdateKind::OnlyIfNotSet),
+    );
+    let own_pid = std::process::id();
+    let own_user = sys.process(Pid::from_u32(own_pid)).and_then(|p| p.user_id()).cloned();
+
+    let mut killed = Vec::new();
+    for (pid, process) in sys.processes() {
+        let name = process.name().to_string_lossy();
+        // An owner that can't be read (ours or the process's) is treated as someone else
+        let same_user = match (&own_user, process.user_id()) {
+            (Some(own), Some(user)) => own == user,
+            _ => false,
+        };
+        if !process_name_matches(&name, &pattern) || !same_user || is_protected_process(pid.as_u32(), &name) {
+            continue;
+        }
+
+        if send_terminate(process, force).is_some() {
+            killed.push(pid.as_u32());
+        }
+    }
+    killed.sort_unstable();
+    Ok(killed)
+}
+
+// Owner name per PID. macOS: one `ps` for every process, like the process details.
+#[cfg(target_os = "macos")]
+fn process_owners() -> HashMap<u32, String> {
+    let out = command_stdout("ps", &["-axo", "pid=,user="]).unwrap_or_default();
+    out.lines()
+        .filter_map(|line| {
+            let (pid, user) = line.trim().split_once(char::is_whitespace)?;
+            Some((pid.parse().ok()?, user.trim().to_string()))
+        })
+        .collect()
+}
+
+// Linux: uid from /proc, Windows: the process token's SID, both resolved to account names
+#[cfg(not(target_os = "macos"))]
+fn process_owners() -> HashMap<u32, String> {
+    use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind, Users};
+
+    let mut sys = System::new();
+    sys.refresh_processes_specifics(
+        ProcessesToUpdate::All,
+        true,
+        ProcessRefreshKind::new().with_user(UpdateKind::OnlyIfNotSet),
+    );
+    let users = Users::new_with_refreshed_list();
+    sys.processes()
+        .iter()
+        .filter_map(|(pid, process)| {
+            let user = users.get_user_by_id(process.user_id()?)?;
+            Some((pid.as_u32(), user.name().to_string()))
+        })
+        .collect()
+}
+
+// Exact, case-sensitive username match: "bob" never picks up "bobby" or "Bob"
+fn pids_owned_by(user: &str) -> Result<HashSet<u32>, String> {
+    let user = user.trim();
+    if user.is_empty() {
+        return Err("A username is required".to_string());
+    }
+    Ok(process_owners()
+        .into_iter()
+        .filter(|(_, owner)| owner == user)
+        .map(|(pid, _)| pid)
+        .collect())
+}
+
+#[tauri::command]
+async fn get_processes_by_user(app: AppHandle, user: String) -> Result<Vec<ProcessMemory>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let pids = pids_owned_by(&user)?;
+        let top = with_shared_processes(&app, DetailLevel::Light, |sys| top_processes(sys, usize::MAX, None, &[], DetailLevel::Light))
+            .map_err(|e| e.to_string())?;
+        Ok(top.processes.into_iter().filter(|p| pids.contains(&p.pid)).collect())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+// Signal every process of one user: SIGTERM, or SIGKILL with force. Protected processes and
+// this app with its children (webview and helper processes) are skipped; other users'
+// processes usually need elevated rights. Returns the PIDs that were signalled.
+#[tauri::command]
+fn kill_user_processes(user: String, force: bool) -> Result<Vec<u32>, String> {
+    let pids = pids_owned_by(&user)?;
+    let sys = process_names_system(sysinfo::ProcessesToUpdate::All);
+    let own_tree = descendant_pids(&sys, sysinfo::Pid::from_u32(std::process::id()));
+    let mut killed = Vec::new();
+    for (pid, process) in sys.processes() {
+        let name = process.name().to_string_lossy();
+        if !pids.contains(&pid.as_u32()) || own_tree.contains(pid) || is_protected_process(pid.as_u32(), &name) {
+            continue;
+        }
+        if send_terminate(process, force).is_some() {
+            killed.push(pid.as_u32());
+        }
+    }
+    killed.sort_unstable();
+    Ok(killed)
+}
+
+// Memory guard: opt-in auto-kill of processes above a memory ceiling
+const MEMORY_GUARD_INTERVAL_SECS: u64 = 5;
+const MEMORY_GUARD_GRACE_SECS: u64 = 10; // Between SIGTERM and SIGKILL
+
+#[derive(Clone, Default, serde::Serialize, serde::Deserialize)]
+struct MemoryGuardRule {
+    enabled: bool,
+    armed: bool,          // Explicit confirmation: nothing is killed unless this is also set
+    name_pattern: String, // Case-insensitive substring of the process name
+    max_memory_mb: f64,
+}
+
+struct MemoryGuardState {
+    rule: Mutex<MemoryGuardRule>,
+}
+
+#[derive(Clone, serde::Serialize)]
+struct GuardKilled {
+    pid: u32,
+    name: String,
+    memory_mb: f64,
+    signal: String,
+}
+
+#[tauri::command]
+fn get_memory_guard(app: AppHandle) -> Result<MemoryGuardRule, String> {
+    let state = app
+        .try_state::<Arc<MemoryGuardState>>()
+        .ok_or("Memory guard state not found")?;
+    let rule = state.rule.lock().map_err(|e| e.to_string())?.clone();
+    Ok(rule)
+}
+
+#[tauri::command]
+fn set_memory_guard(app: AppHandle, rule: MemoryGuardRule) -> Result<(), String> {
+    let state = app
+        .try_state::<Arc<MemoryGuardState>>()
+        .ok_or("Memory guard state not found")?;
+    let value = serde_json::to_value(&rule).map_err(|e| e.to_string())?;
+    *state.rule.lock().map_err(|e| e.to_string())? = rule;
+    save_setting(&app, SETTINGS_MEMORY_GUARD, value);
+    Ok(())
+}
+
+fn spawn_memory_guard(app: AppHandle) {
+    let shutdown = shutdown_signal(&app);
+    std::thread::spawn(move || {
+        use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+
+        let mut sys = System::new();
+        // Processes already sent SIGTERM, waiting for the grace period to expire. Keyed by
+        // (pid, start time) so a reused PID is a new process, never SIGKILLed by inheritance.
+        let mut terminating: HashMap<(u32, u64), Instant> = HashMap::new();
+
+        loop {
+            if !shutdown.sampler_sleep(Duration::from_secs(MEMORY_GUARD_INTERVAL_SECS)) {
+                return;
+            }
+
+            let rule = match app.try_state::<Arc<MemoryGuardState>>() {
+                Some(state) => match state.rule.lock() {
+                    Ok(rule) => rule.clone(),
+                    Err(e) => {
+                        log_monitor_error("memory guard", e);
+                        continue;
+                    }
+                },
+                None => continue,
+            };
+
+            let pattern = rule.name_pattern.trim().to_lowercase();
+            if !rule.enabled || !rule.armed || pattern.is_empty() || rule.max_memory_mb <= 0.0 {
+                terminating.clear();
+                continue;
+            }
+
+            sys.refresh_processes_specifics(
+                ProcessesToUpdate::All,
+                true,
+                ProcessRefreshKind::new().with_memory(),
+            );
+            terminating.retain(|(pid, start_time), _| {
+                sys.process(Pid::from_u32(*pid)).is_some_and(|p| p.start_time() == *start_time)
+            });
+
+            for (pid, process) in sys.processes() {
+                let pid_u32 = pid.as_u32();
+                let name = process.name().to_string_lossy().to_string();
+                if !process_name_matches(&name, &pattern) || is_protected_process(pid_u32, &name) {
+                    continue;
+                }
+
+                let memory_mb = process_footprint_bytes(pid_u32, process) as f64 / 1_048_576.0;
+                let key = (pid_u32, process.start_time());
+
+                let signal = match terminating.get(&key) {
+                    // Still alive after the grace period: force kill
+                    Some(sent_at) if sent_at.elapsed() >= Duration::from_secs(MEMORY_GUARD_GRACE_SECS) => {
+                        terminating.remove(&key);
+                        if !process.kill() {
+                            continue;
+                        }
+                        "SIGKILL"
+                    }
+                    Some(_) => continue,
+                    None if memory_mb > rule.max_memory_mb => match send_terminate(process, false) {
+                        Some("SIGTERM") => {
+                            terminating.insert(key, Instant::now());
+                            "SIGTERM"
+                        }
+                        Some(signal) => signal,
+                        None => continue,
+                    },
+                    None => continue,
+                };
+
+                emit_or_log(&app, "memory guard", "guard:killed", GuardKilled {
+                    pid: pid_u32,
+                    name: name.clone(),
+                    memory_mb,
+                    signal: signal.to_string(),
+                });
+                notify(
+                    &app,
+                    &tr(&app, "notify.memory_guard.title", &[]),
+                    &tr(&app, "notify.memory_guard.body", &[
+                        ("name", &name),
+                        ("pid", &pid_u32.to_string()),
+                        ("memory_mb", &format!("{:.0}", memory_mb)),
+                        ("signal", signal),
+                    ]),
+                );
+                raise_alert(&app);
+            }
+        }
+    });
+}
+
+// Swap alert: notify when swap usage stays above a ratio (or swapouts spike)
+const SWAP_ALERT_INTERVAL_SECS: u64 = 10;
+const SWAP_ALERT_SUSTAIN_SAMPLES: u32 = 3;     // Consecutive samples above threshold before alerting
+const SWAP_ALERT_SWAPOUTS_PER_SEC: f64 = 500.0; // Pages/s, a sign of active thrashing
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+struct SwapAlertConfig {
+    enabled: bool,
+    ratio: f64, // swap_used / swap_total, 0.0-1.0
+}
+
+impl Default for SwapAlertConfig {
+    fn default() -> Self {
+        SwapAlertConfig { enabled: false, ratio: 0.8 }
+    }
+}
+
+struct SwapAlertState {
+    config: Mutex<SwapAlertConfig>,
+}
+
+#[derive(Clone, serde::Serialize)]
+struct SwapAlert {
+    swap_used_gb: f64,
+    swap_total_gb: f64,
+    ratio: f64,
+    swapouts_per_sec: Option<f64>,
+}
+
+#[derive(serde::Serialize)]
+struct SwapFile {
+    path: String,
+    size_bytes: u64,
+}
+
+// macOS: the dynamic pager's swapfile0, swapfile1... in /private/var/vm (sleepimage excluded)
+#[cfg(target_os = "macos")]
+#[tauri::command]
+fn get_swap_files() -> Vec<SwapFile> {
+    let Ok(entries) = std::fs::read_dir("/private/var/vm") else { return Vec::new() };
+    let mut files: Vec<SwapFile> = entries
+        .flatten()
+        .filter(|e| e.file_name().to_string_lossy().starts_with("swapfile"))
+        .filter_map(|e| {
+            Some(SwapFile {
+                path: e.path().to_string_lossy().to_string(),
+                size_bytes: e.metadata().ok()?.len(),
+            })
+        })
+        .collect();
+    // swapfile2 before swapfile10
+    files.sort_by(|a, b| (a.path.len(), &a.path).cmp(&(b.path.len(), &b.path)));
+    files
+}
+
+#[cfg(not(target_os = "macos"))]
+#[tauri::command]
+fn get_swap_files() -> Vec<SwapFile> {
+    Vec::new()
+}
+
+// Cumulative pages swapped out since boot (macOS: vm_statistics, Linux: /proc/vmstat)
+#[cfg(target_os = "macos")]
+fn swapouts_total() -> Option<u64> {
+    host_vm_statistics().map(|(vm_stat, _)| vm_stat.swapouts)
+}
+
+#[cfg(target_os = "linux")]
+fn swapouts_total() -> Option<u64> {
+    std::fs::read_to_string("/proc/vmstat")
+        .ok()?
+        .lines()
+        .find_map(|line| line.strip_prefix("pswpout "))
+        .and_then(|v| v.trim().parse().ok())
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+fn swapouts_total() -> Option<u64> {
+    None
+}
+
+#[tauri::command]
+fn get_swap_alert(app: AppHandle) -> Result<SwapAlertConfig, String> {
+    let state = app
+        .try_state::<Arc<SwapAlertState>>()
+        .ok_or("Swap alert state not found")?;
+    let config = state.config.lock().map_err(|e| e.to_string())?.clone();
+    Ok(config)
+}
+
+#[tauri::command]
+fn set_swap_alert(app: AppHandle, ratio: f64, enabled: bool) -> Result<(), String> {
+    if !(0.0..=1.0).contains(&ratio) {
+        return Err(format!("Ratio must be between 0 and 1, got {}", ratio));
+    }
+    let state = app
+        .try_state::<Arc<SwapAlertState>>()
+        .ok_or("Swap alert state not found")?;
+    let config = SwapAlertConfig { enabled, ratio };
+    let value = serde_json::to_value(&config).map_err(|e| e.to_string())?;
+    *state.config.lock().map_err(|e| e.to_string())? = config;
+    save_setting(&app, SETTINGS_SWAP_ALERT, value);
+    Ok(())
+}
+
+fn spawn_swap_monitor(app: AppHandle) {
+    let shutdown = shutdown_signal(&app);
+    std::thread::spawn(move || {
+        let mut above_count: u32 = 0;
+        let mut alerted = false;
+        let mut last_swapouts: Option<(u64, Instant)> = None;
+
+        loop {
+            if !shutdown.sampler_sleep(Duration::from_secs(SWAP_ALERT_INTERVAL_SECS)) {
+                return;
+            }
+
+            let config = match app.try_state::<Arc<SwapAlertState>>() {
+                Some(state) => match state.config.lock() {
+                    Ok(config) => config.clone(),
+                    Err(e) => {
+                        log_monitor_error("swap monitor", e);
+                        continue;
+                    }
+                },
+                None => continue,
+            };
+
+            // Keep sampling swapouts so the rate is meaningful as soon as alerts are enabled
+            let now = Instant::now();
+            let swapouts = swapouts_total();
+            let swapouts_per_sec = match (swapouts, last_swapouts) {
+                (Some(current), Some((previous, at))) => {
+                    let secs = now.duration_since(at).as_secs_f64();
+                    if secs > 0.0 { Some(current.saturating_sub(previous) as f64 / secs) } else { None }
+                }
+                _ => None,
+            };
+            last_swapouts = swapouts.map(|s| (s, now));
+
+            if !config.enabled {
+                above_count = 0;
+                alerted = false;
+                continue;
+            }
+
+            let memory = with_shared_system(&app, memory_info);
+            let ratio = if memory.swap_total_gb > 0.0 {
+                memory.swap_used_gb / memory.swap_total_gb
+            } else {
+                0.0
+            };
+
+            let exceeded = (memory.swap_total_gb > 0.0 && ratio >= config.ratio)
+                || swapouts_per_sec.map(|r| r >= SWAP_ALERT_SWAPOUTS_PER_SEC).unwrap_or(false);
+
+            if !exceeded {
+                // Back under the threshold: re-arm for the next crossing
+                above_count = 0;
+                alerted = false;
+                continue;
+            }
+
+            above_count += 1;
+            if alerted || above_count < SWAP_ALERT_SUSTAIN_SAMPLES {
+                continue;
+            }
+            alerted = true;
+
+            emit_or_log(&app, "swap monitor", "swap:alert", SwapAlert {
+                swap_used_gb: memory.swap_used_gb,
+                swap_total_gb: memory.swap_total_gb,
+                ratio,
+                swapouts_per_sec,
+            });
+            notify(
+                &app,
+                &tr(&app, "notify.swap.title", &[]),
+                &tr(&app, "notify.swap.body", &[
+                    ("used_gb", &format!("{:.1}", memory.swap_used_gb)),
+                    ("total_gb", &format!("{:.1}", memory.swap_total_gb)),
+                    ("pct", &format!("{:.0}", ratio * 100.0)),
+                ]),
+            );
+            raise_alert(&app);
+        }
+    });
+}
+
+// High load hook: run a user command when total CPU stays above a threshold
+const HIGH_LOAD_INTERVAL_SECS: u64 = 5;
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+struct HighLoadHookConfig {
+    on_high_load_command: Option<String>, // Shell command line, None disables the hook
+    threshold_pct: f64,                   // Total CPU, 0-100
+    sustain_secs: u64,                    // How long the load must stay above the threshold
+}
+
+impl Default for HighLoadHookConfig {
+    fn default() -> Self {
+        HighLoadHookConfig { on_high_load_command: None, threshold_pct: 90.0, sustain_secs: 60 }
+    }
+}
+
+struct HighLoadHookState {
+    config: Mutex<HighLoadHookConfig>,
+}
+
+// "hook:high_load" payload
+#[derive(Clone, serde::Serialize)]
+struct HighLoadHookRun {
+    load_pct: f64,
+    command: String,
+    code: Option<i32>,
+    stdout: String,
+    stderr: String,
+    timed_out: bool, // Killed after the command timeout setting
+}
+
+#[tauri::command]
+fn get_high_load_hook(app: AppHandle) -> Result<HighLoadHookConfig, String> {
+    let state = app
+        .try_state::<Arc<HighLoadHookState>>()
+        .ok_or("High load hook state not found")?;
+    let config = state.config.lock().map_err(|e| e.to_string())?.clone();
+    Ok(config)
+}
+
+#[tauri::command]
+fn set_high_load_hook(
+    app: AppHandle,
+    on_high_load_command: Option<String>,
+    threshold_pct: f64,
+    sustain_secs: u64,
+) -> Result<(), String> {
+    if !(threshold_pct > 0.0 && threshold_pct <= 100.0) {
+        return Err(format!("Threshold must be between 0 and 100, got {}", threshold_pct));
+    }
+    let state = app
+        .try_state::<Arc<HighLoadHookState>>()
+        .ok_or("High load hook state not found")?;
+    let on_high_load_command = on_high_load_command.map(|c| c.trim().to_string()).filter(|c| !c.is_empty());
+    let config = HighLoadHookConfig { on_high_load_command, threshold_pct, sustain_secs };
+    let value = serde_json::to_value(&config).map_err(|e| e.to_string())?;
+    *state.config.lock().map_err(|e| e.to_string())? = config;
+    save_setting(&app, SETTINGS_HIGH_LOAD_HOOK, value);
+    Ok(())
+}
+
+// sh -c with the load as $1, or cmd /C with the load appended as the last argument.
+// Bounded by the command timeout setting and killed with its children on quit.
+fn run_high_load_hook(app: &AppHandle, command: &str, load_pct: f64) -> HighLoadHookRun {
+    let load = format!("{:.1}", load_pct);
+    let mut shell = std::process::Command::new(if cfg!(windows) { "cmd" } else { "sh" });
+    if cfg!(windows) {
+        shell.args(["/C", command, &load]);
+    } else {
+        shell.args(["-c", command, "sh", &load]);
+    }
+    let output = run_hook_command(&shutdown_signal(app), &mut shell, get_command_timeout(app.clone()));
+    let output = output.unwrap_or_else(|e| HookOutput { code: None, stdout: String::new(), stderr: e.to_string(), timed_out: false });
+    HighLoadHookRun {
+        load_pct,
+        command: command.to_string(),
+        code: output.code,
+        stdout: output.stdout,
+        stderr: output.stderr,
+        timed_out: output.timed_out,
+    }
+}
+
+fn spawn_high_load_monitor(app: AppHandle) {
+    let shutdown = shutdown_signal(&app);
+    std::thread::spawn(move || {
+        let mut sys = sysinfo::System::new();
+        sys.refresh_cpu_usage();
+        let mut above_since: Option<Instant> = None;
+        let mut fired = false;
+
+        loop {
+            if !shutdown.sampler_sleep(Duration::from_secs(HIGH_LOAD_INTERVAL_SECS)) {
+                return;
+            }
+            // Usage since the previous refresh, i.e. averaged over the interval
+            sys.refresh_cpu_usage();
+            let load_pct = sys.global_cpu_usage() as f64;
+
+            let config = match app.try_state::<Arc<HighLoadHookState>>() {
+                Some(state) => match state.config.lock() {
+                    Ok(config) => config.clone(),
+                    Err(e) => {
+                        log_monitor_error("high load monitor", e);
+                        continue;
+                    }
+                },
+                None => continue,
+            };
+            let Some(command) = config.on_high_load_command else {
+                above_since = None;
+                fired = false;
+                continue;
+            };
+
+            if load_pct < config.threshold_pct {
+                // Back under the threshold: re-arm for the next crossing
+                above_since = None;
+                fired = false;
+                continue;
+            }
+
+            let since = *above_since.get_or_insert_with(Instant::now);
+            if fired || since.elapsed() < Duration::from_secs(config.sustain_secs) {
+                continue;
+            }
+            fired = true;
+
+            let run = run_high_load_hook(&app, &command, load_pct);
+            emit_or_log(&app, "high load monitor", "hook:high_load", run);
+        }
+    });
+}
+
+// Linux: errno from the sched_* calls, with a vanished PID reported as NotFound
+#[cfg(target_os = "linux")]
+fn affinity_error(pid: u32) -> OrganizerError {
+    let err = std::io::Error::last_os_error();
+    if err.raw_os_error() == Some(libc::ESRCH) {
+        OrganizerError::NotFound(format!("Process {}", pid))
+    } else {
+        err.into()
+    }
+}
+
+// Linux: logical CPUs the process may run on
+#[cfg(target_os = "linux")]
+#[tauri::command]
+fn get_cpu_affinity(pid: u32) -> Result<Vec<usize>, OrganizerError> {
+    let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+    if unsafe { libc::sched_getaffinity(pid as libc::pid_t, std::mem::size_of::<libc::cpu_set_t>(), &mut set) } != 0 {
+        return Err(affinity_error(pid));
+    }
+    Ok((0..libc::CPU_SETSIZE as usize)
+        .filter(|cpu| unsafe { libc::CPU_ISSET(*cpu, &set) })
+        .collect())
+}
+
+// Linux: pin a process to the given logical CPUs (other users' processes need CAP_SYS_NICE)
+#[cfg(target_os = "linux")]
+#[tauri::command]
+fn set_cpu_affinity(pid: u32, cpus: Vec<usize>) -> Result<(), OrganizerError> {
+    let cpu_count = unsafe { libc::sysconf(libc::_SC_NPROCESSORS_CONF) }.max(1) as usize;
+    if cpus.is_empty() {
+        return Err(OrganizerError::InvalidInput("At least one CPU is required".into()));
+    }
+    if let Some(cpu) = cpus.iter().find(|cpu| **cpu >= cpu_count) {
+        return Err(OrganizerError::InvalidInput(format!("CPU {} out of range (0-{})", cpu, cpu_count - 1)));
+    }
+
+    let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+    for cpu in &cpus {
+        unsafe { libc::CPU_SET(*cpu, &mut set) };
+    }
+    if unsafe { libc::sched_setaffinity(pid as libc::pid_t, std::mem::size_of::<libc::cpu_set_t>(), &set) } != 0 {
+        return Err(affinity_error(pid));
+    }
+    Ok(())
+}
+
+// macOS only has affinity hints (thread_policy_set tags), Windows isn't wired up yet
+#[cfg(not(target_os = "linux"))]
+#[tauri::command]
+fn get_cpu_affinity(_pid: u32) -> Result<Vec<usize>, OrganizerError> {
+    Err(OrganizerError::Unsupported("CPU affinity is only available on Linux".into()))
+}
+
+#[cfg(not(target_os = "linux"))]
+#[tauri::command]
+fn set_cpu_affinity(_pid: u32, _cpus: Vec<usize>) -> Result<(), OrganizerError> {
+    Err(OrganizerError::Unsupported("CPU affinity is only available on Linux".into()))
+}
+
+// Disk alert: notify when a volume's used percentage crosses its threshold
+const DISK_ALERT_INTERVAL_SECS: u64 = 60;
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+struct DiskAlertConfig {
+    enabled: bool,
+    default_pct: f64,                // Used percentage, 0-100
+    overrides: HashMap<String, f64>, // Mount point -> used percentage
+}
+
+impl Default for DiskAlertConfig {
+    fn default() -> Self {
+        DiskAlertConfig { enabled: true, default_pct: 90.0, overrides: HashMap::new() }
+    }
+}
+
+impl DiskAlertConfig {
+    fn threshold_for(&self, mount_point: &str) -> f64 {
+        self.overrides.get(mount_point).copied().unwrap_or(self.default_pct)
+    }
+}
+
+struct DiskAlertState {
+    config: Mutex<DiskAlertConfig>,
+}
+
+#[derive(Clone, serde::Serialize)]
+struct DiskAlert {
+    name: String,
+    mount_point: String,
+    used_pct: f64,
+    threshold_pct: f64,
+    available_gb: f64,
+}
+
+fn validate_disk_pct(pct: f64) -> Result<(), String> {
+    if !(pct > 0.0 && pct <= 100.0) {
+        return Err(format!("Percentage must be between 0 and 100, got {}", pct));
+    }
+    Ok(())
+}
+
+// Persist the in-memory config after an update
+fn update_disk_alert(app: &AppHandle, update: impl FnOnce(&mut DiskAlertConfig)) -> Result<(), String> {
+    let state = app
+        .try_state::<Arc<DiskAlertState>>()
+        .ok_or("Disk alert state not found")?;
+    let value = {
+        let mut config = state.config.lock().map_err(|e| e.to_string())?;
+        update(&mut config);
+        serde_json::to_value(&*config).map_err(|e| e.to_string())?
+    };
+    save_setting(app, SETTINGS_DISK_ALERT, value);
+    Ok(())
+}
+
+#[tauri::command]
+fn get_disk_alert(app: AppHandle) -> Result<DiskAlertConfig, String> {
+    let state = app
+        .try_state::<Arc<DiskAlertState>>()
+        .ok_or("Disk alert state not found")?;
+    let config = state.config.lock().map_err(|e| e.to_string())?.clone();
+    Ok(config)
+}
+
+#[tauri::command]
+fn set_disk_alert(app: AppHandle, enabled: bool, default_pct: f64) -> Result<(), String> {
+    validate_disk_pct(default_pct)?;
+    update_disk_alert(&app, |config| {
+        config.enabled = enabled;
+        config.default_pct = default_pct;
+    })
+}
+
+// Per-volume override of the default threshold
+#[tauri::command]
+fn set_disk_alert_threshold(app: AppHandle, mount: String, pct: f64) -> Result<(), String> {
+    validate_disk_pct(pct)?;
+    update_disk_alert(&app, |config| {
+        config.overrides.insert(mount, pct);
+    })
+}
+
+#[tauri::command]
+fn remove_disk_alert_threshold(app: AppHandle, mount: String) -> Result<(), String> {
+    update_disk_alert(&app, |config| {
+        config.overrides.remove(&mount);
+    })
+}
+
+fn spawn_disk_monitor(app: AppHandle) {
+    let shutdown = shutdown_signal(&app);
+    std::thread::spawn(move || {
+        // Volumes currently above their threshold: alert once per crossing
+        let mut alerted: std::collections::HashSet<String> = std::collections::HashSet::new();
+
+        loop {
+            if !shutdown.sampler_sleep(Duration::from_secs(DISK_ALERT_INTERVAL_SECS)) {
+                return;
+            }
+
+            let config = match app.try_state::<Arc<DiskAlertState>>() {
+                Some(state) => match state.config.lock() {
+                    Ok(c) => c.clone(),
+                    Err(e) => {
+                        log_monitor_error("disk monitor", e);
+                        continue;
+                    }
+                },
+                None => continue,
+            };
+            if !config.enabled {
+                alerted.clear();
+                continue;
+            }
+
+            let disks = get_all_disks();
+            // Forget unmounted volumes so they alert again when remounted full
+            alerted.retain(|mount| disks.iter().any(|d| &d.mount_point == mount));
+
+            for disk in disks.iter().filter(|d| d.total_gb > 0.0) {
+                let used_pct = disk.used_pct;
+                let threshold_pct = config.threshold_for(&disk.mount_point);
+
+                if used_pct < threshold_pct {
+                    alerted.remove(&disk.mount_point);
+                    continue;
+                }
+                if !alerted.insert(disk.mount_point.clone()) {
+                    continue;
+                }
+
+                emit_or_log(&app, "disk monitor", "disk:alert", DiskAlert {
+                    name: disk.name.clone(),
+                    mount_point: disk.mount_point.clone(),
+                    used_pct,
+                    threshold_pct,
+                    available_gb: disk.available_gb,
+                });
+                notify(
+                    &app,
+                    &tr(&app, "notify.disk_full.title", &[]),
+                    &tr(&app, "notify.disk_full.body", &[
+                        ("mount_point", &disk.mount_point),
+                        ("pct", &format!("{:.0}", used_pct)),
+                        ("available_gb", &format!("{:.1}", disk.available_gb)),
+                    ]),
+                );
+                raise_alert(&app);
+            }
+        }
+    });
+}
+
+#[derive(Clone, serde::Serialize)]
+struct ThermalState {
+    level: String,                    // nominal | fair | serious | critical
+    throttled: bool,
+    cpu_speed_limit_pct: Option<u32>, // From pmset, Intel Macs only
+}
+
+const THERMAL_MONITOR_INTERVAL_SECS: u64 = 30;
+
+#[cfg(target_os = "macos")]
+#[link(name = "objc")]
+extern "C" {
+    fn objc_getClass(name: *const std::ffi::c_char) -> *mut std::ffi::c_void;
+    fn sel_registerName(name: *const std::ffi::c_char) -> *mut std::ffi::c_void;
+    fn objc_msgSend();
+    fn objc_autoreleasePoolPush() -> *mut std::ffi::c_void;
+    fn objc_autoreleasePoolPop(pool: *mut std::ffi::c_void);
+}
+
+#[cfg(target_os = "macos")]
+#[link(name = "Foundation", kind = "framework")]
+extern "C" {}
+
+#[cfg(target_os = "macos")]
+#[link(name = "AppKit", kind = "framework")]
+extern "C" {}
+
+// Minimal Objective-C messaging for argument-less getters, enough to read a few Cocoa properties
+// without pulling in an objc crate. Callers wrap them in with_autorelease_pool.
+#[cfg(target_os = "macos")]
+unsafe fn objc_send_object(receiver: *mut std::ffi::c_void, selector: &std::ffi::CStr) -> *mut std::ffi::c_void {
+    type Send = unsafe extern "C" fn(*mut std::ffi::c_void, *mut std::ffi::c_void) -> *mut std::ffi::c_void;
+    if receiver.is_null() {
+        return receiver;
+    }
+    let send: Send = std::mem::transmute(objc_msgSend as unsafe extern "C" fn());
+    send(receiver, sel_registerName(selector.as_ptr()))
+}
+
+#[cfg(target_os = "macos")]
+unsafe fn objc_send_integer(receiver: *mut std::ffi::c_void, selector: &std::ffi::CStr) -> Option<isize> {
+    type Send = unsafe extern "C" fn(*mut std::ffi::c_void, *mut std::ffi::c_void) -> isize;
+    if receiver.is_null() {
+        return None;
+    }
+    let send: Send = std::mem::transmute(objc_msgSend as unsafe extern "C" fn());
+    Some(send(receiver, sel_registerName(selector.as_ptr())))
+}
+
+// NSString -> String via UTF8String
+#[cfg(target_os = "macos")]
+unsafe fn nsstring_to_string(string: *mut std::ffi::c_void) -> Option<String> {
+    let utf8 = objc_send_object(string, c"UTF8String") as *const std::ffi::c_char;
+    (!utf8.is_null()).then(|| std::ffi::CStr::from_ptr(utf8).to_string_lossy().into_owned())
+}
+
+#[cfg(target_os = "macos")]
+fn with_autorelease_pool<T>(f: impl FnOnce() -> T) -> T {
+    unsafe {
+        let pool = objc_autoreleasePoolPush();
+        let result = f();
+        objc_autoreleasePoolPop(pool);
+        result
+    }
+}
+
+// [[NSProcessInfo processInfo] thermalState]: 0 nominal, 1 fair, 2 serious, 3 critical
+#[cfg(target_os = "macos")]
+fn ns_thermal_state() -> Option<isize> {
+    with_autorelease_pool(|| unsafe {
+        let info = objc_send_object(objc_getClass(c"NSProcessInfo".as_ptr()), c"processInfo");
+        objc_send_integer(info, c"thermalState")
+    })
+}
+
+// "CPU_Speed_Limit = 80" while the CPU is being held back
+#[cfg(target_os = "macos")]
+fn pmset_cpu_speed_limit() -> Option<u32> {
+    let output = std::process::Command::new("pmset").args(["-g", "therm"]).output().ok()?;
+    String::from_utf8_lossy(&output.stdout).lines().find_map(|line| {
+        let (key, value) = line.split_once('=')?;
+        (key.trim() == "CPU_Speed_Limit").then(|| value.trim().parse().ok())?
+    })
+}
+
+#[cfg(target_os = "macos")]
+fn thermal_state() -> Result<ThermalState, OrganizerError> {
+    let cpu_speed_limit_pct = pmset_cpu_speed_limit();
+    let level = match ns_thermal_state() {
+        Some(0) => "nominal",
+        Some(1) => "fair",
+        Some(2) => "serious",
+        Some(3) => "critical",
+        // No Foundation answer: infer from the speed limit alone
+        _ => match cpu_speed_limit_pct {
+            Some(limit) if limit < 50 => "critical",
+            Some(limit) if limit < 100 => "serious",
+            Some(_) => "nominal",
+            None => return Err(OrganizerError::NotFound("Thermal state".into())),
+        },
+    };
+    Ok(ThermalState {
+        level: level.to_string(),
+        throttled: matches!(level, "serious" | "critical") || cpu_speed_limit_pct.is_some_and(|l| l < 100),
+        cpu_speed_limit_pct,
+    })
+}
+
+#[cfg(not(target_os = "macos"))]
+fn thermal_state() -> Result<ThermalState, OrganizerError> {
+    Err(OrganizerError::Unsupported("Thermal state is only available on macOS".into()))
+}
+
+#[tauri::command]
+fn get_thermal_state() -> Result<ThermalState, OrganizerError> {
+    thermal_state()
+}
+
+#[derive(serde::Serialize)]
+struct IndexingStatus {
+    active: bool,       // Spotlight is using noticeable CPU right now
+    worker_count: u32,  // mdworker / mdworker_shared instances
+    total_cpu_pct: f32, // Per core, summed over every Spotlight process
+    total_mem_mb: f64,
+}
+
+// mds coordinates, mds_stores writes the index, mdworker* import file contents
+#[cfg(target_os = "macos")]
+const SPOTLIGHT_PROCESSES: &[&str] = &["mds", "mds_stores", "mdworker", "mdworker_shared"];
+// Idle workers linger for a while: only real CPU use counts as indexing
+#[cfg(target_os = "macos")]
+const INDEXING_ACTIVE_CPU_PCT: f32 = 5.0;
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+async fn get_indexing_status(app: AppHandle) -> Result<IndexingStatus, OrganizerError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        with_shared_processes(&app, DetailLevel::Full, |sys| {
+            let mut status = IndexingStatus { active: false, worker_count: 0, total_cpu_pct: 0.0, total_mem_mb: 0.0 };
+            for (pid, process) in sys.processes() {
+                let name = process.name().to_string_lossy();
+                if !SPOTLIGHT_PROCESSES.contains(&name.as_ref()) {
+                    continue;
+                }
+                if name.starts_with("mdworker") {
+                    status.worker_count += 1;
+                }
+                status.total_cpu_pct += process.cpu_usage();
+                status.total_mem_mb += process_footprint_bytes(pid.as_u32(), process) as f64 / 1_048_576.0;
+            }
+            status.active = status.total_cpu_pct >= INDEXING_ACTIVE_CPU_PCT;
+            status
+        })
+    })
+    .await
+    .map_err(OrganizerError::from)
+}
+
+#[cfg(not(target_os = "macos"))]
+#[tauri::command]
+async fn get_indexing_status() -> Result<IndexingStatus, OrganizerError> {
+    Err(OrganizerError::Unsupported("Spotlight indexing is only available on macOS".into()))
+}
+
+// mdutil only changes indexing as root; run unprivileged it fails with a generic error
+#[cfg(target_os = "macos")]
+fn set_spotlight_indexing(on: bool) -> Result<(), OrganizerError> {
+    let flag = if on { "on" } else { "off" };
+    run_maintenance_command("mdutil", &["-a", "-i", flag]).map_err(|e| match e {
+        OrganizerError::Io(msg) if msg.contains("root") || msg.contains("-400") || msg.to_lowercase().contains("not permitted") => {
+            OrganizerError::PermissionDenied(format!("mdutil -a -i {} must run as root (EPERM)", flag))
+        }
+        other => other,
+    })
+}
+
+#[cfg(not(target_os = "macos"))]
+fn set_spotlight_indexing(_on: bool) -> Result<(), OrganizerError> {
+    Err(OrganizerError::Unsupported("Spotlight indexing is only available on macOS".into()))
+}
+
+// Stop indexing every volume (e.g. while mdworker hogs a freshly booted Mac)
+#[tauri::command]
+fn pause_spotlight() -> Result<(), OrganizerError> {
+    set_spotlight_indexing(false)
+}
+
+#[tauri::command]
+fn resume_spotlight() -> Result<(), OrganizerError> {
+    set_spotlight_indexing(true)
+}
+
+// Alert once each time the level reaches serious/critical; exits right away where unsupported
+fn spawn_thermal_monitor(app: AppHandle) {
+    let shutdown = shutdown_signal(&app);
+    std::thread::spawn(move || {
+        let mut alerted = false;
+
+        loop {
+            let state = match thermal_state() {
+                Ok(state) => state,
+                Err(OrganizerError::Unsupported(_)) => return,
+                Err(e) => {
+                    log_monitor_error("thermal monitor", e);
+                    if !shutdown.sampler_sleep(Duration::from_secs(THERMAL_MONITOR_INTERVAL_SECS)) {
+                        return;
+                    }
+                    continue;
+                }
+            };
+
+            let hot = matches!(state.level.as_str(), "serious" | "critical");
+            if !hot {
+                alerted = false;
+            } else if !alerted {
+                alerted = true;
+                emit_or_log(&app, "thermal monitor", "thermal:alert", state.clone());
+                notify(
+                    &app,
+                    &tr(&app, "notify.thermal.title", &[]),
+                    &tr(&app, "notify.thermal.body", &[("level", &state.level)]),
+                );
+                raise_alert(&app);
+            }
+
+            if !shutdown.sampler_sleep(Duration::from_secs(THERMAL_MONITOR_INTERVAL_SECS)) {
+                return;
+            }
+        }
+    });
+}
+
+#[derive(Clone, serde::Serialize)]
+struct BatteryStatus {
+    percent: u8,
+    plugged_in: bool, // On AC power (charging, or full and holding)
+    charging: bool,
+}
+
+// macOS: `pmset -g batt`, e.g. "Now drawing from 'AC Power'" then
+// " -InternalBattery-0 (id=1234)\t85%; charging; 1:05 remaining present: true"
+#[cfg(target_os = "macos")]
+fn battery_status() -> Result<Option<BatteryStatus>, OrganizerError> {
+    let out = command_stdout("pmset", &["-g", "batt"]).ok_or_else(|| OrganizerError::Io("pmset -g batt failed".into()))?;
+    let Some(line) = out.lines().find(|l| l.contains("InternalBattery")) else { return Ok(None) };
+    let parse_error = || OrganizerError::Parse(format!("pmset battery line: {}", line));
+    let (_, rest) = line.split_once('\t').ok_or_else(parse_error)?;
+    let mut fields = rest.split(';').map(str::trim);
+    let percent = fields
+        .next()
+        .and_then(|pct| pct.trim_end_matches('%').parse().ok())
+        .ok_or_else(parse_error)?;
+    let state = fields.next().unwrap_or("");
+    Ok(Some(BatteryStatus {
+        percent,
+        plugged_in: out.contains("'AC Power'"),
+        charging: state == "charging",
+    }))
+}
+
+// Linux: the first BAT* entry of /sys/class/power_supply
+#[cfg(target_os = "linux")]
+fn battery_status() -> Result<Option<BatteryStatus>, OrganizerError> {
+    // No power_supply class (containers, some VMs) means no battery either
+    let Ok(entries) = std::fs::read_dir("/sys/class/power_supply") else { return Ok(None) };
+    let battery = entries
+        .flatten()
+        .map(|e| e.path())
+        .find(|p| p.file_name().is_some_and(|n| n.to_string_lossy().starts_with("BAT")));
+    let Some(battery) = battery else { return Ok(None) };
+    let read = |name: &str| std::fs::read_to_string(battery.join(name)).map(|v| v.trim().to_string());
+    let capacity = read("capacity")?;
+    let percent = capacity
+        .parse()
+        .map_err(|_| OrganizerError::Parse(format!("{}/capacity: {}", battery.display(), capacity)))?;
+    let status = read("status").unwrap_or_default();
+    Ok(Some(BatteryStatus {
+        percent,
+        plugged_in: status != "Discharging",
+        charging: status == "Charging",
+    }))
+}
+
+// Windows: GetSystemPowerStatus, BatteryFlag 128 means no system battery
+#[cfg(target_os = "windows")]
+fn battery_status() -> Result<Option<BatteryStatus>, OrganizerError> {
+    use windows_sys::Win32::System::Power::{GetSystemPowerStatus, SYSTEM_POWER_STATUS};
+
+    let mut status: SYSTEM_POWER_STATUS = unsafe { std::mem::zeroed() };
+    if unsafe { GetSystemPowerStatus(&mut status) } == 0 {
+        return Err(std::io::Error::last_os_error().into());
+    }
+    // 255 = unknown charge
+    if status.BatteryFlag & 128 != 0 || status.BatteryLifePercent > 100 {
+        return Ok(None);
+    }
+    Ok(Some(BatteryStatus {
+        percent: status.BatteryLifePercent,
+        plugged_in: status.ACLineStatus == 1,
+        charging: status.BatteryFlag & 8 != 0,
+    }))
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+fn battery_status() -> Result<Option<BatteryStatus>, OrganizerError> {
+    Ok(None)
+}
+
+// None on machines without a battery
+#[tauri::command]
+async fn get_battery_status() -> Result<Option<BatteryStatus>, OrganizerError> {
+    tauri::async_runtime::spawn_blocking(battery_status).await?
+}
+
+// Whether the user silenced notifications. macOS Focus isn't readable without private APIs,
+// but Notification Center already holds banners back while it is on.
+#[cfg(target_os = "windows")]
+fn do_not_disturb() -> bool {
+    use windows_sys::Win32::UI::Shell::{SHQueryUserNotificationState, QUNS_ACCEPTS_NOTIFICATIONS, QUNS_NOT_PRESENT};
+
+    let mut state = 0;
+    let result = unsafe { SHQueryUserNotificationState(&mut state) };
+    result == 0
+        && state != QUNS_ACCEPTS_NOTIFICATIONS
+        && state != QUNS_NOT_PRESENT
+}
+
+// GNOME's Do Not Disturb switch turns banners off
+#[cfg(target_os = "linux")]
+fn do_not_disturb() -> bool {
+    command_stdout("gsettings", &["get", "org.gnome.desktop.notifications", "show-banners"])
+        .is_some_and(|out| out.trim() == "false")
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+fn do_not_disturb() -> bool {
+    false
+}
+
+// Battery alerts: low charge while unplugged, full charge while plugged in (time to unplug)
+const BATTERY_ALERT_INTERVAL_SECS: u64 = 60;
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+struct BatteryAlertConfig {
+    enabled: bool,
+    battery_low_pct: u8,
+    battery_full_pct: u8,
+}
+
+impl Default for BatteryAlertConfig {
+    fn default() -> Self {
+        BatteryAlertConfig { enabled: false, battery_low_pct: 20, battery_full_pct: 100 }
+    }
+}
+
+struct BatteryAlertState {
+    config: Mutex<BatteryAlertConfig>,
+}
+
+#[derive(Clone, serde::Serialize)]
+struct BatteryAlert {
+    kind: &'static str, // "low" or "full"
+    percent: u8,
+    threshold_pct: u8,
+}
+
+#[tauri::command]
+fn get_battery_alerts(app: AppHandle) -> Result<BatteryAlertConfig, String> {
+    let state = app
+        .try_state::<Arc<BatteryAlertState>>()
+        .ok_or("Battery alert state not found")?;
+    let config = state.config.lock().map_err(|e| e.to_string())?.clone();
+    Ok(config)
+}
+
+#[tauri::command]
+fn set_battery_alerts(app: AppHandle, low: u8, high: u8, enabled: bool) -> Result<(), String> {
+    if high > 100 || low >= high {
+        return Err(format!("Thresholds must satisfy low < high <= 100, got {} and {}", low, high));
+    }
+    let state = app
+        .try_state::<Arc<BatteryAlertState>>()
+        .ok_or("Battery alert state not found")?;
+    let config = BatteryAlertConfig { enabled, battery_low_pct: low, battery_full_pct: high };
+    let value = serde_json::to_value(&config).map_err(|e| e.to_string())?;
+    *state.config.lock().map_err(|e| e.to_string())? = config;
+    save_setting(&app, SETTINGS_BATTERY_ALERTS, value);
+    Ok(())
+}
+
+// Once per crossing: a level re-arms after going back over the low threshold (or plugging in),
+// or under the full one (or unplugging). The badge is raised even when DND holds the banner.
+fn spawn_battery_monitor(app: AppHandle) {
+    let shutdown = shutdown_signal(&app);
+    std::thread::spawn(move || {
+        let mut low_alerted = false;
+        let mut full_alerted = false;
+
+        loop {
+            if !shutdown.sampler_sleep(Duration::from_secs(BATTERY_ALERT_INTERVAL_SECS)) {
+                return;
+            }
+
+            let config = match app.try_state::<Arc<BatteryAlertState>>() {
+                Some(state) => match state.config.lock() {
+                    Ok(config) => config.clone(),
+                    Err(e) => {
+                        log_monitor_error("battery monitor", e);
+                        continue;
+                    }
+                },
+                None => continue,
+            };
+            let status = match config.enabled.then(battery_status) {
+                Some(Ok(status)) => status,
+                Some(Err(e)) => {
+                    log_monitor_error("battery monitor", e);
+                    None
+                }
+                None => None,
+            };
+            let Some(status) = status else {
+                low_alerted = false;
+                full_alerted = false;
+                continue;
+            };
+
+            let low = !status.plugged_in && status.percent <= config.battery_low_pct;
+            let full = status.plugged_in && status.percent >= config.battery_full_pct;
+            let alert = match (low, full) {
+                (true, _) if !low_alerted => Some(("low", config.battery_low_pct)),
+                (_, true) if !full_alerted => Some(("full", config.battery_full_pct)),
+                _ => None,
+            };
+            low_alerted = low;
+            full_alerted = full;
+            let Some((kind, threshold_pct)) = alert else { continue };
+
+            emit_or_log(&app, "battery monitor", "battery:alert", BatteryAlert { kind, percent: status.percent, threshold_pct });
+            if !do_not_disturb() {
+                let pct = status.percent.to_string();
+                notify(
+                    &app,
+                    &tr(&app, &format!("notify.battery_{}.title", kind), &[]),
+                    &tr(&app, &format!("notify.battery_{}.body", kind), &[("pct", &pct)]),
+                );
+            }
+            raise_alert(&app);
+        }
+    });
+}
+
+#[derive(Clone, serde::Serialize)]
+struct ActiveApp {
+    name: String,
+    pid: u32,
+    bundle_id: Option<String>, // macOS only
+}
+
+// [[NSWorkspace sharedWorkspace] frontmostApplication]
+#[cfg(target_os = "macos")]
+fn active_app() -> Option<ActiveApp> {
+    with_autorelease_pool(|| unsafe {
+        let workspace = objc_send_object(objc_getClass(c"NSWorkspace".as_ptr()), c"sharedWorkspace");
+        let app = objc_send_object(workspace, c"frontmostApplication");
+        let pid = objc_send_integer(app, c"processIdentifier")?;
+        // processIdentifier is a pid_t: only the low 32 bits of the register are meaningful
+        let pid = pid as i32;
+        if pid <= 0 {
+            return None;
+        }
+        Some(ActiveApp {
+            name: nsstring_to_string(objc_send_object(app, c"localizedName"))
+                .or_else(|| name_by_pid(&process_names_system(sysinfo::ProcessesToUpdate::All), pid as u32))
+                .unwrap_or_default(),
+            pid: pid as u32,
+            bundle_id: nsstring_to_string(objc_send_object(app, c"bundleIdentifier")),
+        })
+    })
+}
+
+#[cfg(target_os = "windows")]
+fn active_app() -> Option<ActiveApp> {
+    use windows_sys::Win32::UI::WindowsAndMessaging::{GetForegroundWindow, GetWindowThreadProcessId};
+
+    let window = unsafe { GetForegroundWindow() };
+    if window.is_null() {
+        return None;
+    }
+    let mut pid = 0;
+    unsafe { GetWindowThreadProcessId(window, &mut pid) };
+    if pid == 0 {
+        return None;
+    }
+    let sysinfo_pid = sysinfo::Pid::from_u32(pid);
+    Some(ActiveApp {
+        name: name_by_pid(&process_names_system(sysinfo::ProcessesToUpdate::Some(&[sysinfo_pid])), pid)?,
+        pid,
+        bundle_id: None,
+    })
+}
+
+// Linux: X11 only, via xdotool or else xprop reading _NET_ACTIVE_WINDOW / _NET_WM_PID
+#[cfg(target_os = "linux")]
+fn active_app() -> Option<ActiveApp> {
+    let xdotool = command_stdout("xdotool", &["getactivewindow", "getwindowpid"])
+        .and_then(|out| out.trim().parse::<u32>().ok());
+    let pid = xdotool.or_else(|| {
+        // "_NET_ACTIVE_WINDOW(WINDOW): window id # 0x3a00007"
+        let root = command_stdout("xprop", &["-root", "_NET_ACTIVE_WINDOW"])?;
+        let window = root.split_whitespace().last()?.to_string();
+        // "_NET_WM_PID(CARDINAL) = 12345"
+        let props = command_stdout("xprop", &["-id", &window, "_NET_WM_PID"])?;
+        props.split('=').nth(1)?.trim().parse().ok()
+    })?;
+
+    let sysinfo_pid = sysinfo::Pid::from_u32(pid);
+    Some(ActiveApp {
+        name: name_by_pid(&process_names_system(sysinfo::ProcessesToUpdate::Some(&[sysinfo_pid])), pid)?,
+        pid,
+        bundle_id: None,
+    })
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+fn active_app() -> Option<ActiveApp> {
+    None
+}
+
+// Focused application, None when it can't be determined (e.g. Wayland without X tools)
+#[tauri::command]
+async fn get_active_app() -> Result<Option<ActiveApp>, OrganizerError> {
+    Ok(tauri::async_runtime::spawn_blocking(active_app).await?)
+}
+
+#[derive(serde::Serialize)]
+struct AppInfo {
+    display_name: String,
+    bundle_id: Option<String>,
+    version: Option<String>,
+    icon_path: Option<String>, // .icns inside the bundle's Resources
+}
+
+// Outermost .app above the executable: helpers nested in Contents/Frameworks
+// (e.g. "Code Helper.app") resolve to the app the user launched
+#[cfg(target_os = "macos")]
+fn app_bundle_of(exe: &std::path::Path) -> Option<std::path::PathBuf> {
+    exe.ancestors()
+        .filter(|dir| dir.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("app")))
+        .last()
+        .map(std::path::Path::to_path_buf)
+}
+
+// PlistBuddy reads both XML and binary Info.plist files
+#[cfg(target_os = "macos")]
+fn plist_string(plist: &std::path::Path, key: &str) -> Option<String> {
+    let plist = plist.to_str()?;
+    command_stdout("/usr/libexec/PlistBuddy", &["-c", &format!("Print :{}", key), plist])
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+#[cfg(target_os = "macos")]
+fn app_info(pid: u32) -> Option<AppInfo> {
+    use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind};
+
+    let sysinfo_pid = Pid::from_u32(pid);
+    let mut sys = System::new();
+    sys.refresh_processes_specifics(
+        ProcessesToUpdate::Some(&[sysinfo_pid]),
+        true,
+        ProcessRefreshKind::new().with_exe(UpdateKind::Always),
+    );
+    let bundle = app_bundle_of(sys.process(sysinfo_pid)?.exe()?)?;
+    let plist = bundle.join("Contents/Info.plist");
+    if !plist.is_file() {
+        return None;
+    }
+
+    let display_name = plist_string(&plist, "CFBundleName")
+        .or_else(|| plist_string(&plist, "CFBundleDisplayName"))
+        .or_else(|| bundle.file_stem().map(|stem| stem.to_string_lossy().to_string()))?;
+    // CFBundleIconFile may omit the extension
+    let icon_path = plist_string(&plist, "CFBundleIconFile").and_then(|icon| {
+        let mut path = bundle.join("Contents/Resources").join(icon);
+        if path.extension().is_none() {
+            path.set_extension("icns");
+        }
+        path.is_file().then(|| path.to_string_lossy().to_string())
+    });
+    Some(AppInfo {
+        display_name,
+        bundle_id: plist_string(&plist, "CFBundleIdentifier"),
+        version: plist_string(&plist, "CFBundleShortVersionString"),
+        icon_path,
+    })
+}
+
+// Only macOS has app bundles
+#[cfg(not(target_os = "macos"))]
+fn app_info(_pid: u32) -> Option<AppInfo> {
+    None
+}
+
+// Real app behind a process (e.g. "Visual Studio Code" rather than "Electron"),
+// None for processes that don't live in a .app bundle
+#[tauri::command]
+async fn get_app_info(pid: u32) -> Result<Option<AppInfo>, OrganizerError> {
+    Ok(tauri::async_runtime::spawn_blocking(move || app_info(pid)).await?)
+}
+
+// PNG icons by bundle id (icon path when there is none), None when conversion failed
+#[derive(Default)]
+struct ProcessIconCache {
+    icons: Mutex<HashMap<String, Option<Vec<u8>>>>,
+}
+
+// Rows show icons at 32pt: 64px stays sharp on Retina
+#[cfg(target_os = "macos")]
+const PROCESS_ICON_SIZE_PX: u32 = 64;
+
+// sips renders the best matching .icns representation, downscaled to the row size
+#[cfg(target_os = "macos")]
+fn icns_to_png(icns: &str) -> Option<Vec<u8>> {
+    static COUNTER: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+    let out = std::env::temp_dir().join(format!(
+        "organizer-icon-{}-{}.png",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    let size = PROCESS_ICON_SIZE_PX.to_string();
+    let converted = command_stdout("sips", &["-s", "format", "png", "-Z", &size, icns, "--out", out.to_str()?]);
+    let png = converted.and_then(|_| std::fs::read(&out).ok());
+    let _ = std::fs::remove_file(&out);
+    png.filter(|bytes| !bytes.is_empty())
+}
+
+#[cfg(not(target_os = "macos"))]
+fn icns_to_png(_icns: &str) -> Option<Vec<u8>> {
+    None
+}
+
+// App icon of a process as PNG bytes (a Uint8Array-compatible number array in JS),
+// None for processes outside an app bundle or bundles without an icon
+#[tauri::command]
+async fn get_process_icon(app: AppHandle, pid: u32) -> Result<Option<Vec<u8>>, OrganizerError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let Some(info) = app_info(pid) else { return Ok(None) };
+        let Some(icon_path) = info.icon_path else { return Ok(None) };
+        let key = info.bundle_id.unwrap_or_else(|| icon_path.clone());
+
+        let cache = app
+            .try_state::<Arc<ProcessIconCache>>()
+            .ok_or_else(|| OrganizerError::NotFound("Process icon cache".into()))?;
+        if let Some(cached) = cache.icons.lock()?.get(&key) {
+            return Ok(cached.clone());
+        }
+        // Converted outside the lock: sips takes a few hundred ms
+        let png = icns_to_png(&icon_path);
+        cache.icons.lock()?.insert(key, png.clone());
+        Ok(png)
+    })
+    .await?
+}
+
+const FOCUS_WATCH_INTERVAL_MS: u64 = 200;
+const FOCUS_WATCH_MAX_SECS: u64 = 600;
+
+// "focus:change" payload
+#[derive(Clone, serde::Serialize)]
+struct FocusChange {
+    app: Option<ActiveApp>,
+    previous: Option<ActiveApp>,
+    elapsed_ms: u64, // Since the watch started
+}
+
+#[derive(serde::Serialize)]
+struct FocusAppStats {
+    name: String,
+    pid: u32,
+    bundle_id: Option<String>,
+    activations: u32, // Times it took focus (the app focused at the start counts once)
+    focused_ms: u64,
+}
+
+#[derive(serde::Serialize)]
+struct FocusWatchSummary {
+    duration_ms: u64,
+    changes: u32,
+    apps: Vec<FocusAppStats>, // Most activations first
+}
+
+// Diagnostic for focus stealing: samples the frontmost app every 200ms for duration_secs
+// (capped at 10 minutes), emitting "focus:change" on every switch
+#[tauri::command]
+async fn focus_watch(app: AppHandle, duration_secs: u64) -> Result<FocusWatchSummary, OrganizerError> {
+    let shutdown = shutdown_signal(&app);
+    tauri::async_runtime::spawn_blocking(move || {
+        let started = Instant::now();
+        let deadline = started + Duration::from_secs(duration_secs.min(FOCUS_WATCH_MAX_SECS));
+        let mut stats: Vec<FocusAppStats> = Vec::new();
+        let mut current: Option<ActiveApp> = None;
+        let mut current_since = started;
+        let mut changes = 0;
+
+        let mut first = true;
+        loop {
+            let now = Instant::now();
+            let sampled = active_app();
+            if first || sampled.as_ref().map(|a| a.pid) != current.as_ref().map(|a| a.pid) {
+                if let Some(previous) = &current {
+                    if let Some(entry) = stats.iter_mut().find(|s| s.pid == previous.pid) {
+                        entry.focused_ms += now.duration_since(current_since).as_millis() as u64;
+                    }
+                }
+                if let Some(focused) = &sampled {
+                    match stats.iter_mut().find(|s| s.pid == focused.pid) {
+                        Some(entry) => entry.activations += 1,
+                        None => stats.push(FocusAppStats {
+                            name: focused.name.clone(),
+                            pid: focused.pid,
+                            bundle_id: focused.bundle_id.clone(),
+                            activations: 1,
+                            focused_ms: 0,
+                        }),
+                    }
+                }
+                if !first {
+                    changes += 1;
+                    let _ = app.emit("focus:change", FocusChange {
+                        app: sampled.clone(),
+                        previous: current.clone(),
+                        elapsed_ms: now.duration_since(started).as_millis() as u64,
+                    });
+                }
+                current = sampled;
+                current_since = now;
+                first = false;
+            }
+            if now >= deadline || !shutdown.sleep(Duration::from_millis(FOCUS_WATCH_INTERVAL_MS)) {
+                break;
+            }
+        }
+
+        let ended = Instant::now();
+        if let Some(last) = &current {
+            if let Some(entry) = stats.iter_mut().find(|s| s.pid == last.pid) {
+                entry.focused_ms += ended.duration_since(current_since).as_millis() as u64;
+            }
+        }
+        stats.sort_by(|a, b| b.activations.cmp(&a.activations).then_with(|| b.focused_ms.cmp(&a.focused_ms)));
+        FocusWatchSummary {
+            duration_ms: ended.duration_since(started).as_millis() as u64,
+            changes,
+            apps: stats,
+        }
+    })
+    .await
+    .map_err(OrganizerError::from)
+}
+
+#[derive(serde::Serialize)]
+struct WindowBounds {
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+}
+
+#[derive(serde::Serialize)]
+struct WindowInfo {
+    window_id: u32,
+    owner_pid: u32,
+    owner_name: String,
+    title: Option<String>, // Only readable with the Screen Recording permission
+    bounds: WindowBounds,
+    layer: i32,            // 0 for normal app windows, higher for menu bar, dock, overlays
+}
+
+#[cfg(target_os = "macos")]
+#[repr(C)]
+struct CGRect {
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+}
+
+#[cfg(target_os = "macos")]
+#[link(name = "CoreGraphics", kind = "framework")]
+extern "C" {
+    fn CGWindowListCopyWindowInfo(option: u32, relative_to_window: u32) -> *const std::ffi::c_void;
+    fn CGRectMakeWithDictionaryRepresentation(dict: *const std::ffi::c_void, rect: *mut CGRect) -> bool;
+    static kCGWindowNumber: *const std::ffi::c_void;
+    static kCGWindowOwnerPID: *const std::ffi::c_void;
+    static kCGWindowOwnerName: *const std::ffi::c_void;
+    static kCGWindowName: *const std::ffi::c_void;
+    static kCGWindowBounds: *const std::ffi::c_void;
+    static kCGWindowLayer: *const std::ffi::c_void;
+}
+
+#[cfg(target_os = "macos")]
+#[link(name = "CoreFoundation", kind = "framework")]
+extern "C" {
+    fn CFArrayGetCount(array: *const std::ffi::c_void) -> isize;
+    fn CFArrayGetValueAtIndex(array: *const std::ffi::c_void, index: isize) -> *const std::ffi::c_void;
+    fn CFDictionaryGetValue(dict: *const std::ffi::c_void, key: *const std::ffi::c_void) -> *const std::ffi::c_void;
+    fn CFNumberGetValue(number: *const std::ffi::c_void, number_type: isize, value: *mut std::ffi::c_void) -> bool;
+    fn CFRelease(object: *const std::ffi::c_void);
+}
+
+#[cfg(target_os = "macos")]
+const CG_WINDOW_LIST_ON_SCREEN_ONLY: u32 = 1 << 0;
+#[cfg(target_os = "macos")]
+const CG_WINDOW_LIST_EXCLUDE_DESKTOP_ELEMENTS: u32 = 1 << 4;
+#[cfg(target_os = "macos")]
+const CF_NUMBER_SINT32_TYPE: isize = 3;
+
+#[cfg(target_os = "macos")]
+unsafe fn cf_dictionary_i32(dict: *const std::ffi::c_void, key: *const std::ffi::c_void) -> Option<i32> {
+    let number = CFDictionaryGetValue(dict, key);
+    let mut value: i32 = 0;
+    (!number.is_null() && CFNumberGetValue(number, CF_NUMBER_SINT32_TYPE, (&mut value as *mut i32).cast()))
+        .then_some(value)
+}
+
+// On-screen windows of every app, front to back. CFString values are read through
+// nsstring_to_string (toll-free bridged).
+#[cfg(target_os = "macos")]
+#[tauri::command]
+fn list_windows() -> Result<Vec<WindowInfo>, OrganizerError> {
+    with_autorelease_pool(|| unsafe {
+        let list = CGWindowListCopyWindowInfo(CG_WINDOW_LIST_ON_SCREEN_ONLY | CG_WINDOW_LIST_EXCLUDE_DESKTOP_ELEMENTS, 0);
+        if list.is_null() {
+            return Err(OrganizerError::Unsupported("Window list unavailable".into()));
+        }
+        let mut windows = Vec::new();
+        for i in 0..CFArrayGetCount(list) {
+            let info = CFArrayGetValueAtIndex(list, i);
+            let Some(owner_pid) = cf_dictionary_i32(info, kCGWindowOwnerPID) else { continue };
+            let mut rect = CGRect { x: 0.0, y: 0.0, width: 0.0, height: 0.0 };
+            let bounds = CFDictionaryGetValue(info, kCGWindowBounds);
+            if !bounds.is_null() {
+                CGRectMakeWithDictionaryRepresentation(bounds, &mut rect);
+            }
+            let string = |key| nsstring_to_string(CFDictionaryGetValue(info, key) as *mut std::ffi::c_void);
+            windows.push(WindowInfo {
+                window_id: cf_dictionary_i32(info, kCGWindowNumber).unwrap_or(0) as u32,
+                owner_pid: owner_pid as u32,
+                owner_name: string(kCGWindowOwnerName).unwrap_or_default(),
+                title: string(kCGWindowName).filter(|t| !t.is_empty()),
+                bounds: WindowBounds { x: rect.x, y: rect.y, width: rect.width, height: rect.height },
+                layer: cf_dictionary_i32(info, kCGWindowLayer).unwrap_or(0),
+            });
+        }
+        CFRelease(list);
+        Ok(windows)
+    })
+}
+
+#[cfg(not(target_os = "macos"))]
+#[tauri::command]
+fn list_windows() -> Result<Vec<WindowInfo>, OrganizerError> {
+    Err(OrganizerError::Unsupported("Window listing is macOS only for now".into()))
+}
+
+// Bring another app forward. An app without windows is still activated (its menu bar shows);
+// if activation is refused, open its bundle again, which also asks it to reopen a window.
+#[cfg(target_os = "macos")]
+#[tauri::command]
+fn activate_app(pid: u32) -> Result<(), OrganizerError> {
+    use std::ffi::c_void;
+    // NSApplicationActivateAllWindows | NSApplicationActivateIgnoringOtherApps
+    const ACTIVATE_OPTIONS: usize = 1 | 2;
+
+    let reopen_path = with_autorelease_pool(|| unsafe {
+        type WithPid = unsafe extern "C" fn(*mut c_void, *mut c_void, i32) -> *mut c_void;
+        type Activate = unsafe extern "C" fn(*mut c_void, *mut c_void, usize) -> i8; // BOOL
+        let with_pid: WithPid = std::mem::transmute(objc_msgSend as unsafe extern "C" fn());
+        let activate: Activate = std::mem::transmute(objc_msgSend as unsafe extern "C" fn());
+
+        let class = objc_getClass(c"NSRunningApplication".as_ptr());
+        let running = with_pid(class, sel_registerName(c"runningApplicationWithProcessIdentifier:".as_ptr()), pid as i32);
+        if running.is_null() {
+            return Err(OrganizerError::NotFound(format!("No running application with PID {}", pid)));
+        }
+        if activate(running, sel_registerName(c"activateWithOptions:".as_ptr()), ACTIVATE_OPTIONS) != 0 {
+            return Ok(None);
+        }
+        Ok(nsstring_to_string(objc_send_object(objc_send_object(running, c"bundleURL"), c"path")))
+    })?;
+
+    if let Some(path) = reopen_path {
+        let status = std::process::Command::new("open").arg(&path).status()?;
+        if !status.success() {
+            return Err(OrganizerError::Io(format!("open {} failed", path)));
+        }
+    }
+    Ok(())
+}
+
+// Windows: the PID's main window is its first visible, unowned top-level window, else its first
+// hidden one (shown again). A process without any window is allowed to take the foreground itself.
+#[cfg(target_os = "windows")]
+#[tauri::command]
+fn activate_app(pid: u32) -> Result<(), OrganizerError> {
+    use windows_sys::Win32::Foundation::{BOOL, HWND, LPARAM};
+    use windows_sys::Win32::UI::WindowsAndMessaging::{
+        AllowSetForegroundWindow, EnumWindows, GetWindow, GetWindowThreadProcessId, IsIconic, IsWindowVisible,
+        SetForegroundWindow, ShowWindow, GW_OWNER, SW_RESTORE, SW_SHOW,
+    };
+
+    struct Search {
+        pid: u32,
+        found: HWND,
+        hidden: HWND,
+    }
+
+    unsafe extern "system" fn visit(window: HWND, lparam: LPARAM) -> BOOL {
+        let search = &mut *(lparam as *mut Search);
+        let mut owner_pid = 0;
+        GetWindowThreadProcessId(window, &mut owner_pid);
+        if owner_pid != search.pid || !GetWindow(window, GW_OWNER).is_null() {
+            return 1;
+        }
+        if IsWindowVisible(window) != 0 {
+            search.found = window;
+            return 0; // Stop enumerating
+        }
+        if search.hidden.is_null() {
+            search.hidden = window;
+        }
+        1
+    }
+
+    let mut search = Search { pid, found: std::ptr::null_mut(), hidden: std::ptr::null_mut() };
+    unsafe { EnumWindows(Some(visit), &mut search as *mut Search as LPARAM) };
+    if search.found.is_null() && search.hidden.is_null() {
+        if unsafe { AllowSetForegroundWindow(pid) } == 0 {
+            return Err(OrganizerError::PermissionDenied(format!("Windows refused to activate process {}", pid)));
+        }
+        return Ok(());
+    }
+
+    unsafe {
+        if search.found.is_null() {
+            search.found = search.hidden;
+            ShowWindow(search.found, SW_SHOW);
+        }
+        if IsIconic(search.found) != 0 {
+            ShowWindow(search.found, SW_RESTORE);
+        }
+        if SetForegroundWindow(search.found) == 0 {
+            return Err(OrganizerError::PermissionDenied(format!("Windows refused to focus process {}", pid)));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+#[tauri::command]
+fn activate_app(_pid: u32) -> Result<(), OrganizerError> {
+    Err(OrganizerError::Unsupported("Activating other apps is not supported on this platform".into()))
+}
+
+// Exit watchers: one polling thread per watched PID, stopped through its cancel flag
+#[derive(Default)]
+struct ExitWatchState {
+    watches: Mutex<HashMap<u32, Arc<AtomicBool>>>,
+}
+
+const EXIT_WATCH_INTERVAL_SECS: u64 = 1;
+
+#[derive(Clone, serde::Serialize)]
+struct ProcessExited {
+    pid: u32,
+    name: String,
+    exit_detected_at: u64, // Unix timestamp
+}
+
+// Notify when a process exits (e.g. a long build). Watching the same PID twice is a no-op.
+#[tauri::command]
+fn notify_on_exit(app: AppHandle, pid: u32) -> Result<(), OrganizerError> {
+    use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+
+    let state = app
+        .try_state::<Arc<ExitWatchState>>()
+        .ok_or_else(|| OrganizerError::NotFound("Exit watch state".into()))?
+        .inner()
+        .clone();
+
+    let sysinfo_pid = Pid::from_u32(pid);
+    let mut sys = System::new();
+    sys.refresh_processes_specifics(ProcessesToUpdate::Some(&[sysinfo_pid]), true, ProcessRefreshKind::new());
+    let process = sys
+        .process(sysinfo_pid)
+        .ok_or_else(|| OrganizerError::NotFound(format!("Process {}", pid)))?;
+    let name = process.name().to_string_lossy().to_string();
+    // A different start time under the same PID means the original exited and the PID was reused
+    let start_time = process.start_time();
+
+    let cancelled = Arc::new(AtomicBool::new(false));
+    {
+        let mut watches = state.watches.lock()?;
+        if watches.contains_key(&pid) {
+            return Ok(());
+        }
+        watches.insert(pid, cancelled.clone());
+    }
+
+    let shutdown = shutdown_signal(&app);
+    std::thread::spawn(move || {
+        loop {
+            if !shutdown.sleep(Duration::from_secs(EXIT_WATCH_INTERVAL_SECS)) || cancelled.load(Ordering::SeqCst) {
+                return;
+            }
+
+            sys.refresh_processes_specifics(ProcessesToUpdate::Some(&[sysinfo_pid]), true, ProcessRefreshKind::new());
+            let alive = sys
+                .process(sysinfo_pid)
+                .is_some_and(|p| p.start_time() == start_time);
+            if !alive {
+                break;
+            }
+        }
+
+        if let Ok(mut watches) = state.watches.lock() {
+            if watches.get(&pid).is_some_and(|flag| Arc::ptr_eq(flag, &cancelled)) {
+                watches.remove(&pid);
+            }
+        }
+
+        let _ = app.emit("process:exited", ProcessExited {
+            pid,
+            name: name.clone(),
+            exit_detected_at: unix_now(),
+        });
+        notify(
+            &app,
+            &tr(&app, "notify.process_exited.title", &[]),
+            &tr(&app, "notify.process_exited.body", &[("name", &name), ("pid", &pid.to_string())]),
+        );
+        raise_alert(&app);
+    });
+
+    Ok(())
+}
+
+#[tauri::command]
+fn cancel_exit_watch(app: AppHandle, pid: u32) -> Result<bool, OrganizerError> {
+    let state = app
+        .try_state::<Arc<ExitWatchState>>()
+        .ok_or_else(|| OrganizerError::NotFound("Exit watch state".into()))?;
+    let removed = state.watches.lock()?.remove(&pid);
+    if let Some(flag) = &removed {
+        flag.store(true, Ordering::SeqCst);
+    }
+    Ok(removed.is_some())
+}
+
+// CPU throttles: one controller thread per PID, stopped through its cancel flag.
+// The start time is kept so begin_shutdown can resume the right process on quit.
+#[derive(Default)]
+struct ThrottleState {
+    throttles: Mutex<HashMap<u32, Throttle>>,
+}
+
+// Only created by the unix throttle_process
+#[cfg_attr(not(unix), allow(dead_code))]
+struct Throttle {
+    cancelled: Arc<AtomicBool>,
+    start_time: u64,
+}
+
+// Signal the process only if it is still the one we throttled (not a reused PID)
+#[cfg(unix)]
+fn signal_same_process(sys: &mut sysinfo::System, pid: u32, start_time: u64, signal: sysinfo::Signal) -> bool {
+    use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate};
+
+    let sysinfo_pid = Pid::from_u32(pid);
+    sys.refresh_processes_specifics(ProcessesToUpdate::Some(&[sysinfo_pid]), true, ProcessRefreshKind::new());
+    sys.process(sysinfo_pid)
+        .filter(|p| p.start_time() == start_time)
+        .and_then(|p| p.kill_with(signal))
+        .unwrap_or(false)
+}
+
+// Quit can land inside a stopped window: the controller threads may never get to resume
+// their process, so every throttled PID is sent SIGCONT before the app exits
+#[cfg(unix)]
+fn resume_throttled_processes(app: &AppHandle) {
+    let Some(state) = app.try_state::<Arc<ThrottleState>>() else { return };
+    let Ok(throttles) = state.throttles.lock() else { return };
+    let mut sys = sysinfo::System::new();
+    for (pid, throttle) in throttles.iter() {
+        throttle.cancelled.store(true, Ordering::SeqCst);
+        signal_same_process(&mut sys, *pid, throttle.start_time, sysinfo::Signal::Continue);
+    }
+}
+
+#[cfg(not(unix))]
+fn resume_throttled_processes(_app: &AppHandle) {}
+
+#[cfg(unix)]
+const THROTTLE_CYCLE_MS: u64 = 100;
+// Cycles between two CPU measurements (and duty adjustments)
+#[cfg(unix)]
+const THROTTLE_MEASURE_CYCLES: u32 = 10;
+
+// cpulimit-style cap: each 100ms cycle the process runs for a fraction of the time and is
+// SIGSTOPped for the rest. The fraction is adjusted every second from the measured CPU,
+// so usage hovers around max_pct (100 = one full core) rather than holding it exactly.
+// The process is always resumed when the throttle ends, whether cancelled, exited or on quit
+// (begin_shutdown resumes it in case quit interrupts a stopped window).
+#[cfg(unix)]
+#[tauri::command]
+fn throttle_process(app: AppHandle, pid: u32, max_pct: f32) -> Result<(), String> {
+    use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, Signal, System};
+
+    if max_pct.is_nan() || max_pct <= 0.0 {
+        return Err("max_pct must be positive".to_string());
+    }
+    let state = app
+        .try_state::<Arc<ThrottleState>>()
+        .ok_or("Throttle state not found")?
+        .inner()
+        .clone();
+
+    let sysinfo_pid = Pid::from_u32(pid);
+    let refresh_kind = ProcessRefreshKind::new().with_cpu();
+    let mut sys = System::new();
+    sys.refresh_processes_specifics(ProcessesToUpdate::Some(&[sysinfo_pid]), true, refresh_kind);
+    let process = sys.process(sysinfo_pid).ok_or_else(|| format!("Process {} not found", pid))?;
+    if is_protected_process(pid, &process.name().to_string_lossy()) {
+        return Err(format!("Process {} is protected", pid));
+    }
+    let start_time = process.start_time();
+
+    let cancelled = Arc::new(AtomicBool::new(false));
+    {
+        let mut throttles = state.throttles.lock().map_err(|e| e.to_string())?;
+        if throttles.contains_key(&pid) {
+            return Err(format!("Process {} is already throttled", pid));
+        }
+        throttles.insert(pid, Throttle { cancelled: cancelled.clone(), start_time });
+    }
+
+    let shutdown = shutdown_signal(&app);
+    std::thread::spawn(move || {
+        let cycle = Duration::from_millis(THROTTLE_CYCLE_MS);
+        let mut run_fraction = (max_pct as f64 / 100.0).clamp(0.05, 1.0);
+        let mut cycles = 0;
+        // Identity is re-read before every signal: the CPU System is only refreshed once a
+        // second, long enough for the PID to be reused
+        let mut identity = System::new();
+        let mut signal = |signal: Signal| signal_same_process(&mut identity, pid, start_time, signal);
+
+        while !cancelled.load(Ordering::SeqCst) && !shutdown.is_stopping() {
+            let running = cycle.mul_f64(run_fraction);
+            if !signal(Signal::Continue) {
+                break;
+            }
+            std::thread::sleep(running);
+            if running < cycle {
+                if !signal(Signal::Stop) {
+                    break;
+                }
+                std::thread::sleep(cycle - running);
+            }
+
+            cycles += 1;
+            if cycles % THROTTLE_MEASURE_CYCLES == 0 {
+                sys.refresh_processes_specifics(ProcessesToUpdate::Some(&[sysinfo_pid]), true, refresh_kind);
+                let Some(measured) = sys.process(sysinfo_pid).map(|p| p.cpu_usage() as f64) else { break };
+                if measured > 0.5 {
+                    run_fraction = (run_fraction * max_pct as f64 / measured).clamp(0.01, 1.0);
+                } else {
+                    run_fraction = (run_fraction * 2.0).min(1.0);
+                }
+            }
+        }
+        signal(Signal::Continue);
+
+        if let Ok(mut throttles) = state.throttles.lock() {
+            if throttles.get(&pid).is_some_and(|t| Arc::ptr_eq(&t.cancelled, &cancelled)) {
+                throttles.remove(&pid);
+            }
+        }
+    });
+
+    Ok(())
+}
+
+#[cfg(not(unix))]
+#[tauri::command]
+fn throttle_process(_pid: u32, _max_pct: f32) -> Result<(), String> {
+    Err("Throttling needs SIGSTOP/SIGCONT, not available on Windows".to_string())
+}
+
+// Ends the throttle and resumes the process; false when it wasn't throttled
+#[tauri::command]
+fn unthrottle_process(app: AppHandle, pid: u32) -> Result<bool, String> {
+    let state = app
+        .try_state::<Arc<ThrottleState>>()
+        .ok_or("Throttle state not found")?;
+    let removed = state.throttles.lock().map_err(|e| e.to_string())?.remove(&pid);
+    if let Some(throttle) = &removed {
+        throttle.cancelled.store(true, Ordering::SeqCst);
+    }
+    Ok(removed.is_some())
+}
+
+// Directory watchers: dropping a watcher stops it, which also ends its coalescing thread
+#[derive(Default)]
+struct DirectoryWatchState {
+    watchers: Mutex<HashMap<String, notify::RecommendedWatcher>>,
+}
+
+// Events for the same path within this window are merged into one fs:event
+const FS_EVENT_COALESCE_MS: u64 = 500;
+
+#[derive(Clone, serde::Serialize)]
+struct FsEvent {
+    kind: &'static str, // "create", "modify" or "delete"
+    path: String,
+}
+
+// Partial downloads and Office lock files churn while being written
+fn is_temp_file(path: &Path) -> bool {
+    let name = path.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+    name.starts_with("~$") || name.ends_with(".part") || name.ends_with(".crdownload")
+}
+
+fn fs_event_kind(kind: &notify::EventKind) -> Option<&'static str> {
+    use notify::EventKind;
+    match kind {
+        EventKind::Create(_) => Some("create"),
+        EventKind::Modify(_) => Some("modify"),
+        EventKind::Remove(_) => Some("delete"),
+        _ => None,
+    }
+}
+
+// Create then modify stays a create; a delete wins over anything before it
+fn merge_fs_event(previous: Option<&'static str>, next: &'static str) -> &'static str {
+    match (previous, next) {
+        (Some("create"), "modify") => "create",
+        _ => next,
+    }
+}
+
+// Stream create/modify/delete events under `path` (recursive) as fs:event.
+// Watching the same path twice is a no-op.
+#[tauri::command]
+fn watch_directory(app: AppHandle, path: String) -> Result<(), String> {
+    use notify::Watcher;
+    use std::sync::mpsc::{self, RecvTimeoutError};
+
+    if !std::fs::metadata(&path).map_err(|e| format!("{}: {}", path, e))?.is_dir() {
+        return Err(format!("{} is not a directory", path));
+    }
+    let state = app
+        .try_state::<Arc<DirectoryWatchState>>()
+        .ok_or("Directory watch state not found")?
+        .inner()
+        .clone();
+    let mut watchers = state.watchers.lock().map_err(|e| e.to_string())?;
+    if watchers.contains_key(&path) {
+        return Ok(());
+    }
+
+    let (tx, rx) = mpsc::channel::<notify::Event>();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        if let Ok(event) = event {
+            let _ = tx.send(event);
+        }
+    })
+    .map_err(|e| e.to_string())?;
+    watcher
+        .watch(Path::new(&path), notify::RecursiveMode::Recursive)
+        .map_err(|e| e.to_string())?;
+    watchers.insert(path, watcher);
+
+    std::thread::spawn(move || {
+        let window = Duration::from_millis(FS_EVENT_COALESCE_MS);
+        // Merged kind and flush deadline per path: the deadline is set by the path's first
+        // event, so its follow-ups within the window merge in and a busy path still flushes
+        // once per window
+        let mut pending: HashMap<std::path::PathBuf, (&'static str, Instant)> = HashMap::new();
+        loop {
+            let timeout = pending
+                .values()
+                .map(|(_, deadline)| deadline.saturating_duration_since(Instant::now()))
+                .min()
+                .unwrap_or(window);
+            let disconnected = match rx.recv_timeout(timeout) {
+                Ok(event) => {
+                    if let Some(kind) = fs_event_kind(&event.kind) {
+                        for path in event.paths.into_iter().filter(|p| !is_temp_file(p)) {
+                            let entry = pending.entry(path).or_insert((kind, Instant::now() + window));
+                            entry.0 = merge_fs_event(Some(entry.0), kind);
+                        }
+                    }
+                    false
+                }
+                Err(RecvTimeoutError::Timeout) => false,
+                Err(RecvTimeoutError::Disconnected) => true,
+            };
+            let now = Instant::now();
+            let due: Vec<std::path::PathBuf> = pending
+                .iter()
+                .filter(|(_, (_, deadline))| disconnected || *deadline <= now)
+                .map(|(path, _)| path.clone())
+                .collect();
+            for path in due {
+                if let Some((kind, _)) = pending.remove(&path) {
+                    let _ = app.emit("fs:event", FsEvent {
+                        kind,
+                        path: path.to_string_lossy().to_string(),
+                    });
+                }
+            }
+            if disconnected {
+                return;
+            }
+        }
+    });
+
+    Ok(())
+}
+
+#[tauri::command]
+fn stop_watching(app: AppHandle, path: String) -> Result<bool, String> {
+    let state = app
+        .try_state::<Arc<DirectoryWatchState>>()
+        .ok_or("Directory watch state not found")?;
+    let removed = state.watchers.lock().map_err(|e| e.to_string())?.remove(&path);
+    Ok(removed.is_some())
+}
+
+// Organize rules: the first rule whose glob matches the file name decides where it goes.
+// dest_dir may start with ~/ (home) or be relative to the file's own folder.
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+struct OrganizeRule {
+    match_glob: String, // On the file name, case-insensitive: * any run, ? one character
+    dest_dir: String,
+}
+
+#[derive(Clone, serde::Serialize)]
+struct OrganizeMoved {
+    source: String,
+    dest: String,
+    rule: String, // match_glob of the rule that applied
+}
+
+fn glob_matches(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    let name: Vec<char> = name.to_lowercase().chars().collect();
+    let (mut p, mut n) = (0, 0);
+    // Last * seen and the name position it currently swallows up to
+    let mut star: Option<(usize, usize)> = None;
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, n));
+            p += 1;
+        } else if let Some((star_p, star_n)) = star {
+            p = star_p + 1;
+            n = star_n + 1;
+            star = Some((star_p, star_n + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+fn organize_rules(app: &AppHandle) -> Vec<OrganizeRule> {
+    app.store("settings.json")
+        .ok()
+        .and_then(|store| store.get(SETTINGS_ORGANIZE_RULES))
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default()
+}
+
+#[tauri::command]
+fn get_organize_rules(app: AppHandle) -> Vec<OrganizeRule> {
+    organize_rules(&app)
+}
+
+#[tauri::command]
+fn set_organize_rules(app: AppHandle, rules: Vec<OrganizeRule>) -> Result<(), String> {
+    if let Some(rule) = rules.iter().find(|r| r.match_glob.trim().is_empty() || r.dest_dir.trim().is_empty()) {
+        return Err(format!("Incomplete rule: '{}' -> '{}'", rule.match_glob, rule.dest_dir));
+    }
+    let value = serde_json::to_value(&rules).map_err(|e| e.to_string())?;
+    save_setting(&app, SETTINGS_ORGANIZE_RULES, value);
+    Ok(())
+}
+
+fn rule_dest_dir(app: &AppHandle, rule: &OrganizeRule, source: &Path) -> Option<std::path::PathBuf> {
+    let dest = rule.dest_dir.trim();
+    if let Some(rest) = dest.strip_prefix("~/") {
+        return app.path().home_dir().ok().map(|home| home.join(rest));
+    }
+    let dest = Path::new(dest);
+    if dest.is_absolute() {
+        Some(dest.to_path_buf())
+    } else {
+        source.parent().map(|parent| parent.join(dest))
+    }
+}
+
+// "report.pdf" -> "report (1).pdf", "report (2).pdf"... until neither the disk nor `taken` has it
+fn unique_destination(
+    dir: &Path,
+    file_name: &std::ffi::OsStr,
+    taken: &HashSet<std::path::PathBuf>,
+) -> std::path::PathBuf {
+    let candidate = dir.join(file_name);
+    if !candidate.exists() && !taken.contains(&candidate) {
+        return candidate;
+    }
+    let name = Path::new(file_name);
+    let stem = name.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    let extension = name.extension().map(|e| format!(".{}", e.to_string_lossy())).unwrap_or_default();
+    (1..)
+        .map(|i| dir.join(format!("{} ({}){}", stem, i, extension)))
+        .find(|candidate| !candidate.exists() && !taken.contains(candidate))
+        .unwrap_or(candidate)
+}
+
+// Where the first matching rule sends `source`, with the rule's glob
+fn organize_destination(
+    app: &AppHandle,
+    rules: &[OrganizeRule],
+    source: &Path,
+    taken: &HashSet<std::path::PathBuf>,
+) -> Option<(std::path::PathBuf, String)> {
+    let file_name = source.file_name()?;
+    let rule = rules
+        .iter()
+        .find(|rule| glob_matches(rule.match_glob.trim(), &file_name.to_string_lossy()))?;
+    let dir = rule_dest_dir(app, rule, source)?;
+    // Already sorted: leave it where it is
+    if source.parent() == Some(dir.as_path()) {
+        return None;
+    }
+    Some((unique_destination(&dir, file_name, taken), rule.match_glob.clone()))
+}
+
+// rename, falling back to copy + delete when the destination is on another volume
+fn move_file(source: &Path, dest: &Path) -> std::io::Result<()> {
+    if let Some(parent) = dest.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    if std::fs::rename(source, dest).is_ok() {
+        return Ok(());
+    }
+    std::fs::copy(source, dest)?;
+    std::fs::remove_file(source)
+}
+
+// Move a file per the first matching rule and return where it went.
+// With dry_run, only returns where it would go.
+#[tauri::command]
+fn organize_file(app: AppHandle, path: String, dry_run: Option<bool>) -> Result<String, String> {
+    let source = Path::new(&path);
+    if !source.is_file() {
+        return Err(format!("{} is not a file", path));
+    }
+    let (dest, rule) = organize_destination(&app, &organize_rules(&app), source, &HashSet::new())
+        .ok_or_else(|| format!("No organize rule matches {}", path))?;
+    let dest_str = dest.to_string_lossy().to_string();
+    if dry_run.unwrap_or(false) {
+        return Ok(dest_str);
+    }
+
+    move_file(source, &dest).map_err(|e| format!("Failed to move {}: {}", path, e))?;
+    let _ = app.emit("organize:moved", OrganizeMoved {
+        source: path,
+        dest: dest_str.clone(),
+        rule,
+    });
+    Ok(dest_str)
+}
+
+// One file of a preview_organize run. dest/rule are None when no rule matches (skipped).
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+struct OrganizePlan {
+    source: String,
+    dest: Option<String>,
+    rule: Option<String>,
+}
+
+#[derive(serde::Serialize)]
+struct OrganizeSkip {
+    source: String,
+    reason: String,
+}
+
+#[derive(serde::Serialize)]
+struct ApplyOrganizeResult {
+    moved: Vec<OrganizeMoved>,
+    skipped: Vec<OrganizeSkip>,
+}
+
+// What the saved rules would do to the files directly inside `path`, without moving anything.
+// Two files heading to the same name get distinct destinations, like they would when applied.
+#[tauri::command]
+fn preview_organize(app: AppHandle, path: String) -> Result<Vec<OrganizePlan>, String> {
+    let rules = organize_rules(&app);
+    let mut sources: Vec<std::path::PathBuf> = std::fs::read_dir(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path, e))?
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|t| t.is_file()))
+        .map(|entry| entry.path())
+        .filter(|p| !is_temp_file(p) && !p.file_name().is_some_and(|n| n.to_string_lossy().starts_with('.')))
+        .collect();
+    sources.sort();
+
+    let mut taken = HashSet::new();
+    Ok(sources
+        .into_iter()
+        .map(|source| {
+            let target = organize_destination(&app, &rules, &source, &taken);
+            if let Some((dest, _)) = &target {
+                taken.insert(dest.clone());
+            }
+            OrganizePlan {
+                source: source.to_string_lossy().to_string(),
+                dest: target.as_ref().map(|(dest, _)| dest.to_string_lossy().to_string()),
+                rule: target.map(|(_, rule)| rule),
+            }
+        })
+        .collect())
+}
+
+// Execute the chosen plans. A destination created since the preview gets a fresh
+// de-duplicated name instead of being overwritten.
+#[tauri::command]
+async fn apply_organize(app: AppHandle, plans: Vec<OrganizePlan>) -> Result<ApplyOrganizeResult, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut result = ApplyOrganizeResult { moved: Vec::new(), skipped: Vec::new() };
+        for plan in plans {
+            let skip = |reason: &str| OrganizeSkip { source: plan.source.clone(), reason: reason.to_string() };
+            let Some(planned) = plan.dest.as_deref().map(Path::new) else {
+                result.skipped.push(skip("No matching rule"));
+                continue;
+            };
+            let source = Path::new(&plan.source);
+            if !source.is_file() {
+                result.skipped.push(skip("File no longer exists"));
+                continue;
+            }
+            let dest = match (planned.parent(), planned.file_name()) {
+                (Some(dir), Some(name)) => unique_destination(dir, name, &HashSet::new()),
+                _ => {
+                    result.skipped.push(skip("Invalid destination"));
+                    continue;
+                }
+            };
+            if let Err(e) = move_file(source, &dest) {
+                result.skipped.push(skip(&e.to_string()));
+                continue;
+            }
+            let moved = OrganizeMoved {
+                source: plan.source.clone(),
+                dest: dest.to_string_lossy().to_string(),
+                rule: plan.rule.clone().unwrap_or_default(),
+            };
+            let _ = app.emit("organize:moved", moved.clone());
+            result.moved.push(moved);
+        }
+        result
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
+
+// Clipboard history: text only, last CLIPBOARD_HISTORY_MAX distinct entries, newest first
+const CLIPBOARD_HISTORY_MAX: usize = 50;
+const CLIPBOARD_POLL_MS: u64 = 1000;
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+struct ClipboardHistoryConfig {
+    enabled: bool,
+    skip_secrets: bool, // Don't keep entries that look like passwords or API keys
+}
+
+impl Default for ClipboardHistoryConfig {
+    fn default() -> Self {
+        ClipboardHistoryConfig { enabled: false, skip_secrets: true }
+    }
+}
+
+struct ClipboardHistoryState {
+    config: Mutex<ClipboardHistoryConfig>,
+    entries: Mutex<VecDeque<String>>,
+}
+
+// Heuristic: a known token prefix, or a single "word" mixing at least 3 character classes
+fn looks_like_secret(text: &str) -> bool {
+    const TOKEN_PREFIXES: &[&str] = &["-----BEGIN", "ghp_", "github_pat_", "glpat-", "sk-", "xox", "AKIA", "AIza"];
+    let text = text.trim();
+    if TOKEN_PREFIXES.iter().any(|prefix| text.starts_with(prefix)) {
+        return true;
+    }
+    if !(8..=128).contains(&text.len()) || text.chars().any(char::is_whitespace) {
+        return false;
+    }
+    let classes = [
+        text.chars().any(|c| c.is_ascii_lowercase()),
+        text.chars().any(|c| c.is_ascii_uppercase()),
+        text.chars().any(|c| c.is_ascii_digit()),
+        text.chars().any(|c| !c.is_alphanumeric()),
+    ];
+    // Paths and URLs mix classes too
+    let path_like = text.contains('/') || text.contains('\\');
+    classes.iter().filter(|c| **c).count() >= 3 && !path_like
+}
+
+fn push_clipboard_entry(entries: &mut VecDeque<String>, text: String) {
+    entries.retain(|e| *e != text);
+    entries.push_front(text);
+    entries.truncate(CLIPBOARD_HISTORY_MAX);
+}
+
+fn spawn_clipboard_monitor(app: AppHandle) {
+    let shutdown = shutdown_signal(&app);
+    std::thread::spawn(move || {
+        use tauri_plugin_clipboard_manager::ClipboardExt;
+
+        let mut last: Option<String> = None;
+        loop {
+            if !shutdown.sampler_sleep(Duration::from_millis(CLIPBOARD_POLL_MS)) {
+                return;
+            }
+            let Some(state) = app.try_state::<Arc<ClipboardHistoryState>>() else { continue };
+            let config = match state.config.lock() {
+                Ok(config) => config.clone(),
+                Err(e) => {
+                    log_monitor_error("clipboard monitor", e);
+                    continue;
+                }
+            };
+            if !config.enabled {
+                last = None;
+                continue;
+            }
+
+            let Ok(text) = app.clipboard().read_text() else { continue };
+            if text.trim().is_empty() || last.as_deref() == Some(text.as_str()) {
+                continue;
+            }
+            last = Some(text.clone());
+            if config.skip_secrets && looks_like_secret(&text) {
+                continue;
+            }
+            let Ok(mut entries) = state.entries.lock() else { continue };
+            push_clipboard_entry(&mut entries, text);
+        }
+    });
+}
+
+#[tauri::command]
+fn get_clipboard_history(app: AppHandle) -> Result<Vec<String>, String> {
+    let state = app
+        .try_state::<Arc<ClipboardHistoryState>>()
+        .ok_or("Clipboard history state not found")?;
+    let entries = state.entries.lock().map_err(|e| e.to_string())?;
+    Ok(entries.iter().cloned().collect())
+}
+
+// Copy an entry (or any text) back to the clipboard; it moves to the top of the history
+#[tauri::command]
+fn set_clipboard(app: AppHandle, text: String) -> Result<(), String> {
+    use tauri_plugin_clipboard_manager::ClipboardExt;
+    app.clipboard().write_text(text.clone()).map_err(|e| e.to_string())?;
+    if let Some(state) = app.try_state::<Arc<ClipboardHistoryState>>() {
+        if state.config.lock().map_err(|e| e.to_string())?.enabled {
+            let mut entries = state.entries.lock().map_err(|e| e.to_string())?;
+            push_clipboard_entry(&mut entries, text);
+        }
+    }
+    Ok(())
+}
+
+#[tauri::command]
+fn clear_clipboard_history(app: AppHandle) -> Result<(), String> {
+    let state = app
+        .try_state::<Arc<ClipboardHistoryState>>()
+        .ok_or("Clipboard history state not found")?;
+    state.entries.lock().map_err(|e| e.to_string())?.clear();
+    Ok(())
+}
+
+#[tauri::command]
+fn get_clipboard_history_config(app: AppHandle) -> Result<ClipboardHistoryConfig, String> {
+    let state = app
+        .try_state::<Arc<ClipboardHistoryState>>()
+        .ok_or("Clipboard history state not found")?;
+    let config = state.config.lock().map_err(|e| e.to_string())?.clone();
+    Ok(config)
+}
+
+// Turning capture off also drops what was captured so far
+#[tauri::command]
+fn set_clipboard_history_config(app: AppHandle, enabled: bool, skip_secrets: bool) -> Result<(), String> {
+    let state = app
+        .try_state::<Arc<ClipboardHistoryState>>()
+        .ok_or("Clipboard history state not found")?;
+    let config = ClipboardHistoryConfig { enabled, skip_secrets };
+    let value = serde_json::to_value(&config).map_err(|e| e.to_string())?;
+    *state.config.lock().map_err(|e| e.to_string())? = config;
+    if !enabled {
+        state.entries.lock().map_err(|e| e.to_string())?.clear();
+    }
+    save_setting(&app, SETTINGS_CLIPBOARD_HISTORY, value);
+    Ok(())
+}
+
+// Update check: fetch a small JSON manifest ({ "version": "x.y.z", "notes_url": "..." })
+const UPDATE_CHECK_INTERVAL_SECS: u64 = 6 * 3600;
+const UPDATE_CHECK_TICK_SECS: u64 = 60;
+
+#[derive(Clone, Default, serde::Serialize, serde::Deserialize)]
+struct UpdateCheckConfig {
+    auto_check: bool,             // Check periodically in the background and notify
+    manifest_url: Option<String>,
+}
+
+#[derive(Clone, serde::Serialize)]
+struct UpdateInfo {
+    current_version: String,
+    latest_version: String,
+    update_available: bool,
+    notes_url: Option<String>,
+    checked_at: u64, // Unix timestamp of the network fetch
+}
+
+#[derive(serde::Deserialize)]
+struct UpdateManifest {
+    version: String,
+    notes_url: Option<String>,
+}
+
+struct UpdateCheckState {
+    config: Mutex<UpdateCheckConfig>,
+    last: Mutex<Option<UpdateInfo>>,
+    last_attempt_at: Mutex<Option<u64>>, // Unix timestamp of the last fetch, failed ones included
+    notified_version: Mutex<Option<String>>,
+}
+
+// "1.10.2" > "1.9" (missing components count as 0, a leading "v" and a "-beta" suffix are ignored)
+fn is_newer_version(latest: &str, current: &str) -> bool {
+    let parse = |v: &str| -> Vec<u64> {
+        v.trim()
+            .trim_start_matches('v')
+            .split(['-', '+'])
+            .next()
+            .unwrap_or("")
+            .split('.')
+            .map(|part| part.parse().unwrap_or(0))
+            .collect()
+    };
+    let (latest, current) = (parse(latest), parse(current));
+    for i in 0..latest.len().max(current.len()) {
+        let (l, c) = (latest.get(i).copied().unwrap_or(0), current.get(i).copied().unwrap_or(0));
+        if l != c {
+            return l > c;
+        }
+    }
+    false
+}
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let line = stdout.lines().nth(1).ok_or("No df output")?;
-    let parts: Vec<&str> = line.split_whitespace().collect();
+const UPDATE_FETCH_TIMEOUT_SECS: u64 = 15;
+
+// Blocking: runs on the checker thread or spawn_blocking, never on the async runtime.
+// TLS trusts the system certificate store, so proxies with a locally installed CA work.
+fn fetch_update_info(url: &str) -> Result<UpdateInfo, OrganizerError> {
+    let fetch_error = |e: reqwest::Error| OrganizerError::Io(format!("Fetching {} failed: {}", url, e));
+    let body = reqwest::blocking::Client::builder()
+        .timeout(Duration::from_secs(UPDATE_FETCH_TIMEOUT_SECS))
+        .user_agent(concat!("organizer/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .map_err(fetch_error)?
+        .get(url)
+        .send()
+        .and_then(|response| response.error_for_status())
+        .and_then(|response| response.bytes())
+        .map_err(fetch_error)?;
+
+    let manifest: UpdateManifest = serde_json::from_slice(&body)?;
+    let current_version = env!("CARGO_PKG_VERSION").to_string();
+    Ok(UpdateInfo {
+        update_available: is_newer_version(&manifest.version, &current_version),
+        current_version,
+        latest_version: manifest.version,
+        notes_url: manifest.notes_url,
+        checked_at: unix_now(),
+    })
+}
 
-    let total_kb: f64 = parts.get(1).ok_or("No total")?.parse().map_err(|e: std::num::ParseFloatError| e.to_string())?;
-    let available_kb: f64 = parts.get(3).ok_or("No available")?.parse().map_err(|e: std::num::ParseFloatError| e.to_string())?;
+// Cached result if checked within UPDATE_CHECK_INTERVAL_SECS, unless force is set
+fn check_for_update_cached(state: &UpdateCheckState, force: bool) -> Result<UpdateInfo, OrganizerError> {
+    if !force {
+        if let Some(last) = state.last.lock()?.clone() {
+            if unix_now().saturating_sub(last.checked_at) < UPDATE_CHECK_INTERVAL_SECS {
+                return Ok(last);
+            }
+        }
+    }
 
-    Ok(DiskSpace {
-        free_gb: available_kb / 1_048_576.0,
-        total_gb: total_kb / 1_048_576.0,
-    })
+    let url = state
+        .config
+        .lock()?
+        .manifest_url
+        .clone()
+        .ok_or_else(|| OrganizerError::NotFound("No update manifest URL configured".into()))?;
+    *state.last_attempt_at.lock()? = Some(unix_now());
+    let info = fetch_update_info(&url)?;
+    *state.last.lock()? = Some(info.clone());
+    Ok(info)
 }
 
-// macOS: use Swift to get detailed disk space including purgeable via Foundation API
-#[cfg(target_os = "macos")]
 #[tauri::command]
-fn get_disk_space_detailed() -> Result<DiskSpaceDetailed, String> {
-    // Swift one-liner to get volume capacities via Foundation API
-    // Returns: total|available|availableForImportantUsage
-    let swift_code = r#"
-import Foundation
-let url = URL(fileURLWithPath: "/")
-let values = try! url.resourceValues(forKeys: [.volumeTotalCapacityKey, .volumeAvailableCapacityKey, .volumeAvailableCapacityForImportantUsageKey])
-print("\(values.volumeTotalCapacity ?? 0)|\(values.volumeAvailableCapacity ?? 0)|\(values.volumeAvailableCapacityForImportantUsage ?? 0)")
-"#;
+async fn check_for_update(app: AppHandle, force: bool) -> Result<UpdateInfo, OrganizerError> {
+    let state = app
+        .try_state::<Arc<UpdateCheckState>>()
+        .ok_or_else(|| OrganizerError::NotFound("Update check state".into()))?
+        .inner()
+        .clone();
+    tauri::async_runtime::spawn_blocking(move || check_for_update_cached(&state, force)).await?
+}
 
-    let output = std::process::Command::new("swift")
-        .args(["-e", swift_code])
-        .output()
-        .map_err(|e| format!("Failed to run swift: {}", e))?;
+#[tauri::command]
+fn get_update_check(app: AppHandle) -> Result<UpdateCheckConfig, String> {
+    let state = app
+        .try_state::<Arc<UpdateCheckState>>()
+        .ok_or("Update check state not found")?;
+    let config = state.config.lock().map_err(|e| e.to_string())?.clone();
+    Ok(config)
+}
 
-    if !output.status.success() {
-        return Err(format!("Swift failed: {}", String::from_utf8_lossy(&output.stderr)));
+#[tauri::command]
+fn set_update_check(app: AppHandle, auto_check: bool, manifest_url: Option<String>) -> Result<(), String> {
+    let manifest_url = manifest_url.map(|u| u.trim().to_string()).filter(|u| !u.is_empty());
+    if let Some(url) = &manifest_url {
+        if !url.starts_with("https://") && !url.starts_with("http://") {
+            return Err(format!("Manifest URL must be http(s): {}", url));
+        }
     }
+    let state = app
+        .try_state::<Arc<UpdateCheckState>>()
+        .ok_or("Update check state not found")?;
+    let config = UpdateCheckConfig { auto_check, manifest_url };
+    let value = serde_json::to_value(&config).map_err(|e| e.to_string())?;
+    *state.config.lock().map_err(|e| e.to_string())? = config;
+    // A different URL may report a different version
+    *state.last.lock().map_err(|e| e.to_string())? = None;
+    *state.last_attempt_at.lock().map_err(|e| e.to_string())? = None;
+    save_setting(&app, SETTINGS_UPDATE_CHECK, value);
+    Ok(())
+}
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let parts: Vec<&str> = stdout.trim().split('|').collect();
-
-    if parts.len() != 3 {
-        return Err(format!("Invalid swift output: {}", stdout));
-    }
+// Background check when auto_check is on; notifies once per new version
+fn spawn_update_checker(app: AppHandle) {
+    let shutdown = shutdown_signal(&app);
+    std::thread::spawn(move || loop {
+        if !shutdown.sampler_sleep(Duration::from_secs(UPDATE_CHECK_TICK_SECS)) {
+            return;
+        }
 
-    let total_bytes: u64 = parts[0].parse().map_err(|_| "Failed to parse total")?;
-    let available_bytes: u64 = parts[1].parse().map_err(|_| "Failed to parse available")?;
-    let available_with_purgeable_bytes: u64 = parts[2].parse().map_err(|_| "Failed to parse available for important")?;
+        let Some(state) = app.try_state::<Arc<UpdateCheckState>>() else { continue };
+        let config = match state.config.lock() {
+            Ok(c) => c.clone(),
+            Err(e) => {
+                log_monitor_error("update checker", e);
+                continue;
+            }
+        };
+        if !config.auto_check || config.manifest_url.is_none() {
+            continue;
+        }
+        // Offline or a dead URL: retry once per interval, not on every tick
+        match state.last_attempt_at.lock() {
+            Ok(at) if at.is_some_and(|at| unix_now().saturating_sub(at) < UPDATE_CHECK_INTERVAL_SECS) => continue,
+            Ok(_) => {}
+            Err(e) => {
+                log_monitor_error("update checker", e);
+                continue;
+            }
+        }
 
-    let purgeable_bytes = available_with_purgeable_bytes.saturating_sub(available_bytes);
-    let used_bytes = total_bytes.saturating_sub(available_with_purgeable_bytes);
+        let info = match check_for_update_cached(&state, false) {
+            Ok(info) => info,
+            Err(e) => {
+                log_monitor_error("update checker", e);
+                continue;
+            }
+        };
+        if !info.update_available {
+            continue;
+        }
+        let mut notified = match state.notified_version.lock() {
+            Ok(notified) => notified,
+            Err(e) => {
+                log_monitor_error("update checker", e);
+                continue;
+            }
+        };
+        if notified.as_deref() == Some(info.latest_version.as_str()) {
+            continue;
+        }
+        *notified = Some(info.latest_version.clone());
+        drop(notified);
+
+        notify(
+            &app,
+            &tr(&app, "notify.update.title", &[]),
+            &tr(&app, "notify.update.body", &[
+                ("latest", &info.latest_version),
+                ("current", &info.current_version),
+            ]),
+        );
+    });
+}
 
-    let bytes_to_gb = |b: u64| b as f64 / 1_073_741_824.0;
+// Per-command latency, recorded by the invoke handler wrapper while profiling is enabled
+const COMMAND_TIMING_SAMPLES: usize = 200;
 
-    Ok(DiskSpaceDetailed {
-        total_gb: bytes_to_gb(total_bytes),
-        available_gb: bytes_to_gb(available_bytes),
-        available_with_purgeable_gb: bytes_to_gb(available_with_purgeable_bytes),
-        purgeable_gb: bytes_to_gb(purgeable_bytes),
-        used_gb: bytes_to_gb(used_bytes),
-    })
+#[derive(Default)]
+struct ProfilingState {
+    enabled: AtomicBool,
+    timings: Mutex<HashMap<String, (u64, VecDeque<f64>)>>, // command -> (total calls, recent durations in ms)
 }
 
-// Windows/Linux: use sysinfo
-#[cfg(not(target_os = "macos"))]
-#[tauri::command]
-fn get_disk_space() -> Result<DiskSpace, String> {
-    let disks = Disks::new_with_refreshed_list();
-
-    let disk = disks
-        .iter()
-        .find(|d| {
-            let mount = d.mount_point().to_string_lossy();
-            mount == "/" || mount == "C:\\"
-        })
-        .or_else(|| disks.iter().next())
-        .ok_or("No disk found")?;
+impl ProfilingState {
+    fn record(&self, command: &str, elapsed: Duration) {
+        if let Ok(mut timings) = self.timings.lock() {
+            let (count, samples) = timings.entry(command.to_string()).or_default();
+            *count += 1;
+            samples.push_back(elapsed.as_secs_f64() * 1000.0);
+            while samples.len() > COMMAND_TIMING_SAMPLES {
+                samples.pop_front();
+            }
+        }
+    }
+}
 
-    let total_bytes = disk.total_space() as f64;
-    let available_bytes = disk.available_space() as f64;
+// Async commands return from the handler as soon as their future is spawned and Tauri has
+// no hook for when the invoke resolves, so timing them here would report ~0ms. They are
+// left out of the timings. Keep in sync with the async fns in generate_handler!.
+const ASYNC_COMMANDS: &[&str] = &[
+    "apply_organize", "capture_snapshot", "check_for_update", "empty_trash",
+    "find_cleanup_candidates", "find_duplicate_files", "find_space_hogs", "focus_watch",
+    "generate_system_report", "get_active_app", "get_app_info", "get_battery_status",
+    "get_cpu_usage", "get_culprit", "get_fd_leaders", "get_gpu_processes", "get_indexing_status",
+    "get_memory_info", "get_network_config", "get_network_mounts", "get_pagein_leaders",
+    "get_per_disk_io", "get_process_ancestry", "get_process_delta", "get_process_details_async",
+    "get_process_icon", "get_process_summary", "get_processes_by_user", "get_socket_summary",
+    "get_subtree_memory", "get_swap_leaders", "get_top_level_usage", "get_top_processes",
+    "get_trash_size", "list_containers", "render_dashboard_png", "sample_process_stack",
+    "scan_directory_sizes", "stream_server_status", "who_holds",
+];
+
+// Wrap the generated handler. When profiling is off this is a single atomic load per call.
+// Sync commands are timed end to end; async commands (ASYNC_COMMANDS) are not recorded.
+fn profiled<F>(handler: F) -> impl Fn(tauri::ipc::Invoke<Wry>) -> bool + Send + Sync + 'static
+where
+    F: Fn(tauri::ipc::Invoke<Wry>) -> bool + Send + Sync + 'static,
+{
+    move |invoke| {
+        let profiling = invoke
+            .message
+            .webview_ref()
+            .try_state::<Arc<ProfilingState>>()
+            .filter(|p| p.enabled.load(Ordering::Relaxed))
+            .map(|p| p.inner().clone());
+        let Some(profiling) = profiling else {
+            return handler(invoke);
+        };
+
+        let command = invoke.message.command().to_string();
+        if ASYNC_COMMANDS.contains(&command.as_str()) {
+            return handler(invoke);
+        }
+        let started = Instant::now();
+        let handled = handler(invoke);
+        profiling.record(&command, started.elapsed());
+        handled
+    }
+}
 
-    Ok(DiskSpace {
-        free_gb: available_bytes / 1_073_741_824.0,
-        total_gb: total_bytes / 1_073_741_824.0,
-    })
+#[derive(serde::Serialize)]
+struct CommandTiming {
+    command: String,
+    count: u64,   // Calls since profiling was enabled
+    avg_ms: f64,  // Over the last COMMAND_TIMING_SAMPLES calls
+    p95_ms: f64,
+    max_ms: f64,
 }
 
-// Windows/Linux: use sysinfo (no purgeable concept)
-#[cfg(not(target_os = "macos"))]
+// Slowest sync commands (by average) first; async commands are not timed (see ASYNC_COMMANDS)
 #[tauri::command]
-fn get_disk_space_detailed() -> Result<DiskSpaceDetailed, String> {
-    let disks = Disks::new_with_refreshed_list();
+fn get_command_timings(app: AppHandle) -> Result<Vec<CommandTiming>, String> {
+    let state = app
+        .try_state::<Arc<ProfilingState>>()
+        .ok_or("Profiling state not found")?;
+    let timings = state.timings.lock().map_err(|e| e.to_string())?;
 
-    let disk = disks
+    let mut result: Vec<CommandTiming> = timings
         .iter()
-        .find(|d| {
-            let mount = d.mount_point().to_string_lossy();
-            mount == "/" || mount == "C:\\"
+        .filter(|(_, (_, samples))| !samples.is_empty())
+        .map(|(command, (count, samples))| {
+            let mut sorted: Vec<f64> = samples.iter().copied().collect();
+            sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+            let p95_idx = ((sorted.len() as f64 * 0.95).ceil() as usize).clamp(1, sorted.len()) - 1;
+            CommandTiming {
+                command: command.clone(),
+                count: *count,
+                avg_ms: sorted.iter().sum::<f64>() / sorted.len() as f64,
+                p95_ms: sorted[p95_idx],
+                max_ms: sorted[sorted.len() - 1],
+            }
         })
-        .or_else(|| disks.iter().next())
-        .ok_or("No disk found")?;
-
-    let total_bytes = disk.total_space();
-    let available_bytes = disk.available_space();
-    let used_bytes = total_bytes.saturating_sub(available_bytes);
+        .collect();
 
-    let bytes_to_gb = |b: u64| b as f64 / 1_073_741_824.0;
+    result.sort_by(|a, b| b.avg_ms.partial_cmp(&a.avg_ms).unwrap_or(std::cmp::Ordering::Equal));
+    Ok(result)
+}
 
-    Ok(DiskSpaceDetailed {
-        total_gb: bytes_to_gb(total_bytes),
-        available_gb: bytes_to_gb(available_bytes),
-        available_with_purgeable_gb: bytes_to_gb(available_bytes), // Same as available on Windows
-        purgeable_gb: 0.0, // No purgeable concept on Windows
-        used_gb: bytes_to_gb(used_bytes),
-    })
+#[tauri::command]
+fn get_profiling_enabled(app: AppHandle) -> bool {
+    app.try_state::<Arc<ProfilingState>>()
+        .map(|state| state.enabled.load(Ordering::SeqCst))
+        .unwrap_or(false)
 }
 
+// Turning profiling on starts from empty timings
 #[tauri::command]
-fn set_tray_badge(app: AppHandle, has_badge: bool) -> Result<(), String> {
-    let icon_state = app
-        .try_state::<Arc<TrayIconState>>()
-        .ok_or("Icon state not found")?;
+fn set_profiling_enabled(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let state = app
+        .try_state::<Arc<ProfilingState>>()
+        .ok_or("Profiling state not found")?;
+    if enabled && !state.enabled.load(Ordering::SeqCst) {
+        state.timings.lock().map_err(|e| e.to_string())?.clear();
+    }
+    state.enabled.store(enabled, Ordering::SeqCst);
+    save_setting(&app, SETTINGS_PROFILING_ENABLED, serde_json::json!(enabled));
+    Ok(())
+}
 
-    let tray = app
-        .tray_by_id("main")
-        .ok_or("Tray not found")?;
+// Panics (any thread) and background monitor errors, in app_data/crashes.log.
+// Past the size cap the log is moved to crashes.log.1, so two generations are kept.
+const CRASH_LOG_FILE: &str = "crashes.log";
+const CRASH_LOG_MAX_BYTES: u64 = 1_048_576;
 
-    if has_badge {
-        // Create icon with badge
-        let icon_with_badge = create_badge_icon(
-            &icon_state.original_icon,
-            icon_state.width,
-            icon_state.height,
-        )
-        .map_err(|e| e.to_string())?;
+static CRASH_LOG_PATH: std::sync::OnceLock<std::path::PathBuf> = std::sync::OnceLock::new();
 
-        let new_icon = Image::new_owned(
-            icon_with_badge,
-            icon_state.width,
-            icon_state.height,
-        );
-        tray.set_icon(Some(new_icon)).map_err(|e| e.to_string())?;
-    } else {
-        // Restore original icon
-        let original = Image::new_owned(
-            icon_state.original_icon.clone(),
-            icon_state.width,
-            icon_state.height,
-        );
-        tray.set_icon(Some(original)).map_err(|e| e.to_string())?;
+fn append_crash_log(entry: &str) {
+    use std::io::Write;
+
+    let Some(path) = CRASH_LOG_PATH.get() else { return };
+    if std::fs::metadata(path).is_ok_and(|m| m.len() > CRASH_LOG_MAX_BYTES) {
+        let _ = std::fs::rename(path, path.with_extension("log.1"));
+    }
+    if let Ok(mut file) = std::fs::OpenOptions::new().create(true).append(true).open(path) {
+        let _ = writeln!(file, "[{}] {}", iso8601_utc(unix_now()), entry.trim_end());
     }
+}
 
-    Ok(())
+// Chains to the default hook, so panics still reach stderr
+fn install_panic_hook(app: &AppHandle) {
+    let Ok(dir) = app.path().app_data_dir() else { return };
+    let _ = std::fs::create_dir_all(&dir);
+    let _ = CRASH_LOG_PATH.set(dir.join(CRASH_LOG_FILE));
+
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let thread = std::thread::current();
+        let backtrace = std::backtrace::Backtrace::force_capture();
+        append_crash_log(&format!(
+            "thread '{}' {}\n{}",
+            thread.name().unwrap_or("<unnamed>"),
+            info,
+            backtrace
+        ));
+        previous(info);
+    }));
 }
 
-fn clear_tray_badge(app: &AppHandle) {
-    if let Some(icon_state) = app.try_state::<Arc<TrayIconState>>() {
-        if let Some(tray) = app.tray_by_id("main") {
-            let original = Image::new_owned(
-                icon_state.original_icon.clone(),
-                icon_state.width,
-                icon_state.height,
-            );
-            let _ = tray.set_icon(Some(original));
+// A monitor that keeps failing the same way logs it once, not on every tick
+fn log_monitor_error(monitor: &str, error: impl std::fmt::Display) {
+    static LAST: Mutex<Option<HashMap<String, String>>> = Mutex::new(None);
+
+    let message = error.to_string();
+    if let Ok(mut last) = LAST.lock() {
+        let last = last.get_or_insert_with(HashMap::new);
+        if last.get(monitor) == Some(&message) {
+            return;
         }
+        last.insert(monitor.to_string(), message.clone());
     }
+    append_crash_log(&format!("{}: {}", monitor, message));
 }
 
-fn create_badge_icon(original: &[u8], width: u32, height: u32) -> Result<Vec<u8>, String> {
-    // Clone the original RGBA data
-    let mut pixels = original.to_vec();
-
-    // Calculate badge position (top-right corner)
-    let badge_center_x = width - BADGE_RADIUS - 2;
-    let badge_center_y = BADGE_RADIUS + 2;
+// Monitor events the UI may be waiting on: a failed emit is logged rather than dropped
+fn emit_or_log<S: serde::Serialize + Clone>(app: &AppHandle, monitor: &str, event: &str, payload: S) {
+    if let Err(e) = app.emit(event, payload) {
+        log_monitor_error(monitor, format!("emit {}: {}", event, e));
+    }
+}
 
-    // Draw filled circle for badge
-    for y in 0..height {
-        for x in 0..width {
-            let dx = x as i32 - badge_center_x as i32;
-            let dy = y as i32 - badge_center_y as i32;
-            let distance_sq = dx * dx + dy * dy;
+// Last `lines` lines of the crash log, oldest first; empty when nothing was logged
+#[tauri::command]
+fn get_crash_log(lines: usize) -> Vec<String> {
+    let Some(text) = CRASH_LOG_PATH.get().and_then(|path| std::fs::read_to_string(path).ok()) else {
+        return Vec::new();
+    };
+    let all: Vec<&str> = text.lines().collect();
+    all[all.len().saturating_sub(lines)..].iter().map(|l| l.to_string()).collect()
+}
 
-            if distance_sq <= (BADGE_RADIUS * BADGE_RADIUS) as i32 {
-                let idx = ((y * width + x) * 4) as usize;
-                if idx + 3 < pixels.len() {
-                    pixels[idx] = BADGE_COLOR[0];     // R
-                    pixels[idx + 1] = BADGE_COLOR[1]; // G
-                    pixels[idx + 2] = BADGE_COLOR[2]; // B
-                    pixels[idx + 3] = BADGE_COLOR[3]; // A
-                }
-            }
+#[tauri::command]
+fn clear_crash_log() -> Result<(), String> {
+    let Some(path) = CRASH_LOG_PATH.get() else { return Ok(()) };
+    for file in [path.clone(), path.with_extension("log.1")] {
+        match std::fs::remove_file(&file) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.to_string()),
+            _ => {}
         }
     }
-
-    Ok(pixels)
+    Ok(())
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     let app = tauri::Builder::default()
+        .manage(Arc::new(Shutdown::default()))
         .plugin(tauri_plugin_store::Builder::new().build())
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_notification::init())
@@ -924,19 +10024,64 @@ pub fn run() {
         ))
         .plugin(tauri_plugin_shell::init())
         .setup(|app| {
+            install_panic_hook(app.handle());
+
             // Load saved settings from store
-            let (autostart_enabled, minimize_to_tray_enabled) = {
-                let store = app.store("settings.json")?;
-                let autostart = store
-                    .get(SETTINGS_AUTOSTART)
-                    .and_then(|v| v.as_bool())
-                    .unwrap_or(false);
-                let minimize = store
-                    .get(SETTINGS_MINIMIZE_TO_TRAY)
-                    .and_then(|v| v.as_bool())
-                    .unwrap_or(false);
-                (autostart, minimize)
-            };
+            let store = app.store("settings.json")?;
+            let autostart_enabled = store
+                .get(SETTINGS_AUTOSTART)
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let minimize_to_tray_enabled = store
+                .get(SETTINGS_MINIMIZE_TO_TRAY)
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let always_on_top_enabled = store
+                .get(SETTINGS_ALWAYS_ON_TOP)
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            // Before any sampler starts
+            let monitoring_paused = store
+                .get(SETTINGS_MONITORING_PAUSED)
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            shutdown_signal(app.handle()).set_paused(monitoring_paused);
+            let memory_guard_rule: MemoryGuardRule = store
+                .get(SETTINGS_MEMORY_GUARD)
+                .and_then(|v| serde_json::from_value(v).ok())
+                .unwrap_or_default();
+            let metric_trays_config: MetricTraysConfig = store
+                .get(SETTINGS_METRIC_TRAYS)
+                .and_then(|v| serde_json::from_value(v).ok())
+                .unwrap_or_default();
+            let swap_alert_config: SwapAlertConfig = store
+                .get(SETTINGS_SWAP_ALERT)
+                .and_then(|v| serde_json::from_value(v).ok())
+                .unwrap_or_default();
+            let high_load_hook_config: HighLoadHookConfig = store
+                .get(SETTINGS_HIGH_LOAD_HOOK)
+                .and_then(|v| serde_json::from_value(v).ok())
+                .unwrap_or_default();
+            let profiling_enabled = store
+                .get(SETTINGS_PROFILING_ENABLED)
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let hidden_process_names: Vec<String> = store
+                .get(SETTINGS_HIDDEN_PROCESS_NAMES)
+                .and_then(|v| serde_json::from_value(v).ok())
+                .unwrap_or_default();
+            let disk_alert_config: DiskAlertConfig = store
+                .get(SETTINGS_DISK_ALERT)
+                .and_then(|v| serde_json::from_value(v).ok())
+                .unwrap_or_default();
+            let update_check_config: UpdateCheckConfig = store
+                .get(SETTINGS_UPDATE_CHECK)
+                .and_then(|v| serde_json::from_value(v).ok())
+                .unwrap_or_default();
+            let clipboard_history_config: ClipboardHistoryConfig = store
+                .get(SETTINGS_CLIPBOARD_HISTORY)
+                .and_then(|v| serde_json::from_value(v).ok())
+                .unwrap_or_default();
 
             // Sync autostart state with system on startup
             {
@@ -949,6 +10094,13 @@ pub fn run() {
                 }
             }
 
+            // Apply always-on-top preference to the main window
+            if always_on_top_enabled {
+                if let Some(window) = app.get_webview_window("main") {
+                    let _ = window.set_always_on_top(true);
+                }
+            }
+
             // Get original icon data for badge overlay
             let icon = app.default_window_icon().unwrap().clone();
             let icon_rgba = icon.rgba().to_vec();
@@ -962,13 +10114,151 @@ pub fn run() {
                 height: icon_height,
             }));
 
+            // Pending alert count shown on the tray badge
+            app.manage(Arc::new(AlertState::default()));
+
+            // Directory scan cache and disk snapshots
+            app.manage(Arc::new(ScanCache::default()));
+            app.manage(Arc::new(DiskSnapshots::default()));
+            app.manage(Arc::new(ProcessSnapshots::default()));
+            app.manage(Arc::new(DiskIoState::default()));
+            #[cfg(target_os = "macos")]
+            app.manage(Arc::new(PageinState::default()));
+
+            // Memory guard (does nothing until enabled and armed)
+            app.manage(Arc::new(MemoryGuardState {
+                rule: Mutex::new(memory_guard_rule),
+            }));
+            spawn_memory_guard(app.handle().clone());
+
+            // Clipboard history (off unless enabled in settings)
+            app.manage(Arc::new(ClipboardHistoryState {
+                config: Mutex::new(clipboard_history_config),
+                entries: Mutex::new(VecDeque::new()),
+            }));
+            spawn_clipboard_monitor(app.handle().clone());
+
+            // Swap usage alert
+            app.manage(Arc::new(SwapAlertState {
+                config: Mutex::new(swap_alert_config),
+            }));
+            spawn_swap_monitor(app.handle().clone());
+
+            // User command run at sustained high CPU load
+            app.manage(Arc::new(HighLoadHookState {
+                config: Mutex::new(high_load_hook_config),
+            }));
+            spawn_high_load_monitor(app.handle().clone());
+
+            // Nearly-full volume alert
+            app.manage(Arc::new(DiskAlertState {
+                config: Mutex::new(disk_alert_config),
+            }));
+            spawn_disk_monitor(app.handle().clone());
+
+            // CPU/memory/disk samples for export_history
+            app.manage(Arc::new(MetricHistory::default()));
+            spawn_history_sampler(app.handle().clone());
+
+            // Session CPU time per program for get_cpu_time_leaders
+            app.manage(Arc::new(CpuAccountingState {
+                since: Mutex::new(unix_now()),
+                totals: Mutex::new(HashMap::new()),
+            }));
+            spawn_cpu_accounting(app.handle().clone());
+
+            // Exe-keyed start times for get_service_restarts
+            app.manage(Arc::new(ServiceRestartState::default()));
+            spawn_service_restart_tracker(app.handle().clone());
+
+            // CPU throttling alert (macOS)
+            spawn_thermal_monitor(app.handle().clone());
+
+            // Low / full battery alerts (nothing happens without a battery)
+            let battery_alert_config: BatteryAlertConfig = store
+                .get(SETTINGS_BATTERY_ALERTS)
+                .and_then(|v| serde_json::from_value(v).ok())
+                .unwrap_or_default();
+            app.manage(Arc::new(BatteryAlertState {
+                config: Mutex::new(battery_alert_config),
+            }));
+            spawn_battery_monitor(app.handle().clone());
+
+            // Process names hidden from the top list
+            app.manage(Arc::new(HiddenProcessesState {
+                names: Mutex::new(hidden_process_names),
+            }));
+
+            // PNGs converted by get_process_icon
+            app.manage(Arc::new(ProcessIconCache::default()));
+
+            // Rows last sent by get_process_delta, per view
+            app.manage(Arc::new(ProcessDeltaState::default()));
+
+            // Last known server status (from the previous run if any)
+            app.manage(Arc::new(ServerStatusCache {
+                last: Mutex::new(load_server_status_cache(app.handle())),
+            }));
+
+            // Shared System for process/memory/CPU commands, warmed up off the main thread
+            app.manage(Arc::new(SharedSystem {
+                sys: Mutex::new(sysinfo::System::new()),
+            }));
+            spawn_system_warmup(app.handle().clone());
+
+            // Live per-process poller
+            app.manage(Arc::new(ProcessPollState {
+                sys: Mutex::new(sysinfo::System::new()),
+                samples: Mutex::new(HashMap::new()),
+            }));
+
+            // Per-process exit watchers (started from notify_on_exit)
+            app.manage(Arc::new(ExitWatchState::default()));
+            app.manage(Arc::new(ThrottleState::default()));
+
+            // Directory watchers (started from watch_directory)
+            app.manage(Arc::new(DirectoryWatchState::default()));
+
+            // Maintenance commands started from run_command
+            app.manage(Arc::new(CommandRunState::default()));
+
+            // Command latency profiling (off unless enabled in settings)
+            app.manage(Arc::new(ProfilingState {
+                enabled: AtomicBool::new(profiling_enabled),
+                ..Default::default()
+            }));
+
+            // Update check (network only when auto_check is on or on explicit request)
+            app.manage(Arc::new(UpdateCheckState {
+                config: Mutex::new(update_check_config),
+                last: Mutex::new(None),
+                last_attempt_at: Mutex::new(None),
+                notified_version: Mutex::new(None),
+            }));
+            spawn_update_checker(app.handle().clone());
+
+            // Language of tray labels and notifications: saved choice, else the system locale
+            let locale = store
+                .get(SETTINGS_LOCALE)
+                .and_then(|v| v.as_str().and_then(i18n::normalize_locale))
+                .unwrap_or_else(system_locale);
+            app.manage(Arc::new(LocaleState {
+                locale: Mutex::new(locale.to_string()),
+            }));
+
+            // GiB unless decimal units were chosen
+            let binary_units = store
+                .get(SETTINGS_UNITS)
+                .and_then(|v| v.as_str().and_then(format::parse_unit_base))
+                .unwrap_or(true);
+            format::set_binary_base(binary_units);
+
             // Create tray menu items
-            let show = MenuItem::with_id(app, "show", "Show Organizer", true, None::<&str>)?;
-            let separator1 = tauri::menu::PredefinedMenuItem::separator(app)?;
+            let show = MenuItem::with_id(app, "show", tr(app.handle(), "tray.show", &[]), true, None::<&str>)?;
             let autostart_item = CheckMenuItem::with_id(
                 app,
                 "autostart",
-                "Start with System",
+                tr(app.handle(), "tray.autostart", &[]),
                 true,
                 autostart_enabled,
                 None::<&str>,
@@ -976,33 +10266,46 @@ pub fn run() {
             let minimize_item = CheckMenuItem::with_id(
                 app,
                 "minimize_to_tray",
-                "Minimize to Tray on Close",
+                tr(app.handle(), "tray.minimize_to_tray", &[]),
                 true,
                 minimize_to_tray_enabled,
                 None::<&str>,
             )?;
-            let separator2 = tauri::menu::PredefinedMenuItem::separator(app)?;
-            let quit = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
+            let always_on_top_item = CheckMenuItem::with_id(
+                app,
+                "always_on_top",
+                tr(app.handle(), "tray.always_on_top", &[]),
+                true,
+                always_on_top_enabled,
+                None::<&str>,
+            )?;
+            let pause_monitoring_item = CheckMenuItem::with_id(
+                app,
+                "pause_monitoring",
+                tr(app.handle(), "tray.pause_monitoring", &[]),
+                true,
+                monitoring_paused,
+                None::<&str>,
+            )?;
+            let quit = MenuItem::with_id(app, "quit", tr(app.handle(), "tray.quit", &[]), true, None::<&str>)?;
 
             // Store references to check menu items and state for later access
             app.manage(Arc::new(TrayMenuState {
+                show: show.clone(),
                 autostart: autostart_item.clone(),
                 minimize_to_tray: minimize_item.clone(),
+                always_on_top: always_on_top_item.clone(),
+                pause_monitoring: pause_monitoring_item.clone(),
+                quit: quit.clone(),
+                metrics: Mutex::new(Vec::new()),
                 autostart_enabled: AtomicBool::new(autostart_enabled),
                 minimize_enabled: AtomicBool::new(minimize_to_tray_enabled),
+                always_on_top_enabled: AtomicBool::new(always_on_top_enabled),
             }));
+            refresh_show_hide_item(app.handle());
 
-            let menu = Menu::with_items(
-                app,
-                &[
-                    &show,
-                    &separator1,
-                    &autostart_item,
-                    &minimize_item,
-                    &separator2,
-                    &quit,
-                ],
-            )?;
+            // Layout from the tray_menu setting
+            let menu = build_tray_menu(app.handle(), &app.state::<Arc<TrayMenuState>>(), &tray_menu_entries(app.handle()))?;
 
             // Create tray icon with ID "main"
             let _tray = TrayIconBuilder::with_id("main")
@@ -1011,14 +10314,14 @@ pub fn run() {
                 .show_menu_on_left_click(false)
                 .on_menu_event(|app, event| match event.id.as_ref() {
                     "show" => {
-                        if let Some(window) = app.get_webview_window("main") {
-                            // Unminimize if minimized, then show and focus
-                            let _ = window.unminimize();
-                            let _ = window.show();
-                            let _ = window.set_focus();
+                        if is_main_window_visible(app) {
+                            if let Some(window) = app.get_webview_window("main") {
+                                let _ = window.hide();
+                            }
+                            refresh_show_hide_item(app);
+                        } else {
+                            show_main_window(app);
                         }
-                        // Clear badge when showing window
-                        clear_tray_badge(app);
                     }
                     "autostart" => {
                         if let Some(state) = app.try_state::<Arc<TrayMenuState>>() {
@@ -1041,31 +10344,30 @@ pub fn run() {
                                 }
                             }
 
-                            // Save to store
-                            if let Ok(store) = app.store("settings.json") {
-                                let _ = store.set(SETTINGS_AUTOSTART.to_string(), serde_json::json!(new_state));
-                                let _ = store.save();
-                            }
+                            // Save to store and notify windows
+                            save_setting(app, SETTINGS_AUTOSTART, serde_json::json!(new_state));
                         }
                     }
                     "minimize_to_tray" => {
                         if let Some(state) = app.try_state::<Arc<TrayMenuState>>() {
                             // Toggle state
                             let current = state.minimize_enabled.load(Ordering::SeqCst);
-                            let new_state = !current;
-                            state.minimize_enabled.store(new_state, Ordering::SeqCst);
-
-                            // Update checkbox visual
-                            let _ = state.minimize_to_tray.set_checked(new_state);
-
-                            // Save to store
-                            if let Ok(store) = app.store("settings.json") {
-                                let _ = store.set(SETTINGS_MINIMIZE_TO_TRAY.to_string(), serde_json::json!(new_state));
-                                let _ = store.save();
-                            }
+                            apply_minimize_to_tray(app, &state, !current);
+                        }
+                    }
+                    "always_on_top" => {
+                        if let Some(state) = app.try_state::<Arc<TrayMenuState>>() {
+                            // Toggle state
+                            let current = state.always_on_top_enabled.load(Ordering::SeqCst);
+                            let _ = apply_always_on_top(app, &state, !current);
                         }
                     }
+                    "pause_monitoring" => {
+                        let paused = shutdown_signal(app).is_paused();
+                        apply_monitoring_paused(app, !paused);
+                    }
                     "quit" => {
+                        begin_shutdown(app);
                         app.exit(0);
                     }
                     _ => {}
@@ -1077,19 +10379,22 @@ pub fn run() {
                         ..
                     } = event
                     {
-                        let app = tray.app_handle();
-                        if let Some(window) = app.get_webview_window("main") {
-                            // Unminimize if minimized, then show and focus
-                            let _ = window.unminimize();
-                            let _ = window.show();
-                            let _ = window.set_focus();
-                        }
-                        // Clear badge when clicking tray icon
-                        clear_tray_badge(&app);
+                        // Show window and clear badge when clicking tray icon
+                        show_main_window(tray.app_handle());
                     }
                 })
                 .build(app)?;
 
+            // Optional per-metric tray icons, created after the main one so they sit beside it
+            let metric_trays = Arc::new(MetricTrayState::default());
+            for (id, enabled) in [("cpu", metric_trays_config.cpu), ("mem", metric_trays_config.mem)] {
+                if enabled {
+                    let _ = create_metric_tray(app.handle(), &metric_trays, id);
+                }
+            }
+            app.manage(metric_trays);
+            spawn_metric_tray_updater(app.handle().clone());
+
             // On Linux, enable media stream and auto-approve media permissions in WebKitGTK
             #[cfg(target_os = "linux")]
             {
@@ -1131,25 +10436,189 @@ pub fn run() {
                         // Hide window instead of closing
                         let _ = window.hide();
                         api.prevent_close();
+                        refresh_show_hide_item(app);
                     }
                     // If not enabled, allow normal close behavior (app exits)
                 }
                 WindowEvent::Focused(focused) => {
+                    // Clear badge when window gets focus
                     if *focused {
-                        // Clear badge when window gets focus
-                        let app = window.app_handle();
-                        clear_tray_badge(&app);
+                        clear_tray_badge(window.app_handle());
                     }
+                    // Focus changes also cover minimize/restore
+                    refresh_show_hide_item(window.app_handle());
                 }
                 _ => {}
             }
         })
-        .invoke_handler(tauri::generate_handler![greet, set_tray_badge, get_disk_space, get_disk_space_detailed, get_memory_info, get_top_processes, get_process_details, stream_server_status])
+        .invoke_handler(profiled(tauri::generate_handler![
+            greet,
+            set_tray_badge,
+            set_tray_badge_count,
+            get_alert_count,
+            set_metric_tray,
+            get_disk_space,
+            get_disk_space_detailed,
+            get_all_disks,
+            get_per_disk_io,
+            get_network_mounts,
+            get_network_config,
+            get_socket_summary,
+            get_trash_size,
+            empty_trash,
+            snapshot_disks,
+            diff_disks,
+            snapshot_processes,
+            diff_processes,
+            save_snapshot_to_disk,
+            load_snapshot_from_disk,
+            get_memory_info,
+            notification_permission_state,
+            get_locale,
+            get_supported_locales,
+            format_bytes,
+            get_units,
+            get_tray_menu,
+            set_tray_menu,
+            rebuild_tray_menu,
+            set_units,
+            set_locale,
+            request_notification_permission,
+            get_cpu_usage,
+            capture_snapshot,
+            get_gpu_processes,
+            get_swap_files,
+            get_top_processes,
+            get_culprit,
+            get_process_delta,
+            forget_process_delta,
+            get_process_summary,
+            resolve_pids_by_name,
+            resolve_name_by_pid,
+            who_holds,
+            kill_matching,
+            get_processes_by_user,
+            kill_user_processes,
+            get_hidden_processes,
+            add_hidden_process,
+            remove_hidden_process,
+            get_process_details,
+            get_process_details_async,
+            get_process_handles,
+            get_fd_leaders,
+            get_pagein_leaders,
+            get_swap_leaders,
+            get_process_start_iso,
+            sample_process_stack,
+            poll_process,
+            get_process_memory_breakdown,
+            get_subtree_memory,
+            get_process_ancestry,
+            notify_on_exit,
+            cancel_exit_watch,
+            throttle_process,
+            unthrottle_process,
+            watch_directory,
+            stop_watching,
+            get_organize_rules,
+            set_organize_rules,
+            organize_file,
+            preview_organize,
+            apply_organize,
+            get_cpu_affinity,
+            set_cpu_affinity,
+            stream_server_status,
+            run_command,
+            get_run_command_allowlist,
+            set_run_command_allowlist,
+            cancel_command,
+            get_command_timeout,
+            set_command_timeout,
+            get_server_status_stall_timeout,
+            set_server_status_stall_timeout,
+            get_ssh_command_template,
+            set_ssh_command_template,
+            get_ssh_binary,
+            set_ssh_binary,
+            get_last_server_status,
+            get_minimize_to_tray,
+            set_minimize_to_tray,
+            set_always_on_top,
+            get_monitoring_paused,
+            set_monitoring_paused,
+            compact_settings,
+            reset_settings,
+            position_window,
+            scan_directory_sizes,
+            find_duplicate_files,
+            clear_scan_cache,
+            diff_directory_scans,
+            get_top_level_usage,
+            find_space_hogs,
+            get_scan_threads,
+            set_scan_threads,
+            find_cleanup_candidates,
+            generate_system_report,
+            flush_dns_cache,
+            list_services,
+            toggle_service,
+            list_containers,
+            get_terminal_app,
+            set_terminal_app,
+            open_terminal_at,
+            render_dashboard_png,
+            export_history,
+            get_cpu_time_leaders,
+            get_service_restarts,
+            reset_cpu_accounting,
+            get_app_paths,
+            check_for_update,
+            get_update_check,
+            set_update_check,
+            get_command_timings,
+            get_profiling_enabled,
+            get_crash_log,
+            clear_crash_log,
+            set_profiling_enabled,
+            check_permissions,
+            get_thermal_state,
+            get_indexing_status,
+            pause_spotlight,
+            resume_spotlight,
+            get_battery_status,
+            get_battery_alerts,
+            set_battery_alerts,
+            get_active_app,
+            get_app_info,
+            get_process_icon,
+            focus_watch,
+            list_windows,
+            activate_app,
+            open_system_settings,
+            get_autostart_details,
+            repair_autostart,
+            reveal_app_data,
+            get_memory_guard,
+            set_memory_guard,
+            get_swap_alert,
+            get_high_load_hook,
+            get_clipboard_history,
+            set_clipboard,
+            clear_clipboard_history,
+            get_clipboard_history_config,
+            set_clipboard_history_config,
+            set_swap_alert,
+            set_high_load_hook,
+            get_disk_alert,
+            set_disk_alert,
+            set_disk_alert_threshold,
+            remove_disk_alert_threshold,
+        ]))
         .build(tauri::generate_context!())
         .expect("error while building tauri application");
 
-    app.run(|app_handle, event| {
-        if let RunEvent::ExitRequested { api, code, .. } = event {
+    app.run(|app_handle, event| match event {
+        RunEvent::ExitRequested { api, code, .. } => {
             // Only prevent exit if this is a user-initiated close (not explicit quit)
             if code.is_none() {
                 let minimize_enabled = app_handle
@@ -1159,8 +10628,54 @@ pub fn run() {
 
                 if minimize_enabled {
                     api.prevent_exit();
+                    return;
                 }
             }
+            begin_shutdown(app_handle);
         }
+        RunEvent::Exit => begin_shutdown(app_handle),
+        _ => {}
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shutdown_stops_a_monitor_loop() {
+        let shutdown = Arc::new(Shutdown::default());
+        let (done, finished) = std::sync::mpsc::channel();
+        let monitor = {
+            let shutdown = shutdown.clone();
+            std::thread::spawn(move || {
+                let mut ticks = 0;
+                // Same shape as the monitors: sleep a long interval, stop when told
+                while shutdown.sleep(Duration::from_secs(60)) {
+                    ticks += 1;
+                }
+                let _ = done.send(ticks);
+            })
+        };
+
+        std::thread::sleep(Duration::from_millis(50));
+        assert!(shutdown.trigger());
+        let ticks = finished
+            .recv_timeout(Duration::from_secs(2))
+            .expect("monitor loop did not stop after trigger");
+        assert_eq!(ticks, 0);
+        monitor.join().unwrap();
+
+        // Later sleeps return false right away, and a second trigger is a no-op
+        let started = Instant::now();
+        assert!(!shutdown.sleep(Duration::from_secs(60)));
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert!(!shutdown.trigger());
+    }
+
+    #[test]
+    fn iso8601_utc_formats_seconds_in_utc() {
+        assert_eq!(iso8601_utc(0), "1970-01-01T00:00:00Z");
+        assert_eq!(iso8601_utc(1_714_566_896), "2024-05-01T12:34:56Z");
+    }
+}
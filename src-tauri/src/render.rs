@@ -0,0 +1,203 @@
+// Dashboard card rendering for sharing: pure pixel drawing, no I/O or system access
+use image::{Rgba, RgbaImage};
+
+pub const DASHBOARD_WIDTH: u32 = 320;
+pub const DASHBOARD_HEIGHT: u32 = 182;
+
+const PADDING: u32 = 12;
+const TEXT_SCALE: u32 = 2;
+const BAR_X: u32 = 120;
+const BAR_HEIGHT: u32 = 10;
+const MAX_LINE_CHARS: usize = 36;
+
+const BACKGROUND: Rgba<u8> = Rgba([45, 45, 45, 255]); // Charcoal
+const BAR_TRACK: Rgba<u8> = Rgba([61, 61, 61, 255]);  // CharcoalLight
+const TEXT: Rgba<u8> = Rgba([230, 230, 230, 255]);
+const MUTED: Rgba<u8> = Rgba([150, 150, 150, 255]);
+const ACCENT: Rgba<u8> = Rgba([107, 159, 255, 255]);  // AccentBlue
+const WARNING: Rgba<u8> = Rgba([255, 159, 10, 255]);
+const CRITICAL: Rgba<u8> = Rgba([255, 59, 48, 255]);
+
+pub struct Metric {
+    pub label: String,
+    pub percent: f64, // 0-100
+}
+
+pub struct DashboardData {
+    pub metrics: Vec<Metric>,               // Drawn as labelled bars (CPU, RAM, disk...)
+    pub top_processes: Vec<(String, String)>, // (name, formatted value)
+}
+
+// 3x5 glyph, one row per entry (bit 2 = left column). Lowercase maps to uppercase.
+pub fn glyph(c: char) -> [u8; 5] {
+    match c.to_ascii_uppercase() {
+        '0' => [0b111, 0b101, 0b101, 0b101, 0b111],
+        '1' => [0b010, 0b110, 0b010, 0b010, 0b111],
+        '2' => [0b111, 0b001, 0b111, 0b100, 0b111],
+        '3' => [0b111, 0b001, 0b111, 0b001, 0b111],
+        '4' => [0b101, 0b101, 0b111, 0b001, 0b001],
+        '5' => [0b111, 0b100, 0b111, 0b001, 0b111],
+        '6' => [0b111, 0b100, 0b111, 0b101, 0b111],
+        '7' => [0b111, 0b001, 0b001, 0b001, 0b001],
+        '8' => [0b111, 0b101, 0b111, 0b101, 0b111],
+        '9' => [0b111, 0b101, 0b111, 0b001, 0b111],
+        'A' => [0b010, 0b101, 0b111, 0b101, 0b101],
+        'B' => [0b110, 0b101, 0b110, 0b101, 0b110],
+        'C' => [0b011, 0b100, 0b100, 0b100, 0b011],
+        'D' => [0b110, 0b101, 0b101, 0b101, 0b110],
+        'E' => [0b111, 0b100, 0b110, 0b100, 0b111],
+        'F' => [0b111, 0b100, 0b110, 0b100, 0b100],
+        'G' => [0b011, 0b100, 0b101, 0b101, 0b011],
+        'H' => [0b101, 0b101, 0b111, 0b101, 0b101],
+        'I' => [0b111, 0b010, 0b010, 0b010, 0b111],
+        'J' => [0b001, 0b001, 0b001, 0b101, 0b010],
+        'K' => [0b101, 0b101, 0b110, 0b101, 0b101],
+        'L' => [0b100, 0b100, 0b100, 0b100, 0b111],
+        'M' => [0b101, 0b111, 0b111, 0b101, 0b101],
+        'N' => [0b110, 0b101, 0b101, 0b101, 0b101],
+        'O' => [0b010, 0b101, 0b101, 0b101, 0b010],
+        'P' => [0b110, 0b101, 0b110, 0b100, 0b100],
+        'Q' => [0b010, 0b101, 0b101, 0b110, 0b011],
+        'R' => [0b110, 0b101, 0b110, 0b101, 0b101],
+        'S' => [0b011, 0b100, 0b010, 0b001, 0b110],
+        'T' => [0b111, 0b010, 0b010, 0b010, 0b010],
+        'U' => [0b101, 0b101, 0b101, 0b101, 0b111],
+        'V' => [0b101, 0b101, 0b101, 0b101, 0b010],
+        'W' => [0b101, 0b101, 0b111, 0b111, 0b101],
+        'X' => [0b101, 0b101, 0b010, 0b101, 0b101],
+        'Y' => [0b101, 0b101, 0b010, 0b010, 0b010],
+        'Z' => [0b111, 0b001, 0b010, 0b100, 0b111],
+        '%' => [0b101, 0b001, 0b010, 0b100, 0b101],
+        '.' => [0b000, 0b000, 0b000, 0b000, 0b010],
+        ',' => [0b000, 0b000, 0b000, 0b010, 0b100],
+        ':' => [0b000, 0b010, 0b000, 0b010, 0b000],
+        '-' => [0b000, 0b000, 0b111, 0b000, 0b000],
+        '_' => [0b000, 0b000, 0b000, 0b000, 0b111],
+        '/' => [0b001, 0b001, 0b010, 0b100, 0b100],
+        '(' => [0b001, 0b010, 0b010, 0b010, 0b001],
+        ')' => [0b100, 0b010, 0b010, 0b010, 0b100],
+        ' ' => [0b000, 0b000, 0b000, 0b000, 0b000],
+        _ => [0b111, 0b001, 0b010, 0b000, 0b010], // ?
+    }
+}
+
+// Width in pixels of text drawn at the given scale (3 columns + 1 spacing per glyph)
+pub fn text_width(text: &str, scale: u32) -> u32 {
+    (text.chars().count() as u32 * 4).saturating_sub(1) * scale
+}
+
+pub fn draw_text(img: &mut RgbaImage, x: u32, y: u32, text: &str, scale: u32, color: Rgba<u8>) {
+    for (i, c) in text.chars().enumerate() {
+        let glyph_x = x + i as u32 * 4 * scale;
+        for (row, bits) in glyph(c).iter().enumerate() {
+            for col in 0..3u32 {
+                if bits & (0b100 >> col) != 0 {
+                    fill_rect(img, glyph_x + col * scale, y + row as u32 * scale, scale, scale, color);
+                }
+            }
+        }
+    }
+}
+
+// Fill a rectangle, clipped to the image bounds
+pub fn fill_rect(img: &mut RgbaImage, x: u32, y: u32, w: u32, h: u32, color: Rgba<u8>) {
+    let (width, height) = img.dimensions();
+    for py in y..(y + h).min(height) {
+        for px in x..(x + w).min(width) {
+            img.put_pixel(px, py, color);
+        }
+    }
+}
+
+fn bar_color(percent: f64) -> Rgba<u8> {
+    if percent >= 90.0 {
+        CRITICAL
+    } else if percent >= 75.0 {
+        WARNING
+    } else {
+        ACCENT
+    }
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        text.to_string()
+    } else {
+        let kept: String = text.chars().take(max_chars.saturating_sub(2)).collect();
+        format!("{}..", kept)
+    }
+}
+
+// Layout: title, one labelled bar per metric, then the top processes list
+pub fn render_dashboard(data: &DashboardData) -> RgbaImage {
+    let mut img = RgbaImage::from_pixel(DASHBOARD_WIDTH, DASHBOARD_HEIGHT, BACKGROUND);
+    let line_height = 5 * TEXT_SCALE;
+    let bar_width = DASHBOARD_WIDTH.saturating_sub(BAR_X + PADDING);
+
+    draw_text(&mut img, PADDING, PADDING, "ORGANIZER", TEXT_SCALE, TEXT);
+
+    let mut y = PADDING + line_height + 12;
+    for metric in data.metrics.iter().take(3) {
+        let percent = metric.percent.clamp(0.0, 100.0);
+        let label = format!("{} {:.0}%", metric.label, percent);
+        draw_text(&mut img, PADDING, y, &label, TEXT_SCALE, TEXT);
+
+        fill_rect(&mut img, BAR_X, y, bar_width, BAR_HEIGHT, BAR_TRACK);
+        let filled = (bar_width as f64 * percent / 100.0).round() as u32;
+        fill_rect(&mut img, BAR_X, y, filled, BAR_HEIGHT, bar_color(percent));
+
+        y += 24;
+    }
+
+    draw_text(&mut img, PADDING, y, "TOP PROCESSES", TEXT_SCALE, MUTED);
+    y += line_height + 6;
+
+    for (name, value) in data.top_processes.iter().take(3) {
+        let value = truncate(value, MAX_LINE_CHARS);
+        let value_x = DASHBOARD_WIDTH.saturating_sub(PADDING + text_width(&value, TEXT_SCALE)).max(PADDING);
+        let name_chars = MAX_LINE_CHARS.saturating_sub(value.chars().count() + 1);
+        draw_text(&mut img, PADDING, y, &truncate(name, name_chars), TEXT_SCALE, TEXT);
+        draw_text(&mut img, value_x, y, &value, TEXT_SCALE, ACCENT);
+        y += line_height + 6;
+    }
+
+    img
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(value: &str) -> DashboardData {
+        DashboardData {
+            metrics: vec![
+                Metric { label: "CPU".into(), percent: 42.0 },
+                Metric { label: "RAM".into(), percent: 150.0 },
+                Metric { label: "DISK".into(), percent: -5.0 },
+            ],
+            top_processes: vec![("firefox".into(), value.into()), ("code".into(), "1.2 GiB".into())],
+        }
+    }
+
+    #[test]
+    fn image_has_dashboard_size() {
+        let img = render_dashboard(&sample("3.4 GiB"));
+        assert_eq!(img.dimensions(), (DASHBOARD_WIDTH, DASHBOARD_HEIGHT));
+
+        let empty = DashboardData { metrics: Vec::new(), top_processes: Vec::new() };
+        assert_eq!(render_dashboard(&empty).dimensions(), (DASHBOARD_WIDTH, DASHBOARD_HEIGHT));
+    }
+
+    #[test]
+    fn value_wider_than_card_does_not_underflow() {
+        let img = render_dashboard(&sample(&"9".repeat(200)));
+        assert_eq!(img.dimensions(), (DASHBOARD_WIDTH, DASHBOARD_HEIGHT));
+    }
+
+    #[test]
+    fn text_width_counts_glyph_spacing() {
+        assert_eq!(text_width("", TEXT_SCALE), 0);
+        assert_eq!(text_width("A", 1), 3);
+        assert_eq!(text_width("AB", 2), 14);
+    }
+}
@@ -6,7 +6,7 @@ import { getCurrentWindow } from "@tauri-apps/api/window";
 // Check if running in Tauri environment
 const isTauri = () => typeof window !== 'undefined' && '__TAURI_INTERNALS__' in window;
 import { compressImage, blobToDataUrl } from "./utils/imageCompression";
-import { initNotifications, consumePendingNotificationRoomId } from "./utils/notifications";
+import { initNotifications, consumePendingNotificationRoomId, watchMonitorNotificationPermission } from "./utils/notifications";
 import { useAuth } from "./contexts/AuthContext";
 import { useServerConfig } from "./contexts/ServerConfigContext";
 import { useUserStatus } from "./contexts/UserStatusContext";
@@ -372,6 +372,16 @@ function App() {
     };
   }, [selectRoom, currentRoomId]);
 
+  // Backend monitors ask for a permission prompt when their alert couldn't be shown
+  useEffect(() => {
+    if (!isTauri()) return;
+
+    const unlisten = watchMonitorNotificationPermission();
+    return () => {
+      unlisten.then(fn => fn());
+    };
+  }, []);
+
   // Keyboard shortcut: Cmd+K (Mac) / Ctrl+K (Win) to open search
   useEffect(() => {
     const handleKeyDown = (e: KeyboardEvent) => {
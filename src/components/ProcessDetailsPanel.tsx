@@ -1,6 +1,7 @@
 import { useState, useEffect, useCallback, useRef } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { InfoPanel, InfoSection } from "./ui/InfoPanel";
+import { formatCommandError } from "../utils/commandError";
 import "./ProcessDetailsPanel.css";
 
 interface ProcessDetails {
@@ -18,6 +19,13 @@ interface ProcessDetails {
   virtual_mb: number;
   disk_read_bytes: number;
   disk_write_bytes: number;
+  handles: HandleStats | null;
+}
+
+interface HandleStats {
+  handle_count: number;
+  gdi_objects: number;
+  user_objects: number;
 }
 
 interface Ancestor {
@@ -116,29 +124,15 @@ export function ProcessDetailsPanel({ pid, onClose }: ProcessDetailsPanelProps)
     setCpuHistory([]); // Reset history when switching processes
   }, [pid]);
 
-  // Fetch ancestors recursively
-  const fetchAncestors = useCallback(async (parentPid: number | null, chain: Ancestor[]): Promise<Ancestor[]> => {
-    if (parentPid === null || parentPid === 0) {
-      return chain;
-    }
-
+  // Ancestors from the root down to the direct parent (the backend returns process first)
+  const fetchAncestors = useCallback(async (pid: number): Promise<Ancestor[]> => {
     try {
-      const parentDetails = await invoke<ProcessDetails>("get_process_details", { pid: parentPid });
-      chain.unshift({ pid: parentDetails.pid, name: parentDetails.name });
-
-      // Stop recursion if parent_pid is same as pid (kernel) or null
-      if (parentDetails.parent_pid !== null && parentDetails.parent_pid !== parentDetails.pid) {
-        return fetchAncestors(parentDetails.parent_pid, chain);
-      }
+      const chain = await invoke<Ancestor[]>("get_process_ancestry", { pid });
+      return chain.slice(1).reverse().map(({ pid, name }) => ({ pid, name }));
     } catch {
-      // Parent process might have exited or be inaccessible
-      // Add a placeholder for known system processes
-      if (parentPid === 1) {
-        chain.unshift({ pid: 1, name: "launchd" });
-      }
+      // Process might have exited or be inaccessible
+      return [];
     }
-
-    return chain;
   }, []);
 
   useEffect(() => {
@@ -152,7 +146,7 @@ export function ProcessDetailsPanel({ pid, onClose }: ProcessDetailsPanelProps)
           setError(null);
           setProcessGone(false);
         }
-        const data = await invoke<ProcessDetails>("get_process_details", { pid: currentPid });
+        const data = await invoke<ProcessDetails>("get_process_details_async", { pid: currentPid });
         if (!cancelled) {
           setDetails(data);
 
@@ -165,7 +159,7 @@ export function ProcessDetailsPanel({ pid, onClose }: ProcessDetailsPanelProps)
           // Fetch ancestors only on initial load
           if (isInitial) {
             if (data.parent_pid !== null && data.parent_pid !== data.pid) {
-              const ancestorChain = await fetchAncestors(data.parent_pid, []);
+              const ancestorChain = await fetchAncestors(data.pid);
               if (!cancelled) {
                 setAncestors(ancestorChain);
               }
@@ -178,7 +172,7 @@ export function ProcessDetailsPanel({ pid, onClose }: ProcessDetailsPanelProps)
       } catch (err) {
         if (!cancelled) {
           if (isInitial) {
-            setError(err instanceof Error ? err.message : String(err));
+            setError(formatCommandError(err));
             setLoading(false);
           } else {
             // Process was killed during refresh
@@ -254,6 +248,10 @@ export function ProcessDetailsPanel({ pid, onClose }: ProcessDetailsPanelProps)
         { label: "Virtual", value: formatMemory(details.virtual_mb) },
         { label: "Disk Read", value: formatBytes(details.disk_read_bytes) },
         { label: "Disk Write", value: formatBytes(details.disk_write_bytes) },
+        ...(details.handles ? [
+          { label: "Handles", value: String(details.handles.handle_count), mono: true },
+          { label: "GDI / USER", value: `${details.handles.gdi_objects} / ${details.handles.user_objects}`, mono: true },
+        ] : []),
       ],
     });
 
@@ -25,6 +25,9 @@ interface DiskSpaceDetailed {
   available_with_purgeable_gb: number;
   purgeable_gb: number;
   used_gb: number;
+  free_for_user_gb: number;
+  free_total_gb: number;
+  reserved_gb: number;
 }
 
 interface MemoryInfo {
@@ -43,9 +46,17 @@ interface MemoryInfo {
 interface ProcessMemory {
   pid: number;
   name: string;
-  cwd: string | null; // Current working directory (last segment only)
+  cwd: string | null; // Current working directory (last segment only), detailLevel "full"
   memory_mb: number;
   virtual_mb: number;
+  cpu_usage: number | null; // Only with detailLevel "full"
+  disk_read_bytes: number | null;
+  disk_write_bytes: number | null;
+}
+
+interface TopProcesses {
+  processes: ProcessMemory[];
+  hidden_count: number; // Left out via the hidden process names setting
 }
 
 interface ServerStatus {
@@ -405,8 +416,9 @@ export function StatusBar({ onOpenAdmin, onChangeServer, serverName, currentRoom
     }
 
     const fetchTopProcesses = () => {
-      invoke<ProcessMemory[]>("get_top_processes", { limit: 10 })
-        .then((processes) => {
+      // Full: the rows show each process cwd
+      invoke<TopProcesses>("get_top_processes", { limit: 10, detailLevel: "full" })
+        .then(({ processes }) => {
           // Compute rank changes by comparing with previous ranks
           const changes = new Map<number, number>();
           processes.forEach((proc, newIndex) => {
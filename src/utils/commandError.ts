@@ -0,0 +1,17 @@
+// Errors returned by Tauri commands that use OrganizerError (src-tauri/src/lib.rs)
+export type CommandErrorKind = 'NotFound' | 'PermissionDenied' | 'Unsupported' | 'InvalidInput' | 'Io' | 'Parse';
+
+export interface CommandError {
+  kind: CommandErrorKind;
+  message: string;
+}
+
+export function isCommandError(err: unknown): err is CommandError {
+  return typeof err === 'object' && err !== null && 'kind' in err && 'message' in err;
+}
+
+export function formatCommandError(err: unknown): string {
+  if (isCommandError(err)) return err.message;
+  if (err instanceof Error) return err.message;
+  return String(err);
+}
@@ -3,6 +3,8 @@ import {
   requestPermission,
   sendNotification,
 } from "@tauri-apps/plugin-notification";
+import { invoke } from "@tauri-apps/api/core";
+import { listen, UnlistenFn } from "@tauri-apps/api/event";
 
 let permissionGranted = false;
 
@@ -26,6 +28,28 @@ export async function initNotifications(): Promise<boolean> {
   }
 }
 
+/**
+ * Prompt for notification permission when a backend monitor (memory, disk, thermal...)
+ * tried to alert while permission was never asked for. Returns the unlisten function.
+ * Desktop builds always report "granted" (see notification_permission_state), so this
+ * only fires on mobile.
+ */
+export function watchMonitorNotificationPermission(): Promise<UnlistenFn> {
+  let prompting = false;
+  return listen<string>("notifications:permission", async (event) => {
+    if (event.payload !== "default" || prompting) return;
+    prompting = true;
+    try {
+      const state = await invoke<string>("request_notification_permission");
+      permissionGranted = state === "granted";
+    } catch (err) {
+      console.error("Failed to request notification permission:", err);
+    } finally {
+      prompting = false;
+    }
+  });
+}
+
 /**
  * Show a desktop notification for a new message.
  * Stores the roomId so the app can navigate to it when the window gains focus